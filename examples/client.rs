@@ -8,10 +8,12 @@ use std::net::SocketAddr;
 use std::process;
 use std::time::Duration;
 
-use ironsight::client::{BinaryClient, BinaryEvent};
+use ironsight::alert::AlertMonitor;
+use ironsight::client::{BinaryClient, BinaryEvent, ReconnectPolicy};
 use ironsight::conn::DEFAULT_ADDR;
 use ironsight::protocol::camera::CamConfig;
-use ironsight::protocol::config::{ParamData, ParamValue, RadarCal, MODE_CHIPPING};
+use ironsight::protocol::config::{BallType, DetectionMode};
+use ironsight::protocol::notice::DeviceNotice;
 use ironsight::seq::AvrSettings;
 use ironsight::BinaryConnection;
 
@@ -40,6 +42,8 @@ fn run() -> Result<(), ironsight::ConnError> {
     conn.set_on_recv(|env| println!("<< {env:?}"));
 
     let mut client = BinaryClient::from_tcp(conn)?;
+    client.enable_reconnect(ReconnectPolicy::default())?;
+    client.set_alert_monitor(AlertMonitor::default());
 
     // Enqueue the full startup sequence.
     client.handshake();
@@ -49,7 +53,7 @@ fn run() -> Result<(), ironsight::ConnError> {
 
     println!("Starting poll loop...");
     loop {
-        if let Some(event) = client.poll()? {
+        if let Some(event) = client.poll_timeout(Duration::from_secs(1))? {
             match event {
                 BinaryEvent::Handshake(h) => {
                     println!("\n=== Handshake complete ===");
@@ -104,11 +108,50 @@ fn run() -> Result<(), ironsight::ConnError> {
                     }
                     println!("  === RE-ARMED ===");
                 }
+                BinaryEvent::ShotAborted { reason, partial } => {
+                    println!("\n  === Shot aborted ({reason:?}) ===");
+                    if let Some(ref e8) = partial {
+                        println!("    Partial: ball {:.1} mph", ms_to_mph(e8.ball_velocity));
+                    }
+                }
                 BinaryEvent::Disarmed => {
                     println!("\n=== Disarmed ===");
                 }
+                BinaryEvent::Reconnecting { attempt, delay } => {
+                    println!("\n=== Connection lost — reconnect #{attempt} in {delay:?} ===");
+                }
+                BinaryEvent::SettingsApplied => {
+                    println!("\n=== Settings applied — ARMED ===");
+                }
+                BinaryEvent::SettingsRolledBack(e) => {
+                    println!("\n=== Settings change failed ({e}) — previous settings restored ===");
+                }
+                BinaryEvent::SettingsFailed(e) => {
+                    println!("\n=== Settings change failed ({e}) — no previous settings to restore ===");
+                }
+                BinaryEvent::Reconnected => {
+                    println!("\n=== Reconnected — restoring session ===");
+                }
+                BinaryEvent::Notice {
+                    notice: DeviceNotice::AdcErrors(n),
+                    ..
+                } if n > 0 => {
+                    println!("  WARNING: {n} radar ADC errors");
+                }
+                BinaryEvent::Alert(alert) => {
+                    println!("\n=== ALERT: {alert:?} ===");
+                }
+                BinaryEvent::DspDebug(line) => {
+                    println!("  DSP [{:?}] {}", line.severity, line.text);
+                }
                 BinaryEvent::ShotDatum(_)
+                | BinaryEvent::Notice { .. }
+                | BinaryEvent::LevelReading(_)
+                | BinaryEvent::EpochOffset(_)
                 | BinaryEvent::Keepalive(_)
+                | BinaryEvent::StateChanged(_)
+                | BinaryEvent::ParamsRead(_)
+                | BinaryEvent::WifiScan(_)
                 | BinaryEvent::Message(_) => {
                     // on_recv callback already printed it
                 }
@@ -118,27 +161,13 @@ fn run() -> Result<(), ironsight::ConnError> {
 }
 
 fn default_avr_settings() -> AvrSettings {
-    AvrSettings {
-        mode: MODE_CHIPPING,
-        params: vec![
-            ParamValue {
-                param_id: 0x06,
-                value: ParamData::Int24(0),
-            },
-            ParamValue {
-                param_id: 0x0F,
-                value: ParamData::Float40(1.0),
-            },
-            ParamValue {
-                param_id: 0x26,
-                value: ParamData::Float40(0.0381),
-            },
-        ],
-        radar_cal: Some(RadarCal {
-            range_mm: 2743,
-            height_mm: 25,
-        }),
-    }
+    AvrSettings::builder(DetectionMode::Chipping)
+        .ball_type(BallType::Rct)
+        .min_track_pct(1.0)
+        .tee_height_in(1.5)
+        .radar_cal_ft(9.0, 1.0)
+        .build()
+        .expect("settings in range")
 }
 
 fn default_cam_config() -> CamConfig {
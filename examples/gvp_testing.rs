@@ -17,7 +17,7 @@ use std::process;
 use std::thread;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
-use ironsight::client::{BinaryClient, BinaryEvent};
+use ironsight::client::{BinaryClient, BinaryEvent, HandshakeOutcome};
 use ironsight::conn::DEFAULT_ADDR;
 use ironsight::gvp::client::GvpClient;
 use ironsight::gvp::config::GvpConfig;
@@ -563,11 +563,16 @@ fn run() -> Result<(), ConnError> {
     println!("PI sync...");
     let pi = seq::sync_pi(&mut conn)?;
 
+    let device = HandshakeOutcome { dsp, avr, pi };
+    let caps = device.capabilities();
+
     println!("\n--- Handshake complete ---");
-    println!("  DSP: {}", dsp.dev_info.text);
-    println!("  AVR: {}", avr.dev_info.text);
-    println!("  PI:  {}", pi.dev_info.text);
-    println!("  Battery: {}%", dsp.status.battery_percent());
+    println!("  DSP: {}", device.dsp.dev_info.text);
+    println!("  AVR: {}", device.avr.dev_info.text);
+    println!("  PI:  {}", device.pi.dev_info.text);
+    if caps.power_status {
+        println!("  Battery: {}%", device.dsp.status.battery_percent());
+    }
 
     let settings = AvrSettings {
         mode: MODE_CHIPPING,
@@ -597,9 +602,9 @@ fn run() -> Result<(), ConnError> {
     println!("\nPhase A: Standard camera warmup...");
     start_camera(&mut conn, &CamConfig::standard_preset(), "std")?;
 
-    let fusion_config = CamConfig::raw_fusion_preset();
+    let fusion_config = caps.fusion_preset();
     println!(
-        "Phase B: Raw Fusion ({}x{}, raw_mode={}, fusion_camera_mode={})...",
+        "Phase B: Fusion ({}x{}, raw_mode={}, fusion_camera_mode={})...",
         fusion_config.resolution_width,
         fusion_config.resolution_height,
         fusion_config.raw_camera_mode,
@@ -655,6 +660,10 @@ fn run() -> Result<(), ConnError> {
                     let _ = gvp.send_config(&GvpConfig::fusion());
                 }
 
+                BinaryEvent::ShotAborted { reason, .. } => {
+                    println!("=== SHOT ABORTED ({reason:?}) guid={shot_guid} ===");
+                }
+
                 BinaryEvent::ShotComplete(data) => {
                     // Send trajectory hints if not already sent from pre-PROCESSED data.
                     if !hints_sent {
@@ -779,7 +788,20 @@ fn run() -> Result<(), ConnError> {
                 | BinaryEvent::Disarmed
                 | BinaryEvent::Handshake(_)
                 | BinaryEvent::ShotDatum(_)
-                | BinaryEvent::Keepalive(_) => {}
+                | BinaryEvent::Keepalive(_)
+                | BinaryEvent::ParamsRead(_)
+                | BinaryEvent::WifiScan(_)
+                | BinaryEvent::Alert(_)
+                | BinaryEvent::LevelReading(_)
+                | BinaryEvent::EpochOffset(_)
+                | BinaryEvent::SettingsApplied
+                | BinaryEvent::SettingsRolledBack(_)
+                | BinaryEvent::SettingsFailed(_)
+                | BinaryEvent::Notice { .. }
+                | BinaryEvent::DspDebug(_)
+                | BinaryEvent::Reconnecting { .. }
+                | BinaryEvent::Reconnected
+                | BinaryEvent::StateChanged(_) => {}
             }
         }
 
@@ -0,0 +1,303 @@
+//! Threshold alerts from keepalive status.
+//!
+//! [`AlertMonitor`] watches each [`StatusSnapshot`] for battery level,
+//! external power, temperature and tilt/roll, and reports transitions as
+//! [`Alert`]s. Every threshold has a hysteresis band so a value hovering
+//! at the limit does not flap: an alert fires when the limit is crossed
+//! and clears only once the value is back past limit ± hysteresis.
+//!
+//! Install one with [`BinaryClient::set_alert_monitor`]; alerts are then
+//! emitted as [`BinaryEvent::Alert`] after each keepalive.
+//!
+//! [`BinaryClient::set_alert_monitor`]: crate::client::BinaryClient::set_alert_monitor
+//! [`BinaryEvent::Alert`]: crate::client::BinaryEvent::Alert
+
+use crate::client::StatusSnapshot;
+use crate::protocol::status::DspStatus;
+
+/// Alert thresholds. All comparisons are inclusive.
+#[derive(Debug, Clone)]
+pub struct AlertThresholds {
+    /// Battery percentage at or below which `BatteryLow` fires (default: 20).
+    pub battery_low_pct: u8,
+    /// Battery percentage at or below which `BatteryCritical` fires
+    /// (default: 10).
+    pub battery_critical_pct: u8,
+    /// Percentage points above a battery threshold before it clears
+    /// (default: 3).
+    pub battery_hysteresis_pct: u8,
+    /// Temperature (°C) at or above which `Overheating` fires (default: 60).
+    pub overheat_c: f64,
+    /// Degrees below `overheat_c` before it clears (default: 3).
+    pub temperature_hysteresis_c: f64,
+    /// Largest acceptable |tilt| in degrees (default: 5).
+    pub max_tilt_deg: f64,
+    /// Largest acceptable |roll| in degrees (default: 3).
+    pub max_roll_deg: f64,
+    /// Degrees inside the tilt/roll limits before `OutOfLevel` clears
+    /// (default: 0.5).
+    pub level_hysteresis_deg: f64,
+}
+
+impl Default for AlertThresholds {
+    fn default() -> Self {
+        Self {
+            battery_low_pct: 20,
+            battery_critical_pct: 10,
+            battery_hysteresis_pct: 3,
+            overheat_c: 60.0,
+            temperature_hysteresis_c: 3.0,
+            max_tilt_deg: 5.0,
+            max_roll_deg: 3.0,
+            level_hysteresis_deg: 0.5,
+        }
+    }
+}
+
+/// A device condition starting or clearing.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Alert {
+    /// Battery fell to `battery_low_pct` (or recovered from critical to
+    /// low).
+    BatteryLow { percent: u8 },
+    /// Battery fell to `battery_critical_pct`.
+    BatteryCritical { percent: u8 },
+    /// Battery back above the low threshold.
+    BatteryOk { percent: u8 },
+    /// External power plugged in.
+    PowerConnected,
+    /// External power unplugged; running on battery.
+    PowerDisconnected,
+    /// Temperature reached `overheat_c`.
+    Overheating { celsius: f64 },
+    /// Temperature back below the overheat threshold.
+    TemperatureOk { celsius: f64 },
+    /// Tilt or roll outside the configured limits.
+    OutOfLevel { tilt: f64, roll: f64 },
+    /// Tilt and roll back within limits.
+    Level { tilt: f64, roll: f64 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Battery {
+    Ok,
+    Low,
+    Critical,
+}
+
+/// Stateful threshold monitor over successive [`StatusSnapshot`]s.
+#[derive(Debug, Clone)]
+pub struct AlertMonitor {
+    thresholds: AlertThresholds,
+    battery: Battery,
+    external_power: Option<bool>,
+    overheating: bool,
+    out_of_level: bool,
+}
+
+impl Default for AlertMonitor {
+    fn default() -> Self {
+        Self::new(AlertThresholds::default())
+    }
+}
+
+impl AlertMonitor {
+    #[must_use]
+    pub fn new(thresholds: AlertThresholds) -> Self {
+        Self {
+            thresholds,
+            battery: Battery::Ok,
+            external_power: None,
+            overheating: false,
+            out_of_level: false,
+        }
+    }
+
+    #[must_use]
+    pub fn thresholds(&self) -> &AlertThresholds {
+        &self.thresholds
+    }
+
+    /// Check a snapshot and return the alerts for every condition that
+    /// started or cleared since the previous one.
+    ///
+    /// Battery, power and DSP temperature come from the Gen1 (0x80) DSP
+    /// status only; the Gen2 layout is not decoded, so Gen2 temperature
+    /// falls back to the AVR status. A low battery or bad level present
+    /// in the first snapshot is reported; the initial power state is not.
+    pub fn update(&mut self, status: &StatusSnapshot) -> Vec<Alert> {
+        let mut alerts = Vec::new();
+        let dsp = match &status.dsp {
+            Some(s @ DspStatus::V80(_)) => Some(s),
+            _ => None,
+        };
+        if let Some(dsp) = dsp {
+            self.check_battery(dsp.battery_percent(), &mut alerts);
+            let power = dsp.external_power();
+            if let Some(prev) = self.external_power.replace(power)
+                && prev != power
+            {
+                alerts.push(if power {
+                    Alert::PowerConnected
+                } else {
+                    Alert::PowerDisconnected
+                });
+            }
+        }
+        let celsius = dsp
+            .map(DspStatus::temperature_c)
+            .or(status.avr.as_ref().map(|a| a.temperature));
+        if let Some(celsius) = celsius {
+            self.check_temperature(celsius, &mut alerts);
+        }
+        if let Some(avr) = &status.avr {
+            self.check_level(avr.tilt, avr.roll, &mut alerts);
+        }
+        alerts
+    }
+
+    fn check_battery(&mut self, percent: u8, alerts: &mut Vec<Alert>) {
+        let t = &self.thresholds;
+        let below = |limit: u8| percent < limit.saturating_add(t.battery_hysteresis_pct);
+        let next = match self.battery {
+            Battery::Critical if below(t.battery_critical_pct) => Battery::Critical,
+            _ if percent <= t.battery_critical_pct => Battery::Critical,
+            Battery::Low | Battery::Critical if below(t.battery_low_pct) => Battery::Low,
+            _ if percent <= t.battery_low_pct => Battery::Low,
+            _ => Battery::Ok,
+        };
+        if next == self.battery {
+            return;
+        }
+        self.battery = next;
+        alerts.push(match next {
+            Battery::Ok => Alert::BatteryOk { percent },
+            Battery::Low => Alert::BatteryLow { percent },
+            Battery::Critical => Alert::BatteryCritical { percent },
+        });
+    }
+
+    fn check_temperature(&mut self, celsius: f64, alerts: &mut Vec<Alert>) {
+        let t = &self.thresholds;
+        if !self.overheating && celsius >= t.overheat_c {
+            self.overheating = true;
+            alerts.push(Alert::Overheating { celsius });
+        } else if self.overheating && celsius <= t.overheat_c - t.temperature_hysteresis_c {
+            self.overheating = false;
+            alerts.push(Alert::TemperatureOk { celsius });
+        }
+    }
+
+    fn check_level(&mut self, tilt: f64, roll: f64, alerts: &mut Vec<Alert>) {
+        let t = &self.thresholds;
+        let within = |margin: f64| {
+            tilt.abs() <= t.max_tilt_deg - margin && roll.abs() <= t.max_roll_deg - margin
+        };
+        if !self.out_of_level && !within(0.0) {
+            self.out_of_level = true;
+            alerts.push(Alert::OutOfLevel { tilt, roll });
+        } else if self.out_of_level && within(t.level_hysteresis_deg) {
+            self.out_of_level = false;
+            alerts.push(Alert::Level { tilt, roll });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::status::{AvrStatus, DspStatus46, DspStatus80};
+
+    fn snapshot(percent: u8, power: bool, celsius: f64, tilt: f64, roll: f64) -> StatusSnapshot {
+        StatusSnapshot {
+            dsp: Some(DspStatus::V80(DspStatus80 {
+                state: 6,
+                input_voltage_usb: 0,
+                system_voltage: 0,
+                battery_current: 0,
+                temperature_raw: (celsius * 100.0) as i16,
+                battery_voltage: 0,
+                battery_voltage_2: 0,
+                power_level: i16::from(percent) << 8,
+                external_power: power,
+            })),
+            avr: Some(AvrStatus {
+                version: 0x18,
+                state: 1,
+                hw_id_hi: 0,
+                hw_id_lo: 0,
+                full_app_id: 0,
+                temperature: celsius,
+                tilt,
+                roll,
+            }),
+            ..StatusSnapshot::default()
+        }
+    }
+
+    fn battery(m: &mut AlertMonitor, percent: u8) -> Vec<Alert> {
+        m.update(&snapshot(percent, false, 40.0, 0.0, 0.0))
+    }
+
+    #[test]
+    fn battery_thresholds_with_hysteresis() {
+        let mut m = AlertMonitor::default();
+        assert!(battery(&mut m, 50).is_empty());
+        assert_eq!(battery(&mut m, 20), [Alert::BatteryLow { percent: 20 }]);
+        // Hovering at the limit does not flap.
+        assert!(battery(&mut m, 21).is_empty());
+        assert!(battery(&mut m, 20).is_empty());
+        assert_eq!(battery(&mut m, 9), [Alert::BatteryCritical { percent: 9 }]);
+        assert!(battery(&mut m, 12).is_empty());
+        assert_eq!(battery(&mut m, 13), [Alert::BatteryLow { percent: 13 }]);
+        assert_eq!(battery(&mut m, 23), [Alert::BatteryOk { percent: 23 }]);
+    }
+
+    #[test]
+    fn initial_low_battery_is_reported() {
+        let mut m = AlertMonitor::default();
+        assert_eq!(battery(&mut m, 8), [Alert::BatteryCritical { percent: 8 }]);
+    }
+
+    #[test]
+    fn power_temperature_and_level() {
+        let mut m = AlertMonitor::default();
+        assert!(m.update(&snapshot(80, false, 40.0, 0.0, 0.0)).is_empty());
+        assert_eq!(
+            m.update(&snapshot(80, true, 61.0, 4.0, -3.5)),
+            [
+                Alert::PowerConnected,
+                Alert::Overheating { celsius: 61.0 },
+                Alert::OutOfLevel {
+                    tilt: 4.0,
+                    roll: -3.5
+                },
+            ]
+        );
+        // Inside the limits but not past the hysteresis bands.
+        assert!(m.update(&snapshot(80, true, 58.0, 4.0, 2.8)).is_empty());
+        assert_eq!(
+            m.update(&snapshot(80, false, 57.0, 1.0, 0.2)),
+            [
+                Alert::PowerDisconnected,
+                Alert::TemperatureOk { celsius: 57.0 },
+                Alert::Level {
+                    tilt: 1.0,
+                    roll: 0.2
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn gen2_skips_undecoded_dsp_fields() {
+        let mut m = AlertMonitor::default();
+        let mut s = snapshot(0, false, 65.0, 0.0, 0.0);
+        s.dsp = Some(DspStatus::V46(DspStatus46 {
+            state: 6,
+            version: 0x46,
+            payload: vec![0; 71],
+        }));
+        assert_eq!(m.update(&s), [Alert::Overheating { celsius: 65.0 }]);
+    }
+}
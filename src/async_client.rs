@@ -0,0 +1,501 @@
+//! Async client for tokio (`async` feature).
+//!
+//! [`AsyncBinaryClient`] runs the same [`BinaryClient`] state machine — and
+//! therefore the same [`Sequence`](crate::seq::Sequence) sequencers — over a
+//! `tokio::io::AsyncRead + AsyncWrite` stream. Instead of a busy
+//! [`poll()`](BinaryClient::poll) loop, [`next_event()`](AsyncBinaryClient::next_event)
+//! sleeps until either data arrives or the client's next timer falls due
+//! (operation deadline, keepalive, calibration or drain timeout; see
+//! [`BinaryClient::next_deadline`]).
+//!
+//! Internally the sync client talks to an in-memory bridge stream;
+//! `next_event()` moves bytes between the bridge and the real socket.
+//! With the `gvp` feature, [`AsyncGvpClient`](crate::gvp::async_client::AsyncGvpClient)
+//! does the same for the camera protocol.
+//!
+//! [`set_reconnect()`](AsyncBinaryClient::set_reconnect) and
+//! [`enable_reconnect()`](AsyncBinaryClient::enable_reconnect) mirror the
+//! sync reconnect, with an async dial.
+//!
+//! ```no_run
+//! use ironsight::async_client::AsyncBinaryClient;
+//! use ironsight::BinaryEvent;
+//!
+//! # async fn run() -> Result<(), ironsight::ConnError> {
+//! let mut client = AsyncBinaryClient::connect(ironsight::conn::DEFAULT_ADDR).await?;
+//! client.handshake();
+//! loop {
+//!     match client.next_event().await? {
+//!         BinaryEvent::Handshake(h) => {
+//!             println!("Connected to {}", h.pi.ssid);
+//!             client.arm();
+//!         }
+//!         BinaryEvent::ShotComplete(shot) => println!("{shot:?}"),
+//!         _ => {}
+//!     }
+//! }
+//! # }
+//! ```
+
+use std::collections::VecDeque;
+use std::future::Future;
+use std::io::{self, Read, Write};
+use std::pin::Pin;
+use std::time::{Duration, Instant};
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::{TcpStream, ToSocketAddrs};
+
+use crate::addr::BusAddr;
+use crate::alert::AlertMonitor;
+use crate::capabilities::Capabilities;
+use crate::client::{
+    BinaryClient, BinaryEvent, ClientState, HandshakeOutcome, RECONNECT_TIMEOUT, ReconnectPolicy,
+    StatusSnapshot,
+};
+use crate::epoch::{EpochMap, EpochMapPolicy};
+use crate::conn::{BinaryConnection, ConnError, Envelope, SendHooks, Timed};
+use crate::health::LinkHealth;
+use crate::level::LevelAssist;
+use crate::protocol::Command;
+use crate::protocol::camera::CamConfig;
+use crate::protocol::config::DetectionMode;
+use crate::seq::AvrSettings;
+
+// ---------------------------------------------------------------------------
+// Bridge
+// ---------------------------------------------------------------------------
+
+/// In-memory `Read + Write` stream between a sync client and an async socket.
+///
+/// Reads drain `inbound` (filled from the socket) and report `WouldBlock`
+/// when it is empty (EOF once [closed](Bridge::close)); writes append to
+/// `outbound` (flushed to the socket).
+#[derive(Debug, Default)]
+pub(crate) struct Bridge {
+    inbound: VecDeque<u8>,
+    outbound: VecDeque<u8>,
+    /// Set when a read found `inbound` empty: the client has consumed
+    /// everything and needs more data before it can make progress.
+    starved: bool,
+    /// The socket failed; reads report EOF once `inbound` is drained.
+    closed: bool,
+}
+
+impl Read for Bridge {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.inbound.is_empty() {
+            if self.closed {
+                return Ok(0);
+            }
+            self.starved = true;
+            return Err(io::ErrorKind::WouldBlock.into());
+        }
+        let n = buf.len().min(self.inbound.len());
+        for (dst, src) in buf.iter_mut().zip(self.inbound.drain(..n)) {
+            *dst = src;
+        }
+        Ok(n)
+    }
+}
+
+impl Write for Bridge {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.outbound.extend(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Bridge {
+    /// Write all buffered outbound bytes to `io`. Cancel-safe: bytes leave
+    /// the buffer only once the socket has accepted them.
+    pub(crate) async fn flush_to<S: AsyncWrite + Unpin>(&mut self, io: &mut S) -> io::Result<()> {
+        while !self.outbound.is_empty() {
+            let (head, _) = self.outbound.as_slices();
+            let n = io.write(head).await?;
+            if n == 0 {
+                return Err(io::ErrorKind::WriteZero.into());
+            }
+            self.outbound.drain(..n);
+        }
+        io.flush().await
+    }
+
+    /// Read once from `io` into the inbound buffer, optionally giving up at
+    /// `deadline`. Returns `Ok(false)` on timeout and `UnexpectedEof` when
+    /// the peer closed. Cancel-safe.
+    pub(crate) async fn fill_from<S: AsyncRead + Unpin>(
+        &mut self,
+        io: &mut S,
+        buf: &mut [u8],
+        deadline: Option<Instant>,
+    ) -> io::Result<bool> {
+        let read = io.read(buf);
+        let n = match deadline {
+            Some(d) => match tokio::time::timeout_at(d.into(), read).await {
+                Ok(r) => r?,
+                Err(_) => return Ok(false),
+            },
+            None => read.await?,
+        };
+        if n == 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        self.inbound.extend(&buf[..n]);
+        self.starved = false;
+        Ok(true)
+    }
+
+    /// Clear the starved flag before a round of sync polling.
+    pub(crate) fn begin_poll(&mut self) {
+        self.starved = false;
+    }
+
+    pub(crate) fn is_starved(&self) -> bool {
+        self.starved
+    }
+
+    /// Mark the socket as failed so the client sees the disconnect. Unsent
+    /// bytes are dropped.
+    pub(crate) fn close(&mut self) {
+        self.closed = true;
+        self.outbound.clear();
+    }
+}
+
+// ---------------------------------------------------------------------------
+// AsyncBinaryClient
+// ---------------------------------------------------------------------------
+
+/// Opens a replacement stream for [`AsyncBinaryClient::set_reconnect`].
+type Connect<S> =
+    Box<dyn FnMut() -> Pin<Box<dyn Future<Output = Result<S, ConnError>> + Send>> + Send>;
+
+/// Async wrapper around [`BinaryClient`] for tokio streams.
+///
+/// Operation enqueuers and accessors mirror `BinaryClient`; events come
+/// from [`next_event()`](Self::next_event) instead of `poll()`.
+pub struct AsyncBinaryClient<S> {
+    io: S,
+    client: BinaryClient<Bridge, SendHooks>,
+    read_buf: Box<[u8; 4096]>,
+    /// Poll result waiting on an outbound flush.
+    ready: Option<Result<Timed<BinaryEvent>, ConnError>>,
+    connect: Option<Connect<S>>,
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> AsyncBinaryClient<S> {
+    /// Wrap a connected async stream.
+    pub fn new(io: S) -> Self {
+        Self {
+            io,
+            client: BinaryClient::new(BinaryConnection::with_hooks(Bridge::default())),
+            read_buf: Box::new([0u8; 4096]),
+            ready: None,
+            connect: None,
+        }
+    }
+
+    /// Wait for the next event.
+    ///
+    /// Drives the wrapped client until it yields an event, flushing its
+    /// writes to the socket and sleeping until data arrives or
+    /// [`BinaryClient::next_deadline`] passes. Cancel-safe: dropping the
+    /// future (e.g. in `tokio::select!`) loses no bytes or events; a
+    /// reconnect dial in progress is dropped and retried on the next call.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`BinaryClient::poll`]. A closed socket is
+    /// [`ConnError::Disconnected`].
+    pub async fn next_event(&mut self) -> Result<BinaryEvent, ConnError> {
+        Ok(self.next_event_timed().await?.event)
+    }
+
+    /// Like [`next_event()`](Self::next_event), with the host time behind
+    /// the event. See [`BinaryClient::poll_timed`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`next_event()`](Self::next_event).
+    pub async fn next_event_timed(&mut self) -> Result<Timed<BinaryEvent>, ConnError> {
+        loop {
+            // Stash the poll result before awaiting the flush so a cancelled
+            // flush doesn't drop the event; the next call returns it.
+            if self.ready.is_none() {
+                if let Some(connect) = self.connect.as_mut()
+                    && self.client.reconnect_due()
+                {
+                    let result = connect().await;
+                    self.ready = self.reconnect_with(result).transpose();
+                } else {
+                    self.bridge().begin_poll();
+                    self.ready = self.client.poll_timed().transpose();
+                }
+            }
+            let bridge = self.client.conn_mut().stream_mut();
+            if let Err(e) = bridge.flush_to(&mut self.io).await {
+                self.socket_failed(e)?;
+            }
+            if let Some(result) = self.ready.take() {
+                return result;
+            }
+            let deadline = self.client.next_deadline();
+            if self.client.is_reconnecting() {
+                // The old socket is dead; wait for the next attempt.
+                if let Some(deadline) = deadline {
+                    tokio::time::sleep_until(deadline.into()).await;
+                }
+                continue;
+            }
+            if !self.bridge().is_starved() {
+                // Consumed a message without emitting; more may be buffered.
+                continue;
+            }
+            let bridge = self.client.conn_mut().stream_mut();
+            if let Err(e) = bridge
+                .fill_from(&mut self.io, &mut self.read_buf[..], deadline)
+                .await
+            {
+                self.socket_failed(e)?;
+            }
+        }
+    }
+
+    /// Reconnect automatically when the connection drops.
+    ///
+    /// Same session replay as [`BinaryClient::set_reconnect`]; `connect`
+    /// dials the replacement stream. For TCP,
+    /// [`enable_reconnect()`](AsyncBinaryClient::enable_reconnect)
+    /// re-dials the current peer.
+    pub fn set_reconnect<F, Fut>(&mut self, policy: ReconnectPolicy, mut connect: F)
+    where
+        F: FnMut() -> Fut + Send + 'static,
+        Fut: Future<Output = Result<S, ConnError>> + Send + 'static,
+    {
+        self.connect = Some(Box::new(move || Box::pin(connect())));
+        self.client.set_reconnect_policy(policy);
+    }
+
+    /// Whether the connection is down and waiting for a reconnect attempt.
+    #[must_use]
+    pub fn is_reconnecting(&self) -> bool {
+        self.client.is_reconnecting()
+    }
+
+    /// Register a callback invoked for every command sent.
+    pub fn set_on_send(&mut self, f: impl FnMut(&Command, BusAddr) + Send + 'static) {
+        self.client.conn_mut().set_on_send(f);
+    }
+
+    /// Register a callback invoked after every successful frame decode.
+    pub fn set_on_recv(&mut self, f: impl FnMut(&Envelope) + Send + 'static) {
+        self.client.conn_mut().set_on_recv(f);
+    }
+
+    /// Borrow the underlying async stream.
+    pub fn stream(&self) -> &S {
+        &self.io
+    }
+
+    fn bridge(&mut self) -> &mut Bridge {
+        self.client.conn_mut().stream_mut()
+    }
+
+    /// Hand a dialled stream (or the dial error) to the client.
+    fn reconnect_with(
+        &mut self,
+        result: Result<S, ConnError>,
+    ) -> Result<Option<Timed<BinaryEvent>>, ConnError> {
+        let result = result.map(|io| {
+            self.io = io;
+            Bridge::default()
+        });
+        self.client.reconnect_with(result)
+    }
+
+    /// A socket read or write failed. With reconnect enabled the client
+    /// sees it as a disconnect on its next poll; otherwise it is returned.
+    fn socket_failed(&mut self, e: io::Error) -> Result<(), ConnError> {
+        if self.connect.is_some() {
+            self.bridge().close();
+            return Ok(());
+        }
+        if e.kind() == io::ErrorKind::UnexpectedEof {
+            return Err(ConnError::Disconnected);
+        }
+        Err(ConnError::Io(e))
+    }
+
+    // -- Operation enqueuers (see BinaryClient) ------------------------------
+
+    /// Enqueue a three-phase handshake. See [`BinaryClient::handshake`].
+    pub fn handshake(&mut self) {
+        self.client.handshake();
+    }
+
+    /// Enqueue AVR configuration. See [`BinaryClient::configure_avr`].
+    pub fn configure_avr(&mut self, avr: AvrSettings) {
+        self.client.configure_avr(avr);
+    }
+
+    /// Enqueue camera configuration. See [`BinaryClient::configure_cam`].
+    pub fn configure_cam(&mut self, cam: CamConfig) {
+        self.client.configure_cam(cam);
+    }
+
+    /// Enqueue arming the device. See [`BinaryClient::arm`].
+    pub fn arm(&mut self) {
+        self.client.arm();
+    }
+
+    /// Enqueue disarming the device. See [`BinaryClient::disarm`].
+    pub fn disarm(&mut self) {
+        self.client.disarm();
+    }
+
+    /// Enqueue an atomic settings change. See [`BinaryClient::apply_settings`].
+    pub fn apply_settings(&mut self, avr: AvrSettings) {
+        self.client.apply_settings(avr);
+    }
+
+    /// Enqueue a detection mode change. See [`BinaryClient::set_mode`].
+    pub fn set_mode(&mut self, mode: DetectionMode) {
+        self.client.set_mode(mode);
+    }
+
+    /// Enqueue a parameter read. See [`BinaryClient::read_params`].
+    pub fn read_params(&mut self, ids: &[u8], dest: BusAddr) {
+        self.client.read_params(ids, dest);
+    }
+
+    /// Enqueue a WiFi scan. See [`BinaryClient::scan_wifi`].
+    pub fn scan_wifi(&mut self) {
+        self.client.scan_wifi();
+    }
+
+    // -- Accessors and configuration -----------------------------------------
+
+    /// Latest cached status from keepalive responses.
+    #[must_use]
+    pub fn status(&self) -> &StatusSnapshot {
+        self.client.status()
+    }
+
+    /// Link health metrics. See [`BinaryClient::health`].
+    #[must_use]
+    pub fn health(&self) -> &LinkHealth {
+        self.client.health()
+    }
+
+    /// Handshake outcome, available after `BinaryEvent::Handshake`.
+    #[must_use]
+    pub fn device(&self) -> Option<&HandshakeOutcome> {
+        self.client.device()
+    }
+
+    /// Device capabilities. See [`BinaryClient::capabilities`].
+    #[must_use]
+    pub fn capabilities(&self) -> Option<Capabilities> {
+        self.client.capabilities()
+    }
+
+    /// Whether the device is currently armed.
+    #[must_use]
+    pub fn is_armed(&self) -> bool {
+        self.client.is_armed()
+    }
+
+    /// Current coarse client state. See [`BinaryClient::state`].
+    #[must_use]
+    pub fn state(&self) -> ClientState {
+        self.client.state()
+    }
+
+    /// Set the keepalive polling interval (default: 1s).
+    pub fn set_keepalive_interval(&mut self, interval: Duration) {
+        self.client.set_keepalive_interval(interval);
+    }
+
+    /// Set the operation timeout (default: 30s).
+    pub fn set_operation_timeout(&mut self, timeout: Duration) {
+        self.client.set_operation_timeout(timeout);
+    }
+
+    /// Set the trigger timeout. See [`BinaryClient::set_trigger_timeout`].
+    pub fn set_trigger_timeout(&mut self, timeout: Duration) {
+        self.client.set_trigger_timeout(timeout);
+    }
+
+    /// Re-arm after a trigger timeout. See
+    /// [`BinaryClient::set_rearm_after_abort`].
+    pub fn set_rearm_after_abort(&mut self, rearm: bool) {
+        self.client.set_rearm_after_abort(rearm);
+    }
+
+    /// Start level assist. See [`BinaryClient::start_level_assist`].
+    pub fn start_level_assist(&mut self, config: LevelAssist) {
+        self.client.start_level_assist(config);
+    }
+
+    /// Stop level assist. See [`BinaryClient::stop_level_assist`].
+    pub fn stop_level_assist(&mut self) {
+        self.client.stop_level_assist();
+    }
+
+    /// Map device epochs from shot text to host time. See
+    /// [`BinaryClient::enable_epoch_map`].
+    pub fn enable_epoch_map(&mut self, policy: EpochMapPolicy) {
+        self.client.enable_epoch_map(policy);
+    }
+
+    /// Stop epoch mapping. See [`BinaryClient::disable_epoch_map`].
+    pub fn disable_epoch_map(&mut self) {
+        self.client.disable_epoch_map();
+    }
+
+    /// The epoch map. See [`BinaryClient::epoch_map`].
+    #[must_use]
+    pub fn epoch_map(&self) -> Option<&EpochMap> {
+        self.client.epoch_map()
+    }
+
+    /// Emit alerts from keepalive status. See
+    /// [`BinaryClient::set_alert_monitor`].
+    pub fn set_alert_monitor(&mut self, monitor: AlertMonitor) {
+        self.client.set_alert_monitor(monitor);
+    }
+}
+
+impl AsyncBinaryClient<TcpStream> {
+    /// Connect to a Mevo+ device over tokio TCP.
+    pub async fn connect(addr: impl ToSocketAddrs) -> Result<Self, ConnError> {
+        let stream = TcpStream::connect(addr).await?;
+        let _ = stream.set_nodelay(true);
+        Ok(Self::new(stream))
+    }
+
+    /// Reconnect to the current peer when the connection drops.
+    ///
+    /// TCP shorthand for [`set_reconnect()`](Self::set_reconnect): each
+    /// attempt connects to the same address with a 5s timeout.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the peer address can't be read from the socket.
+    pub fn enable_reconnect(&mut self, policy: ReconnectPolicy) -> Result<(), ConnError> {
+        let addr = self.io.peer_addr()?;
+        self.set_reconnect(policy, move || async move {
+            let stream = tokio::time::timeout(RECONNECT_TIMEOUT, TcpStream::connect(addr))
+                .await
+                .map_err(|_| io::Error::from(io::ErrorKind::TimedOut))??;
+            let _ = stream.set_nodelay(true);
+            Ok(stream)
+        });
+        Ok(())
+    }
+}
@@ -5,15 +5,18 @@
 
 use std::io::Write;
 use std::process::ExitCode;
-use std::thread;
 use std::time::Duration;
 
-use ironsight::client::{BinaryClient, BinaryEvent};
+use ironsight::alert::AlertMonitor;
+use ironsight::client::{BinaryClient, BinaryEvent, ReconnectPolicy};
 use ironsight::conn::{BinaryConnection, DEFAULT_ADDR};
 use ironsight::frp::FrpServer;
 use ironsight::protocol::config;
 use ironsight::seq::AvrSettings;
 
+/// How long to block on the Mevo socket before checking the controller.
+const CONTROLLER_POLL: Duration = Duration::from_millis(50);
+
 fn main() -> ExitCode {
     let mevo_addr = std::env::args()
         .nth(1)
@@ -59,12 +62,20 @@ fn main() -> ExitCode {
         }
     };
 
+    if let Err(e) = client.enable_reconnect(ReconnectPolicy::default()) {
+        eprintln!("ironsight-frp: failed to enable reconnect: {e}");
+        return ExitCode::FAILURE;
+    }
+    client.set_alert_monitor(AlertMonitor::default());
+
     client.handshake();
 
     let mut armed = false;
+    // The client replays config + arm itself after a reconnect.
+    let mut restoring = false;
 
     loop {
-        match client.poll() {
+        match client.poll_timeout(CONTROLLER_POLL) {
             Ok(Some(event)) => {
                 match &event {
                     BinaryEvent::Handshake(h) => {
@@ -75,17 +86,40 @@ fn main() -> ExitCode {
                         }
 
                         // Configure for indoor full-swing and arm
-                        client.configure_avr(AvrSettings {
-                            mode: config::MODE_INDOOR,
-                            params: vec![],
-                            radar_cal: None,
-                        });
-                        client.arm();
+                        if !restoring {
+                            client.configure_avr(AvrSettings {
+                                mode: config::MODE_INDOOR,
+                                params: vec![],
+                                radar_cal: None,
+                            });
+                            client.arm();
+                        }
                     }
                     BinaryEvent::Armed => {
                         armed = true;
+                        restoring = false;
                         eprintln!("ironsight-frp: armed");
                     }
+                    BinaryEvent::SettingsApplied => {
+                        armed = true;
+                        eprintln!("ironsight-frp: mode change applied, armed");
+                    }
+                    BinaryEvent::SettingsRolledBack(e) => {
+                        armed = true;
+                        eprintln!("ironsight-frp: mode change failed ({e}), previous mode restored");
+                    }
+                    BinaryEvent::SettingsFailed(e) => {
+                        armed = true;
+                        eprintln!("ironsight-frp: mode change failed ({e}), nothing to restore");
+                    }
+                    BinaryEvent::Reconnecting { attempt, delay } => {
+                        armed = false;
+                        eprintln!("ironsight-frp: Mevo connection lost, retry #{attempt} in {delay:?}");
+                    }
+                    BinaryEvent::Reconnected => {
+                        restoring = true;
+                        eprintln!("ironsight-frp: reconnected, restoring session");
+                    }
                     BinaryEvent::Trigger => {
                         eprintln!("ironsight-frp: shot triggered");
                     }
@@ -97,6 +131,12 @@ fn main() -> ExitCode {
                             );
                         }
                     }
+                    BinaryEvent::ShotAborted { reason, .. } => {
+                        eprintln!("ironsight-frp: shot aborted ({reason:?})");
+                    }
+                    BinaryEvent::Alert(alert) => {
+                        eprintln!("ironsight-frp: device alert: {alert:?}");
+                    }
                     BinaryEvent::Keepalive(_) => {}
                     _ => {}
                 }
@@ -109,17 +149,9 @@ fn main() -> ExitCode {
                 // Check for controller commands
                 if let Some(mode) = frp.check_controller() {
                     eprintln!("ironsight-frp: detection mode → {mode}");
-                    let avr_mode = ironsight::frp::detection_mode_to_avr(mode);
-                    client.configure_avr(AvrSettings {
-                        mode: avr_mode,
-                        params: vec![],
-                        radar_cal: None,
-                    });
-                    client.arm();
+                    client.set_mode(ironsight::frp::detection_mode_to_avr_mode(mode));
                     armed = false;
                 }
-
-                thread::sleep(Duration::from_millis(1));
             }
             Err(e) => {
                 eprintln!("ironsight-frp: poll error: {e}");
@@ -0,0 +1,94 @@
+//! What a connected unit can do, by hardware generation and firmware.
+//!
+//! Mevo+ and Gen2 hardware, and successive firmware releases, differ in
+//! ways that matter to a client: which status fields are decoded, which
+//! camera preset the PI expects, whether IDLE reliably follows a shot.
+//! [`Capabilities`] collects those differences in one place, derived from
+//! the [`HandshakeOutcome`], so sequencers and apps check a flag instead
+//! of matching on generation bytes and version strings.
+//!
+//! ```no_run
+//! use ironsight::conn::DEFAULT_ADDR;
+//! use ironsight::{BinaryClient, BinaryConnection, BinaryEvent};
+//!
+//! let mut client = BinaryClient::from_tcp(BinaryConnection::connect(DEFAULT_ADDR)?)?;
+//! client.handshake();
+//! loop {
+//!     if let Some(BinaryEvent::Handshake(h)) = client.poll()? {
+//!         let caps = h.capabilities();
+//!         if !caps.power_status {
+//!             println!("battery level not available on {}", caps.generation);
+//!         }
+//!         client.configure_cam(caps.fusion_preset());
+//!     }
+//! }
+//! # Ok::<(), ironsight::ConnError>(())
+//! ```
+
+use std::time::Duration;
+
+use crate::client::HandshakeOutcome;
+use crate::protocol::camera::CamConfig;
+use crate::protocol::handshake::{DeviceGen, FirmwareVersion};
+use crate::protocol::status::DspStatus;
+use crate::seq::DRAIN_TIMEOUT;
+
+/// Feature matrix for one device. See the [module docs](self).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Capabilities {
+    pub generation: DeviceGen,
+    /// DSP firmware version, if it parses.
+    pub dsp_firmware: Option<FirmwareVersion>,
+    /// AVR firmware version, if it parses.
+    pub avr_firmware: Option<FirmwareVersion>,
+    /// PI firmware revision, if it parses.
+    pub pi_firmware: Option<FirmwareVersion>,
+    /// AVR config (0xA2) wire format version: 1 = Mevo+, 2 = Gen2.
+    pub avr_config_version: u8,
+    /// DSP status reports battery, external power and temperature. Only
+    /// the Mevo+ (0x80) status layout is decoded; Gen2 reports none.
+    pub power_status: bool,
+    /// The device runs a ConfigQuery/ConfigResp exchange after each shot.
+    /// Mevo+ only; Gen2 firmware skips it.
+    pub post_shot_config_query: bool,
+    /// PI firmware expects [`CamConfig::raw_fusion_preset()`] rather than
+    /// [`CamConfig::fusion_preset()`] (BM17.04 and later).
+    pub raw_fusion: bool,
+    /// The DSP streams console output (0xF0 DSP_DEBUG). Gen2 only.
+    pub dsp_debug: bool,
+    /// How long the shot sequencer waits for IDLE after flight data before
+    /// re-arming without it. Some Mevo+ units omit IDLE; Gen2 gets the
+    /// same fallback so a dropped IDLE can't leave it disarmed.
+    pub drain_timeout: Duration,
+}
+
+impl Capabilities {
+    #[must_use]
+    pub fn from_handshake(handshake: &HandshakeOutcome) -> Self {
+        let generation = handshake.dsp.hw_info.device_gen();
+        let gen2 = generation == DeviceGen::Gen2;
+        let pi_firmware = handshake.pi.info().firmware;
+        Self {
+            generation,
+            dsp_firmware: handshake.dsp.info().firmware,
+            avr_firmware: handshake.avr.info().firmware,
+            raw_fusion: CamConfig::supports_raw_fusion(pi_firmware.as_ref()),
+            pi_firmware,
+            avr_config_version: handshake.avr.avr_config.version(),
+            power_status: matches!(handshake.dsp.status, DspStatus::V80(_)),
+            post_shot_config_query: generation == DeviceGen::MevoPlus,
+            dsp_debug: gen2,
+            drain_timeout: DRAIN_TIMEOUT,
+        }
+    }
+
+    /// The Fusion camera preset for this unit's PI firmware.
+    #[must_use]
+    pub fn fusion_preset(&self) -> CamConfig {
+        if self.raw_fusion {
+            CamConfig::raw_fusion_preset()
+        } else {
+            CamConfig::fusion_preset()
+        }
+    }
+}
@@ -8,21 +8,35 @@
 //!
 //! Keepalives are managed automatically after the first arm — queued as
 //! proper operations so they never interleave with other sequencers.
+//!
+//! With a [`ReconnectPolicy`] installed, a dropped connection is re-dialled
+//! with backoff and the session (handshake, last AVR/camera config, arm) is
+//! replayed automatically.
 
-use std::collections::VecDeque;
-use std::io::{Read, Write};
-use std::net::TcpStream;
+use std::collections::{HashMap, VecDeque};
+use std::io::{self, Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::thread;
 use std::time::{Duration, Instant};
 
 use crate::addr::BusAddr;
-use crate::conn::{BinaryConnection, ConnError, Envelope};
-use crate::protocol::camera::CamConfig;
-use crate::protocol::status::{AvrStatus, DspStatus, PiStatus};
-use crate::protocol::Message;
+use crate::alert::{Alert, AlertMonitor};
+use crate::capabilities::Capabilities;
+use crate::conn::{BinaryConnection, ConnError, Envelope, Hooks, LocalHooks, Timed, Timestamp};
+use crate::epoch::{EpochMap, EpochMapPolicy, EpochOffset, EpochSample};
+use crate::health::LinkHealth;
+use crate::level::{LevelAssist, LevelFilter, LevelReading};
+use crate::protocol::camera::{CamConfig, WifiScan};
+use crate::protocol::config::{DetectionMode, ParamData};
+use crate::protocol::debug::{DspDebugDecoder, DspDebugLine};
+use crate::protocol::notice::DeviceNotice;
+use crate::protocol::shot::FlightResultV1;
+use crate::protocol::status::{AvrStatus, ConfigAck, DspStatus, PiStatus, StatusPoll};
+use crate::protocol::{Command, Message};
 use crate::seq::{
     self, Action, ArmSequencer, AvrConfigSequencer, AvrSequencer, AvrSettings, AvrSync,
-    CameraConfigSequencer, DisarmSequencer, DspSequencer, DspSync, PiSequencer, PiSync, Sequence,
-    ShotData, ShotDatum, ShotSequencer,
+    CameraConfigSequencer, DisarmSequencer, DspSequencer, DspSync, ParamReadSequencer,
+    PiSequencer, PiSync, Sequence, ShotData, ShotDatum, ShotSequencer, WifiScanSequencer,
 };
 
 // ---------------------------------------------------------------------------
@@ -33,8 +47,9 @@ use crate::seq::{
 #[derive(Debug)]
 pub enum BinaryEvent {
     /// Three-phase handshake (DSP + AVR + PI sync) complete.
-    Handshake(HandshakeOutcome),
-    /// Device disarmed (explicit disarm before re-configure).
+    Handshake(Box<HandshakeOutcome>),
+    /// Device disarmed ([`disarm()`](BinaryClient::disarm), or the
+    /// automatic disarm before re-configure).
     Disarmed,
     /// AVR config + camera config applied.
     Configured,
@@ -51,12 +66,99 @@ pub enum BinaryEvent {
     /// accumulated [`ShotData`] for convenience. Non-streaming callers
     /// can ignore `ShotDatum` events and use this exclusively.
     ShotComplete(Box<ShotData>),
+    /// A triggered shot ended without results: "PROCESSED" never arrived
+    /// within the trigger timeout
+    /// ([`set_trigger_timeout()`](BinaryClient::set_trigger_timeout)), or
+    /// the connection dropped mid-shot. `partial` is the early flight
+    /// estimate (0xE8), if one arrived.
+    ShotAborted {
+        reason: ShotAbortReason,
+        partial: Option<FlightResultV1>,
+    },
     /// Keepalive round-trip complete. Contains the latest cached status
-    /// from DSP/AVR/PI responses. Useful for staleness detection and
-    /// telemetry updates.
+    /// from DSP/AVR/PI responses and the updated [`LinkHealth`]. Useful
+    /// for staleness detection and telemetry updates.
     Keepalive(StatusSnapshot),
-    /// Any message not consumed by the active operation.
+    /// A battery, power, temperature or level condition started or
+    /// cleared. Emitted after `Keepalive` when an [`AlertMonitor`] is
+    /// installed ([`set_alert_monitor()`](BinaryClient::set_alert_monitor)).
+    Alert(Alert),
+    /// Smoothed tilt/roll from level assist
+    /// ([`start_level_assist()`](BinaryClient::start_level_assist)), one
+    /// per AVR status poll.
+    LevelReading(LevelReading),
+    /// Updated device-minus-host offset bound after a device-stamped
+    /// message ([`enable_epoch_map()`](BinaryClient::enable_epoch_map)).
+    EpochOffset(EpochOffset),
+    /// Parameter values from [`read_params()`](BinaryClient::read_params),
+    /// keyed by parameter ID.
+    ParamsRead(HashMap<u8, ParamData>),
+    /// Raw pages of the PI's WiFi scan, in page order
+    /// ([`scan_wifi()`](BinaryClient::scan_wifi)).
+    WifiScan(Vec<WifiScan>),
+    /// [`apply_settings()`](BinaryClient::apply_settings) or
+    /// [`set_mode()`](BinaryClient::set_mode) finished; the device is
+    /// armed with the new settings.
+    SettingsApplied,
+    /// A settings change failed with the given error; the previous
+    /// settings were restored and the device re-armed.
+    SettingsRolledBack(ConnError),
+    /// A settings change failed with the given error before any settings
+    /// had been applied, so there was nothing to restore. The device was
+    /// re-armed holding whatever the failed change left behind; follow up
+    /// with [`configure_avr()`](BinaryClient::configure_avr) or another
+    /// [`apply_settings()`](BinaryClient::apply_settings).
+    SettingsFailed(ConnError),
+    /// Device narration (0xE3 TEXT / 0xE5 SHOT_TEXT), parsed. Emitted for
+    /// every text message, including those that also drive an operation
+    /// or the shot lifecycle, ahead of any event that message produces.
+    Notice { src: BusAddr, notice: DeviceNotice },
+    /// A complete line of Gen2 DSP console output (0xF0 DSP_DEBUG),
+    /// decoded from VT100. One frame may yield several lines or none.
+    DspDebug(DspDebugLine),
+    /// Any message not consumed by the active operation. Text messages
+    /// come through here too, right after their [`Notice`](Self::Notice);
+    /// DSP debug output is reported as [`DspDebug`](Self::DspDebug)
+    /// instead.
     Message(Envelope),
+    /// The connection dropped (or a reconnect attempt failed); the next
+    /// attempt starts after `delay`. Only emitted with a
+    /// [`ReconnectPolicy`] installed.
+    Reconnecting { attempt: u32, delay: Duration },
+    /// A new connection is up. The session restore (handshake, last AVR
+    /// and camera config, arm) follows as ordinary `Handshake`,
+    /// `Configured` and `Armed` events.
+    Reconnected,
+    /// [`BinaryClient::state()`] changed. Emitted on the poll after the
+    /// event that caused the transition.
+    StateChanged(ClientState),
+}
+
+/// Coarse client state, for status indicators. See [`BinaryClient::state()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClientState {
+    /// No handshake completed yet, or the connection was lost.
+    Disconnected,
+    /// Handshake in progress.
+    Handshaking,
+    /// Connected and not armed.
+    Idle,
+    /// Configure, arm or disarm in progress.
+    Configuring,
+    /// Armed and waiting for a shot.
+    Armed,
+    /// Ball triggered; shot data still being collected.
+    ShotInProgress,
+}
+
+/// Why a shot was abandoned. See [`BinaryEvent::ShotAborted`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShotAbortReason {
+    /// "BALL TRIGGER" without "PROCESSED" within the trigger timeout
+    /// (misread, ball not found).
+    Timeout,
+    /// The connection dropped before the shot completed.
+    ConnectionLost,
 }
 
 /// Combined results from the three-phase handshake.
@@ -67,12 +169,64 @@ pub struct HandshakeOutcome {
     pub pi: PiSync,
 }
 
+impl HandshakeOutcome {
+    /// What this unit supports, by generation and firmware.
+    #[must_use]
+    pub fn capabilities(&self) -> Capabilities {
+        Capabilities::from_handshake(self)
+    }
+}
+
 /// Latest cached status from keepalive responses.
 #[derive(Debug, Clone, Default)]
 pub struct StatusSnapshot {
     pub dsp: Option<DspStatus>,
     pub avr: Option<AvrStatus>,
     pub pi: Option<PiStatus>,
+    /// Keepalive RTT, jitter, misses and throughput.
+    pub health: LinkHealth,
+}
+
+/// Backoff schedule for automatic reconnection.
+///
+/// See [`BinaryClient::set_reconnect`]. The delay before attempt `n` is
+/// `initial_backoff * 2^(n-1)`, capped at `max_backoff`.
+#[derive(Debug, Clone)]
+pub struct ReconnectPolicy {
+    /// Delay before the first attempt (default: 500ms).
+    pub initial_backoff: Duration,
+    /// Upper bound on the delay between attempts (default: 30s).
+    pub max_backoff: Duration,
+    /// Give up after this many failed attempts (default: `None`, retry
+    /// forever). `poll()` then returns the last connect error, and
+    /// [`ConnError::Disconnected`] from then on.
+    pub max_attempts: Option<u32>,
+    /// Consecutive keepalive timeouts that count as a dropped connection
+    /// (default: 3). A device that leaves WiFi often never closes the TCP
+    /// socket, so silence is the only signal.
+    pub missed_keepalives: u32,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            max_attempts: None,
+            missed_keepalives: 3,
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    /// Delay before the given attempt (1-based).
+    #[must_use]
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 1u32 << attempt.saturating_sub(1).min(16);
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -85,7 +239,21 @@ enum QueuedOp {
     ConfigureAvr(AvrSettings),
     ConfigureCam(CamConfig),
     Arm,
+    Disarm,
+    Apply(AvrSettings),
+    ReadParams(Vec<u8>, BusAddr),
+    ScanWifi,
     Keepalive,
+    LevelPoll,
+}
+
+impl QueuedOp {
+    /// Whether the op still runs after a reconnect. The session replay
+    /// supersedes handshake, configure, arm and disarm; keepalives and
+    /// level polls restart on their own.
+    fn survives_reconnect(&self) -> bool {
+        matches!(self, Self::Apply(_) | Self::ReadParams(..) | Self::ScanWifi)
+    }
 }
 
 /// Currently executing operation (at most one at a time).
@@ -100,7 +268,11 @@ enum ActiveOp {
     Configure(ConfigurePhase),
     Arm(ArmSequencer),
     Shot(Box<ShotSequencer>),
+    Apply(Box<ApplyOp>),
+    ReadParams(ParamReadSequencer),
+    ScanWifi(WifiScanSequencer),
     Keepalive(KeepaliveSequencer),
+    LevelPoll(LevelPollSequencer),
 }
 
 /// Handshake runs 3 sequencers in series.
@@ -110,30 +282,69 @@ enum HandshakePhase {
     Pi(PiSequencer),
 }
 
+/// Atomic settings change: disarm → configure → arm, in series.
+struct ApplyOp {
+    phase: ApplyPhase,
+    /// Settings written in the configure phase (`None` skips it).
+    write: Option<AvrSettings>,
+    /// Full settings the device holds once this op completes.
+    target: Option<AvrSettings>,
+    /// Set while rolling back; reported when the rollback completes.
+    failure: Option<ConnError>,
+}
+
+enum ApplyPhase {
+    Disarm(DisarmSequencer),
+    Configure(AvrConfigSequencer),
+    Arm(ArmSequencer),
+}
+
 /// Single-phase configure operations (AVR or camera, independently).
 enum ConfigurePhase {
     Avr(AvrConfigSequencer),
     Camera(CameraConfigSequencer),
 }
 
+/// Reconnect configuration and progress.
+struct Reconnect<S, H: Hooks> {
+    policy: ReconnectPolicy,
+    /// `None` when an async wrapper dials and hands over the stream.
+    connect: Option<Box<H::Connect<S>>>,
+    /// Failed attempts since the connection dropped.
+    attempt: u32,
+    /// Set while disconnected: when the next attempt is due.
+    retry_at: Option<Instant>,
+    /// `max_attempts` ran out; no further attempts are made.
+    gave_up: bool,
+}
+
+/// Session state replayed after a reconnect.
+#[derive(Default)]
+struct SessionState {
+    handshake: bool,
+    avr: Option<AvrSettings>,
+    cam: Option<CamConfig>,
+    armed: bool,
+}
+
 // ---------------------------------------------------------------------------
 // KeepaliveSequencer
 // ---------------------------------------------------------------------------
 
-/// Sends 3 StatusPolls (DSP + AVR + PI) and collects all 3 responses.
+/// Sends 3 StatusPolls (DSP + AVR + PI) and collects all 3 responses,
+/// timing each from when the polls were sent.
 struct KeepaliveSequencer {
-    got_dsp: bool,
-    got_avr: bool,
-    got_pi: bool,
+    sent_at: Instant,
+    /// Round-trip times, indexed DSP, AVR, PI.
+    rtts: [Option<Duration>; 3],
 }
 
 impl KeepaliveSequencer {
     fn new() -> (Self, Vec<Action>) {
         (
             Self {
-                got_dsp: false,
-                got_avr: false,
-                got_pi: false,
+                sent_at: Instant::now(),
+                rtts: [None; 3],
             },
             seq::keepalive_actions(),
         )
@@ -142,17 +353,48 @@ impl KeepaliveSequencer {
 
 impl Sequence for KeepaliveSequencer {
     fn feed(&mut self, env: &Envelope) -> Vec<Action> {
-        match (&env.message, env.src) {
-            (Message::DspStatus(_), BusAddr::Dsp) => self.got_dsp = true,
-            (Message::AvrStatus(_), BusAddr::Avr) => self.got_avr = true,
-            (Message::PiStatus(_), BusAddr::Pi) => self.got_pi = true,
-            _ => {}
+        let slot = match (&env.message, env.src) {
+            (Message::DspStatus(_), BusAddr::Dsp) => 0,
+            (Message::AvrStatus(_), BusAddr::Avr) => 1,
+            (Message::PiStatus(_), BusAddr::Pi) => 2,
+            _ => return vec![],
+        };
+        self.rtts[slot].get_or_insert_with(|| self.sent_at.elapsed());
+        vec![]
+    }
+
+    fn is_complete(&self) -> bool {
+        self.rtts.iter().all(Option::is_some)
+    }
+}
+
+/// Sends one AVR StatusPoll for level assist and waits for the AvrStatus.
+struct LevelPollSequencer {
+    done: bool,
+}
+
+impl LevelPollSequencer {
+    fn new() -> (Self, Vec<Action>) {
+        (
+            Self { done: false },
+            vec![Action::Send(
+                Command::StatusPoll(StatusPoll { pi_mode: false }),
+                BusAddr::Avr,
+            )],
+        )
+    }
+}
+
+impl Sequence for LevelPollSequencer {
+    fn feed(&mut self, env: &Envelope) -> Vec<Action> {
+        if let (Message::AvrStatus(_), BusAddr::Avr) = (&env.message, env.src) {
+            self.done = true;
         }
         vec![]
     }
 
     fn is_complete(&self) -> bool {
-        self.got_dsp && self.got_avr && self.got_pi
+        self.done
     }
 }
 
@@ -162,7 +404,10 @@ impl Sequence for KeepaliveSequencer {
 
 const DEFAULT_OP_TIMEOUT: Duration = Duration::from_secs(30);
 const DEFAULT_KEEPALIVE_INTERVAL: Duration = Duration::from_secs(1);
+const DEFAULT_TRIGGER_TIMEOUT: Duration = Duration::from_secs(10);
 const KEEPALIVE_TIMEOUT: Duration = Duration::from_secs(3);
+const LEVEL_POLL_TIMEOUT: Duration = Duration::from_secs(1);
+pub(crate) const RECONNECT_TIMEOUT: Duration = Duration::from_secs(5);
 
 // ---------------------------------------------------------------------------
 // BinaryClient
@@ -198,8 +443,8 @@ const KEEPALIVE_TIMEOUT: Duration = Duration::from_secs(3);
 /// }
 /// # Ok::<(), ironsight::ConnError>(())
 /// ```
-pub struct BinaryClient<S: Read + Write> {
-    conn: BinaryConnection<S>,
+pub struct BinaryClient<S: Read + Write, H: Hooks = LocalHooks> {
+    conn: BinaryConnection<S, H>,
     queue: VecDeque<QueuedOp>,
     active: Option<ActiveOp>,
     op_deadline: Option<Instant>,
@@ -212,6 +457,8 @@ pub struct BinaryClient<S: Read + Write> {
 
     // Configuration
     op_timeout: Duration,
+    trigger_timeout: Duration,
+    rearm_after_abort: bool,
 
     // Cached state
     status: StatusSnapshot,
@@ -221,9 +468,45 @@ pub struct BinaryClient<S: Read + Write> {
     /// pre-PROCESSED messages (E8) are intercepted and yielded as
     /// `ShotDatum` events instead of passing through as `Message`.
     shot_in_progress: bool,
+    /// Receive time of the current shot's "BALL TRIGGER", until
+    /// "PROCESSED" arrives.
+    trigger_at: Option<Timestamp>,
+    /// Early flight estimate (0xE8) of the current shot.
+    shot_partial: Option<FlightResultV1>,
+
+    /// Full AVR settings last confirmed by the device, for diffing in
+    /// `apply_settings`.
+    applied: Option<AvrSettings>,
+
+    // Reconnect
+    reconnect: Option<Reconnect<S, H>>,
+    alerts: Option<AlertMonitor>,
+    session: SessionState,
+    missed_keepalives: u32,
+
+    // Level assist
+    level: Option<LevelFilter>,
+    level_queued: bool,
+    last_level_poll: Instant,
+
+    // Device epoch mapping
+    epochs: Option<EpochMap>,
+
+    // State reporting
+    state: ClientState,
+    /// Set once `poll()` has reported `Disconnected` (no reconnect policy).
+    disconnected: bool,
+    /// Events waiting behind the one returned by the last poll (the
+    /// event a text message produced, further DSP debug lines, then
+    /// `StateChanged`).
+    deferred: VecDeque<BinaryEvent>,
+    dsp_debug: DspDebugDecoder,
+    /// Receive time of the message behind the events of the current poll
+    /// (poll time for events with no message), for [`Timed`].
+    event_time: Timestamp,
 }
 
-impl<S: Read + Write> BinaryClient<S> {
+impl<S: Read + Write, H: Hooks> BinaryClient<S, H> {
     /// Create a new client wrapping the given connection.
     ///
     /// The underlying stream **must** be non-blocking (or have a short read
@@ -234,7 +517,7 @@ impl<S: Read + Write> BinaryClient<S> {
     ///
     /// For `TcpStream`, use [`from_tcp()`](Self::from_tcp) which sets
     /// non-blocking mode automatically.
-    pub fn new(conn: BinaryConnection<S>) -> Self {
+    pub fn new(conn: BinaryConnection<S, H>) -> Self {
         Self {
             conn,
             queue: VecDeque::new(),
@@ -245,10 +528,28 @@ impl<S: Read + Write> BinaryClient<S> {
             last_keepalive: Instant::now(),
             keepalive_interval: DEFAULT_KEEPALIVE_INTERVAL,
             op_timeout: DEFAULT_OP_TIMEOUT,
+            trigger_timeout: DEFAULT_TRIGGER_TIMEOUT,
+            rearm_after_abort: true,
             status: StatusSnapshot::default(),
             device: None,
             armed: false,
             shot_in_progress: false,
+            trigger_at: None,
+            shot_partial: None,
+            applied: None,
+            reconnect: None,
+            alerts: None,
+            session: SessionState::default(),
+            missed_keepalives: 0,
+            level: None,
+            level_queued: false,
+            last_level_poll: Instant::now(),
+            epochs: None,
+            state: ClientState::Disconnected,
+            disconnected: false,
+            deferred: VecDeque::new(),
+            dsp_debug: DspDebugDecoder::new(),
+            event_time: Timestamp::now(),
         }
     }
 
@@ -262,28 +563,161 @@ impl<S: Read + Write> BinaryClient<S> {
     ///
     /// Returns `Err` on I/O errors, wire decode errors, disconnection,
     /// or operation timeout (except keepalive timeouts, which are non-fatal).
+    /// With a [`ReconnectPolicy`] installed, disconnection, I/O errors and
+    /// missed keepalives instead start a reconnect; `Err` is returned for
+    /// those only once `max_attempts` is exhausted. An operation timeout
+    /// is still returned as [`ConnError::Timeout`].
     pub fn poll(&mut self) -> Result<Option<BinaryEvent>, ConnError> {
+        Ok(self.poll_timed()?.map(|timed| timed.event))
+    }
+
+    /// Like [`poll()`](Self::poll), with the host time behind the event:
+    /// when the message that produced it was received, or when `poll()`
+    /// ran for events with no message (timeouts, reconnects). Events
+    /// queued behind one message share its time.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`poll()`](Self::poll).
+    pub fn poll_timed(&mut self) -> Result<Option<Timed<BinaryEvent>>, ConnError> {
+        if let Some(event) = self.deferred.pop_front() {
+            return Ok(Some(self.timed(event)));
+        }
+        self.event_time = Timestamp::now();
+        let result = self.poll_reconnecting();
+        Ok(self.track_state(result)?.map(|event| self.timed(event)))
+    }
+
+    fn timed(&self, event: BinaryEvent) -> Timed<BinaryEvent> {
+        Timed {
+            at: self.event_time,
+            event,
+        }
+    }
+
+    /// Whether a reconnect attempt is due and no earlier event is waiting.
+    /// See [`set_reconnect_policy()`](Self::set_reconnect_policy).
+    #[cfg(feature = "async")]
+    pub(crate) fn reconnect_due(&self) -> bool {
+        self.deferred.is_empty()
+            && self
+                .reconnect
+                .as_ref()
+                .and_then(|r| r.retry_at)
+                .is_some_and(|retry_at| Instant::now() >= retry_at)
+    }
+
+    /// Complete a due reconnect attempt with a stream the caller dialled
+    /// (or the error it got). Returns what [`poll()`](Self::poll) would.
+    #[cfg(feature = "async")]
+    pub(crate) fn reconnect_with(
+        &mut self,
+        result: Result<S, ConnError>,
+    ) -> Result<Option<Timed<BinaryEvent>>, ConnError> {
+        self.event_time = Timestamp::now();
+        let result = self.reconnected(result);
+        Ok(self.track_state(result)?.map(|event| self.timed(event)))
+    }
+
+    /// Record a terminal disconnect and report a `StateChanged` alongside
+    /// `result` if the coarse state moved.
+    fn track_state(
+        &mut self,
+        result: Result<Option<BinaryEvent>, ConnError>,
+    ) -> Result<Option<BinaryEvent>, ConnError> {
+        if matches!(result, Err(ConnError::Disconnected)) {
+            self.disconnected = true;
+        }
+        let state = self.state();
+        if state == self.state {
+            return result;
+        }
+        self.state = state;
+        let changed = BinaryEvent::StateChanged(state);
+        match result {
+            Ok(None) => Ok(Some(changed)),
+            other => {
+                self.deferred.push_back(changed);
+                other
+            }
+        }
+    }
+
+    fn poll_reconnecting(&mut self) -> Result<Option<BinaryEvent>, ConnError> {
+        if self.reconnect.as_ref().is_some_and(|r| r.gave_up) {
+            return Err(ConnError::Disconnected);
+        }
+        if let Some(retry_at) = self.reconnect.as_ref().and_then(|r| r.retry_at) {
+            if Instant::now() < retry_at {
+                return Ok(None);
+            }
+            return self.try_reconnect();
+        }
+        match self.poll_connected() {
+            Err(e)
+                if self.keepalives_lost()
+                    || (self.reconnect.is_some() && is_connection_lost(&e)) =>
+            {
+                self.begin_reconnect();
+                Ok(Some(self.next_attempt()))
+            }
+            result => result,
+        }
+    }
+
+    fn poll_connected(&mut self) -> Result<Option<BinaryEvent>, ConnError> {
         // 1. Check operation deadline.
         if let Some(deadline) = self.op_deadline
             && Instant::now() >= deadline
         {
-            if matches!(self.active, Some(ActiveOp::Keepalive(_))) {
+            if let Some(ActiveOp::Keepalive(ref ka)) = self.active {
                 // Keepalive timeout is non-fatal. Update last_keepalive
                 // so we don't immediately re-queue another one.
+                self.status.health.record_poll(ka.rtts, Instant::now());
                 self.active = None;
                 self.op_deadline = None;
                 self.keepalive_queued = false;
                 self.last_keepalive = Instant::now();
+                self.missed_keepalives += 1;
+                if self.keepalives_lost() {
+                    return Err(ConnError::Timeout);
+                }
+            } else if let Some(ActiveOp::LevelPoll(_)) = self.active {
+                // Level poll timeout is non-fatal too.
+                self.active = None;
+                self.op_deadline = None;
+                self.level_queued = false;
+                self.last_level_poll = Instant::now();
+            } else if let Some(ActiveOp::Apply(ref op)) = self.active
+                && op.failure.is_none()
+            {
+                self.rollback_apply(ConnError::Timeout)?;
             } else {
                 return Err(ConnError::Timeout);
             }
         }
 
+        // 1b. Trigger timeout: "BALL TRIGGER" but no "PROCESSED".
+        if let Some(due) = self.shot_abort_due()
+            && Instant::now() >= due
+        {
+            if self.rearm_after_abort && self.session.armed {
+                self.queue.push_back(QueuedOp::Arm);
+            }
+            return Ok(Some(self.abort_shot(ShotAbortReason::Timeout)));
+        }
+
         // 2. Start next queued operation if idle.
         if self.active.is_none()
             && let Some(queued) = self.queue.pop_front()
         {
             self.start_op(queued)?;
+            // An empty read has nothing to wait for.
+            if let Some(ActiveOp::ReadParams(ref seq)) = self.active
+                && seq.is_complete()
+            {
+                return self.finish_op();
+            }
         }
 
         // 3. AvrSequencer cal timeout check.
@@ -316,11 +750,21 @@ impl<S: Read + Write> BinaryClient<S> {
             self.keepalive_queued = true;
         }
 
+        // 4b. Auto-queue level assist poll (connected and not armed).
+        if let Some(due) = self.level_poll_due()
+            && Instant::now() >= due
+        {
+            self.queue.push_back(QueuedOp::LevelPoll);
+            self.level_queued = true;
+        }
+
         // 5. Non-blocking recv.
         let env = match self.conn.recv()? {
             Some(env) => env,
             None => return Ok(None),
         };
+        self.event_time = env.received;
+        self.status.health.on_message();
 
         // 6. Update status cache.
         match (&env.message, env.src) {
@@ -330,6 +774,50 @@ impl<S: Read + Write> BinaryClient<S> {
             _ => {}
         }
 
+        // 6b. Device text → Notice, emitted ahead of whatever the message
+        //     produces below.
+        if let Some(notice) = DeviceNotice::from_message(&env.message) {
+            let estimate = self.epochs.as_mut().and_then(|map| {
+                let sample =
+                    EpochSample::from_notice(&notice, env.received, map.policy().max_delay)?;
+                Some(BinaryEvent::EpochOffset(map.add(sample)))
+            });
+            let notice = BinaryEvent::Notice {
+                src: env.src,
+                notice,
+            };
+            return match self.dispatch(env) {
+                Ok(event) => {
+                    self.deferred.extend(event);
+                    self.deferred.extend(estimate);
+                    Ok(Some(notice))
+                }
+                Err(e) => {
+                    self.deferred.push_back(notice);
+                    self.deferred.extend(estimate);
+                    Err(e)
+                }
+            };
+        }
+        // 6c. Gen2 DSP debug output → decoded lines. Every sequencer
+        //     skips it, so it never reaches step 7.
+        if let Message::DspDebug(ref bytes) = env.message {
+            let mut lines = self
+                .dsp_debug
+                .feed(bytes)
+                .into_iter()
+                .map(BinaryEvent::DspDebug);
+            let first = lines.next();
+            self.deferred.extend(lines);
+            return Ok(first);
+        }
+        self.dispatch(env)
+    }
+
+    /// Steps 7-11 of [`poll_connected`](Self::poll_connected): route a
+    /// received message to the active operation, shot detection, or the
+    /// caller.
+    fn dispatch(&mut self, env: Envelope) -> Result<Option<BinaryEvent>, ConnError> {
         // 7. Feed active operation.
         //
         //    Keepalive gets special handling: only status responses
@@ -340,21 +828,29 @@ impl<S: Read + Write> BinaryClient<S> {
         //    expected response type. Without this, unsolicited shot
         //    messages (BALL TRIGGER, PROCESSED, E8, etc.) that arrive
         //    while a keepalive is in-flight would be silently consumed
-        //    and the shot would never be detected.
+        //    and the shot would never be detected. Level polls likewise
+        //    take only the AvrStatus.
         if let Some(ref mut active) = self.active {
-            let dominated = matches!(active, ActiveOp::Keepalive(_))
-                && !matches!(
+            let dominated = match active {
+                ActiveOp::Keepalive(_) => !matches!(
                     (&env.message, env.src),
                     (Message::DspStatus(_), BusAddr::Dsp)
                         | (Message::AvrStatus(_), BusAddr::Avr)
                         | (Message::PiStatus(_), BusAddr::Pi)
-                );
+                ),
+                ActiveOp::LevelPoll(_) => !matches!(
+                    (&env.message, env.src),
+                    (Message::AvrStatus(_), BusAddr::Avr)
+                ),
+                _ => false,
+            };
             if !dominated {
                 match Self::feed_active(active, &env, &mut self.conn)? {
                     FeedResult::Consumed => return Ok(None),
                     FeedResult::Intermediate(event) => return Ok(Some(*event)),
                     FeedResult::PhaseComplete => return self.advance_phase(),
                     FeedResult::Done => return self.finish_op(),
+                    FeedResult::Rejected(e) => return self.reject_op(e),
                 }
             }
         }
@@ -363,11 +859,18 @@ impl<S: Read + Write> BinaryClient<S> {
         if let Message::ShotText(ref st) = env.message {
             if st.is_trigger() {
                 self.shot_in_progress = true;
+                self.trigger_at = Some(env.received);
+                self.shot_partial = None;
                 self.armed = false;
                 return Ok(Some(BinaryEvent::Trigger));
             }
             if st.is_processed() {
                 let (seq, actions) = ShotSequencer::new();
+                let mut seq = seq.with_times(self.trigger_at.take(), Some(env.received));
+                if let Some(caps) = self.capabilities() {
+                    seq = seq.with_drain_timeout(Some(caps.drain_timeout));
+                }
+                self.shot_partial = None;
                 for a in actions {
                     seq::send_action(&mut self.conn, a)?;
                 }
@@ -375,6 +878,7 @@ impl<S: Read + Write> BinaryClient<S> {
                 // dominated — reset its queued flag so future
                 // keepalives aren't permanently suppressed.
                 self.keepalive_queued = false;
+                self.level_queued = false;
                 self.active = Some(ActiveOp::Shot(Box::new(seq)));
                 self.op_deadline = Some(Instant::now() + self.op_timeout);
                 return Ok(None);
@@ -385,6 +889,7 @@ impl<S: Read + Write> BinaryClient<S> {
         if self.shot_in_progress
             && let Message::FlightResultV1(ref e8) = env.message
         {
+            self.shot_partial = Some(e8.clone());
             return Ok(Some(BinaryEvent::ShotDatum(ShotDatum::FlightV1(
                 e8.clone(),
             ))));
@@ -398,6 +903,7 @@ impl<S: Read + Write> BinaryClient<S> {
 
     /// Enqueue a three-phase handshake (DSP + AVR + PI sync).
     pub fn handshake(&mut self) {
+        self.session.handshake = true;
         self.queue.push_back(QueuedOp::Handshake);
     }
 
@@ -408,6 +914,7 @@ impl<S: Read + Write> BinaryClient<S> {
     /// completes before new config commands are sent.
     /// Emits [`BinaryEvent::Disarmed`] then [`BinaryEvent::Configured`].
     pub fn configure_avr(&mut self, avr: AvrSettings) {
+        self.session.avr = Some(avr.clone());
         self.queue.push_back(QueuedOp::ConfigureAvr(avr));
     }
 
@@ -415,14 +922,68 @@ impl<S: Read + Write> BinaryClient<S> {
     ///
     /// Emits [`BinaryEvent::Configured`] on completion.
     pub fn configure_cam(&mut self, cam: CamConfig) {
+        self.session.cam = Some(cam.clone());
         self.queue.push_back(QueuedOp::ConfigureCam(cam));
     }
 
     /// Enqueue arming the device.
     pub fn arm(&mut self) {
+        self.session.armed = true;
         self.queue.push_back(QueuedOp::Arm);
     }
 
+    /// Enqueue disarming the device (e.g. between players, or to save
+    /// battery while paused). Keepalives stop until the next arm.
+    ///
+    /// Emits [`BinaryEvent::Disarmed`] on completion.
+    pub fn disarm(&mut self) {
+        self.session.armed = false;
+        self.queue.push_back(QueuedOp::Disarm);
+    }
+
+    /// Enqueue an atomic settings change: disarm, write the params and
+    /// radar calibration that differ from the last applied settings,
+    /// ModeSet, then arm.
+    ///
+    /// Params and calibration omitted from `avr` keep their current
+    /// values. If any step times out or the AVR answers a write with
+    /// ConfigNack, the previous settings are written back and the device
+    /// re-armed, and the op ends with [`BinaryEvent::SettingsRolledBack`]
+    /// instead of [`BinaryEvent::SettingsApplied`]. With no previous
+    /// settings to write back it ends with
+    /// [`BinaryEvent::SettingsFailed`].
+    pub fn apply_settings(&mut self, avr: AvrSettings) {
+        self.queue.push_back(QueuedOp::Apply(avr));
+    }
+
+    /// Enqueue a detection mode change, keeping all other settings. See
+    /// [`apply_settings()`](Self::apply_settings).
+    pub fn set_mode(&mut self, mode: DetectionMode) {
+        self.apply_settings(AvrSettings {
+            mode: mode.comms_index(),
+            params: vec![],
+            radar_cal: None,
+        });
+    }
+
+    /// Enqueue reading parameters back from `dest` (usually
+    /// [`BusAddr::Avr`]), e.g. the `PARAM_*` IDs in
+    /// [`protocol::config`](crate::protocol::config).
+    ///
+    /// Emits [`BinaryEvent::ParamsRead`] once every ID has answered.
+    pub fn read_params(&mut self, ids: &[u8], dest: BusAddr) {
+        self.queue.push_back(QueuedOp::ReadParams(ids.to_vec(), dest));
+    }
+
+    /// Enqueue a WiFi scan on the PI.
+    ///
+    /// Emits [`BinaryEvent::WifiScan`] with the raw pages once all
+    /// [`WIFI_SCAN_PAGES`](seq::WIFI_SCAN_PAGES) have been read. The
+    /// network entries are not decoded yet.
+    pub fn scan_wifi(&mut self) {
+        self.queue.push_back(QueuedOp::ScanWifi);
+    }
+
     // -- Read-only accessors ------------------------------------------------
 
     /// Latest cached status from keepalive responses.
@@ -431,18 +992,143 @@ impl<S: Read + Write> BinaryClient<S> {
         &self.status
     }
 
+    /// Link health: per-node keepalive RTT, jitter and misses, and message
+    /// throughput. Kept across reconnects.
+    #[must_use]
+    pub fn health(&self) -> &LinkHealth {
+        &self.status.health
+    }
+
     /// Handshake outcome, available after `BinaryEvent::Handshake`.
     #[must_use]
     pub fn device(&self) -> Option<&HandshakeOutcome> {
         self.device.as_ref()
     }
 
+    /// The device's [`Capabilities`], available after
+    /// `BinaryEvent::Handshake`.
+    #[must_use]
+    pub fn capabilities(&self) -> Option<Capabilities> {
+        self.device.as_ref().map(HandshakeOutcome::capabilities)
+    }
+
     /// Whether the device is currently armed.
     #[must_use]
     pub fn is_armed(&self) -> bool {
         self.armed
     }
 
+    /// Current coarse state, derived from the active operation and the
+    /// armed/shot flags. Changes are also reported as
+    /// [`BinaryEvent::StateChanged`].
+    #[must_use]
+    pub fn state(&self) -> ClientState {
+        if self.disconnected || self.is_reconnecting() {
+            return ClientState::Disconnected;
+        }
+        match self.active {
+            Some(ActiveOp::Handshake { .. }) => return ClientState::Handshaking,
+            Some(ActiveOp::Shot(_)) => return ClientState::ShotInProgress,
+            Some(
+                ActiveOp::Configure(_)
+                | ActiveOp::Arm(_)
+                | ActiveOp::Disarm(_)
+                | ActiveOp::Apply(_),
+            ) => {
+                return ClientState::Configuring;
+            }
+            Some(
+                ActiveOp::ReadParams(_)
+                | ActiveOp::ScanWifi(_)
+                | ActiveOp::Keepalive(_)
+                | ActiveOp::LevelPoll(_),
+            )
+            | None => {}
+        }
+        if self.device.is_none() {
+            ClientState::Disconnected
+        } else if self.shot_in_progress {
+            ClientState::ShotInProgress
+        } else if self.armed {
+            ClientState::Armed
+        } else {
+            ClientState::Idle
+        }
+    }
+
+    /// Whether the connection is down and waiting for a reconnect attempt.
+    #[must_use]
+    pub fn is_reconnecting(&self) -> bool {
+        self.reconnect.as_ref().is_some_and(|r| r.retry_at.is_some())
+    }
+
+    /// Earliest instant at which [`poll()`](Self::poll) has time-driven work
+    /// to do even if no data arrives: an operation deadline, a keepalive
+    /// falling due, or a calibration/drain timeout inside a sequencer.
+    ///
+    /// Returns `None` when the client is idle and only incoming data can
+    /// produce an event. Event loops that wait on socket readiness should
+    /// wake by this instant.
+    #[must_use]
+    pub fn next_deadline(&self) -> Option<Instant> {
+        if !self.deferred.is_empty() {
+            return Some(Instant::now());
+        }
+        if let Some(retry_at) = self.reconnect.as_ref().and_then(|r| r.retry_at) {
+            return Some(retry_at);
+        }
+        // Queued work starts on the next poll.
+        if self.active.is_none() && !self.queue.is_empty() {
+            return Some(Instant::now());
+        }
+        let keepalive = (self.keepalive_enabled
+            && !self.keepalive_queued
+            && !self.shot_in_progress)
+            .then(|| self.last_keepalive + self.keepalive_interval);
+        let sequencer = match &self.active {
+            Some(ActiveOp::Handshake { phase, .. }) => match &**phase {
+                HandshakePhase::Avr(avr) => avr.cal_deadline(),
+                _ => None,
+            },
+            Some(ActiveOp::Shot(seq)) => seq.drain_deadline(),
+            _ => None,
+        };
+        [
+            self.op_deadline,
+            self.shot_abort_due(),
+            keepalive,
+            self.level_poll_due(),
+            sequencer,
+        ]
+        .into_iter()
+            .flatten()
+            .min()
+    }
+
+    /// When the next level assist poll should be queued, if one is due at
+    /// all (level assist on, handshake done, not armed, none queued).
+    fn level_poll_due(&self) -> Option<Instant> {
+        let level = self.level.as_ref()?;
+        (!self.level_queued && self.device.is_some() && !self.armed && !self.shot_in_progress)
+            .then(|| self.last_level_poll + level.config.interval)
+    }
+
+    /// When a triggered shot still waiting for "PROCESSED" times out.
+    fn shot_abort_due(&self) -> Option<Instant> {
+        let trigger = self.trigger_at.filter(|_| self.shot_in_progress)?;
+        Some(trigger.instant + self.trigger_timeout)
+    }
+
+    /// Clear the in-progress shot and build its `ShotAborted` event.
+    fn abort_shot(&mut self, reason: ShotAbortReason) -> BinaryEvent {
+        self.shot_in_progress = false;
+        self.trigger_at = None;
+        BinaryEvent::ShotAborted {
+            reason,
+            partial: self.shot_partial.take(),
+        }
+    }
+
     // -- Configuration ------------------------------------------------------
 
     /// Set the keepalive polling interval (default: 1s).
@@ -456,11 +1142,263 @@ impl<S: Read + Write> BinaryClient<S> {
         self.op_timeout = timeout;
     }
 
+    /// Set how long to wait for "PROCESSED" after "BALL TRIGGER" before
+    /// giving up on the shot with [`BinaryEvent::ShotAborted`] (default:
+    /// 10s). Keepalives are suppressed until then.
+    pub fn set_trigger_timeout(&mut self, timeout: Duration) {
+        self.trigger_timeout = timeout;
+    }
+
+    /// Whether to re-arm after a trigger timeout (default: true). Only
+    /// applies if the device was armed via [`arm()`](Self::arm) or a
+    /// settings change, not disarmed since.
+    pub fn set_rearm_after_abort(&mut self, rearm: bool) {
+        self.rearm_after_abort = rearm;
+    }
+
+    /// Start level assist: poll the AVR every `config.interval` while the
+    /// device is connected but not armed, and emit smoothed
+    /// [`BinaryEvent::LevelReading`]s. Arming pauses it (keepalives keep
+    /// [`status()`](Self::status) current); disarming resumes it.
+    pub fn start_level_assist(&mut self, config: LevelAssist) {
+        self.level = Some(LevelFilter::new(config));
+    }
+
+    /// Stop level assist. A poll already in flight still completes.
+    pub fn stop_level_assist(&mut self) {
+        self.level = None;
+    }
+
+    /// Bound the device-minus-host offset from the whole-second epochs in
+    /// "BALL TRIGGER" and "Clubimpact" text (see [`crate::epoch`]). Each
+    /// stamped message emits [`BinaryEvent::EpochOffset`] after its
+    /// `Notice`; convert device epochs with
+    /// [`epoch_map()`](Self::epoch_map). Nothing is known until the first
+    /// shot, and drift is not tracked.
+    pub fn enable_epoch_map(&mut self, policy: EpochMapPolicy) {
+        self.epochs = Some(EpochMap::new(policy));
+    }
+
+    /// Stop epoch mapping and drop the offset bound.
+    pub fn disable_epoch_map(&mut self) {
+        self.epochs = None;
+    }
+
+    /// The epoch map, when enabled.
+    #[must_use]
+    pub fn epoch_map(&self) -> Option<&EpochMap> {
+        self.epochs.as_ref()
+    }
+
+    /// Check every keepalive status against `monitor`'s thresholds and
+    /// emit [`BinaryEvent::Alert`] for each condition that starts or
+    /// clears.
+    pub fn set_alert_monitor(&mut self, monitor: AlertMonitor) {
+        self.alerts = Some(monitor);
+    }
+
+    /// Reconnect with `policy`, leaving the dialling to the caller: when
+    /// [`reconnect_due()`](Self::reconnect_due), it opens a new stream and
+    /// hands the result to [`reconnect_with()`](Self::reconnect_with).
+    #[cfg(feature = "async")]
+    pub(crate) fn set_reconnect_policy(&mut self, policy: ReconnectPolicy) {
+        self.install_reconnect(policy, None);
+    }
+
+    fn install_reconnect(
+        &mut self,
+        policy: ReconnectPolicy,
+        connect: Option<Box<H::Connect<S>>>,
+    ) {
+        self.reconnect = Some(Reconnect {
+            policy,
+            connect,
+            attempt: 0,
+            retry_at: None,
+            gave_up: false,
+        });
+    }
+
+    /// The wrapped connection (for adapters that own the byte transport).
+    #[cfg(any(feature = "async", feature = "manager"))]
+    pub(crate) fn conn_mut(&mut self) -> &mut BinaryConnection<S, H> {
+        &mut self.conn
+    }
+
+    // -- Internal: reconnect ------------------------------------------------
+
+    /// Drop all per-connection state after the connection is lost.
+    fn begin_reconnect(&mut self) {
+        if self.shot_in_progress {
+            let aborted = self.abort_shot(ShotAbortReason::ConnectionLost);
+            self.deferred.push_back(aborted);
+        }
+        self.queue.retain(QueuedOp::survives_reconnect);
+        match self.active.take() {
+            Some(ActiveOp::Apply(op)) => match (op.failure, op.target) {
+                (Some(e), Some(_)) => self.deferred.push_back(BinaryEvent::SettingsRolledBack(e)),
+                (Some(e), None) => self.deferred.push_back(BinaryEvent::SettingsFailed(e)),
+                (None, Some(target)) => self.queue.push_front(QueuedOp::Apply(target)),
+                (None, None) => {}
+            },
+            Some(ActiveOp::ReadParams(seq)) => {
+                let (ids, dest) = seq.request();
+                self.queue.push_front(QueuedOp::ReadParams(ids.to_vec(), dest));
+            }
+            Some(ActiveOp::ScanWifi(_)) => self.queue.push_front(QueuedOp::ScanWifi),
+            _ => {}
+        }
+        self.op_deadline = None;
+        self.keepalive_enabled = false;
+        self.keepalive_queued = false;
+        self.missed_keepalives = 0;
+        self.level_queued = false;
+        self.armed = false;
+        self.shot_in_progress = false;
+        self.trigger_at = None;
+        self.device = None;
+        // The device may have rebooted; the session replay re-applies.
+        self.applied = None;
+        self.dsp_debug = DspDebugDecoder::new();
+    }
+
+    /// Whether enough keepalives in a row went unanswered to treat the
+    /// link as dropped.
+    fn keepalives_lost(&self) -> bool {
+        self.reconnect
+            .as_ref()
+            .is_some_and(|r| self.missed_keepalives >= r.policy.missed_keepalives)
+    }
+
+    /// Schedule the next attempt and build its `Reconnecting` event.
+    fn next_attempt(&mut self) -> BinaryEvent {
+        let r = self.reconnect.as_mut().expect("next_attempt without reconnect");
+        r.attempt += 1;
+        let delay = r.policy.backoff(r.attempt);
+        r.retry_at = Some(Instant::now() + delay);
+        BinaryEvent::Reconnecting {
+            attempt: r.attempt,
+            delay,
+        }
+    }
+
+    fn try_reconnect(&mut self) -> Result<Option<BinaryEvent>, ConnError> {
+        let r = self.reconnect.as_mut().expect("try_reconnect without reconnect");
+        let Some(connect) = r.connect.as_mut() else {
+            // Dialled by the wrapper; see reconnect_with().
+            return Ok(None);
+        };
+        let result = connect();
+        self.reconnected(result)
+    }
+
+    /// Finish a reconnect attempt: replay the session on success, else
+    /// schedule the next attempt or give up.
+    fn reconnected(
+        &mut self,
+        result: Result<S, ConnError>,
+    ) -> Result<Option<BinaryEvent>, ConnError> {
+        let r = self.reconnect.as_mut().expect("reconnected without reconnect");
+        match result {
+            Ok(stream) => {
+                r.attempt = 0;
+                r.retry_at = None;
+                self.conn.replace_stream(stream);
+                // Replay ahead of the ops that outlived the drop (and any
+                // queued while reconnecting).
+                self.queue.retain(QueuedOp::survives_reconnect);
+                let mut replay = Vec::new();
+                if self.session.handshake {
+                    replay.push(QueuedOp::Handshake);
+                }
+                if let Some(avr) = &self.session.avr {
+                    replay.push(QueuedOp::ConfigureAvr(avr.clone()));
+                }
+                if let Some(cam) = &self.session.cam {
+                    replay.push(QueuedOp::ConfigureCam(cam.clone()));
+                }
+                if self.session.armed {
+                    replay.push(QueuedOp::Arm);
+                }
+                for op in replay.into_iter().rev() {
+                    self.queue.push_front(op);
+                }
+                Ok(Some(BinaryEvent::Reconnected))
+            }
+            Err(e) => {
+                if r.policy.max_attempts.is_some_and(|max| r.attempt >= max) {
+                    r.retry_at = None;
+                    r.gave_up = true;
+                    self.disconnected = true;
+                    return Err(e);
+                }
+                Ok(Some(self.next_attempt()))
+            }
+        }
+    }
+
+    // -- Internal: atomic settings change -----------------------------------
+
+    /// Start an apply (or rollback) op. Disarms first if armed.
+    fn start_apply(
+        &mut self,
+        write: Option<AvrSettings>,
+        target: Option<AvrSettings>,
+        failure: Option<ConnError>,
+    ) -> Result<(), ConnError> {
+        let (phase, actions) = if self.armed {
+            let (seq, actions) = DisarmSequencer::new();
+            (ApplyPhase::Disarm(seq), actions)
+        } else if let Some(w) = &write {
+            let (seq, actions) = AvrConfigSequencer::new(w.clone());
+            (ApplyPhase::Configure(seq), actions)
+        } else {
+            let (seq, actions) = ArmSequencer::new();
+            (ApplyPhase::Arm(seq), actions)
+        };
+        // Active before sending, so a send that fails on a dropped link
+        // leaves the op for begin_reconnect to carry over.
+        self.op_deadline = Some(Instant::now() + self.op_timeout);
+        self.active = Some(ActiveOp::Apply(Box::new(ApplyOp {
+            phase,
+            write,
+            target,
+            failure,
+        })));
+        for a in actions {
+            seq::send_action(&mut self.conn, a)?;
+        }
+        Ok(())
+    }
+
+    /// An apply step failed: write back the last applied settings (if
+    /// known) and re-arm.
+    fn rollback_apply(&mut self, failure: ConnError) -> Result<(), ConnError> {
+        self.active = None;
+        let previous = self.applied.clone();
+        self.start_apply(previous.clone(), previous, Some(failure))
+    }
+
+    /// The device refused the active op: roll back an apply, fail
+    /// anything else.
+    fn reject_op(&mut self, error: ConnError) -> Result<Option<BinaryEvent>, ConnError> {
+        if let Some(ActiveOp::Apply(ref op)) = self.active
+            && op.failure.is_none()
+        {
+            self.rollback_apply(error)?;
+            return Ok(None);
+        }
+        self.active = None;
+        self.op_deadline = None;
+        Err(error)
+    }
+
     // -- Internal: start an operation ---------------------------------------
 
     fn start_op(&mut self, op: QueuedOp) -> Result<(), ConnError> {
         let timeout = match &op {
             QueuedOp::Keepalive => KEEPALIVE_TIMEOUT,
+            QueuedOp::LevelPoll => LEVEL_POLL_TIMEOUT,
             _ => self.op_timeout,
         };
         self.op_deadline = Some(Instant::now() + timeout);
@@ -508,6 +1446,35 @@ impl<S: Read + Write> BinaryClient<S> {
                 }
                 self.active = Some(ActiveOp::Arm(seq));
             }
+            QueuedOp::Disarm => {
+                let (seq, actions) = DisarmSequencer::new();
+                for a in actions {
+                    seq::send_action(&mut self.conn, a)?;
+                }
+                self.active = Some(ActiveOp::Disarm(seq));
+            }
+            QueuedOp::Apply(avr) => {
+                let (write, target) = match &self.applied {
+                    Some(prev) => (avr.changes_from(prev), avr.merged_onto(prev)),
+                    None => (avr.clone(), avr),
+                };
+                self.start_apply(Some(write), Some(target), None)?;
+            }
+            QueuedOp::ReadParams(ids, dest) => {
+                // Active before sending, as in start_apply.
+                let (seq, actions) = ParamReadSequencer::new(&ids, dest);
+                self.active = Some(ActiveOp::ReadParams(seq));
+                for a in actions {
+                    seq::send_action(&mut self.conn, a)?;
+                }
+            }
+            QueuedOp::ScanWifi => {
+                let (seq, actions) = WifiScanSequencer::new();
+                self.active = Some(ActiveOp::ScanWifi(seq));
+                for a in actions {
+                    seq::send_action(&mut self.conn, a)?;
+                }
+            }
             QueuedOp::Keepalive => {
                 let (seq, actions) = KeepaliveSequencer::new();
                 for a in actions {
@@ -515,6 +1482,18 @@ impl<S: Read + Write> BinaryClient<S> {
                 }
                 self.active = Some(ActiveOp::Keepalive(seq));
             }
+            QueuedOp::LevelPoll if self.armed => {
+                // Queued behind an arm that has since completed.
+                self.op_deadline = None;
+                self.level_queued = false;
+            }
+            QueuedOp::LevelPoll => {
+                let (seq, actions) = LevelPollSequencer::new();
+                for a in actions {
+                    seq::send_action(&mut self.conn, a)?;
+                }
+                self.active = Some(ActiveOp::LevelPoll(seq));
+            }
         }
         Ok(())
     }
@@ -524,7 +1503,7 @@ impl<S: Read + Write> BinaryClient<S> {
     fn feed_active(
         active: &mut ActiveOp,
         env: &Envelope,
-        conn: &mut BinaryConnection<S>,
+        conn: &mut BinaryConnection<S, H>,
     ) -> Result<FeedResult, ConnError> {
         match active {
             ActiveOp::Handshake { phase, .. } => {
@@ -566,6 +1545,11 @@ impl<S: Read + Write> BinaryClient<S> {
                 for a in actions {
                     seq::send_action(conn, a)?;
                 }
+                if let ConfigurePhase::Avr(seq) = config_phase
+                    && let Some(nack) = seq.rejected()
+                {
+                    return Ok(FeedResult::Rejected(config_rejected(nack)));
+                }
                 let complete = match config_phase {
                     ConfigurePhase::Avr(seq) => seq.is_complete(),
                     ConfigurePhase::Camera(seq) => seq.is_complete(),
@@ -600,6 +1584,48 @@ impl<S: Read + Write> BinaryClient<S> {
                     Ok(FeedResult::Consumed)
                 }
             }
+            ActiveOp::Apply(op) => {
+                let (actions, complete) = match &mut op.phase {
+                    ApplyPhase::Disarm(seq) => (seq.feed(env), seq.is_complete()),
+                    ApplyPhase::Configure(seq) => (seq.feed(env), seq.is_complete()),
+                    ApplyPhase::Arm(seq) => (seq.feed(env), seq.is_complete()),
+                };
+                for a in actions {
+                    seq::send_action(conn, a)?;
+                }
+                if let ApplyPhase::Configure(seq) = &op.phase
+                    && let Some(nack) = seq.rejected()
+                {
+                    return Ok(FeedResult::Rejected(config_rejected(nack)));
+                }
+                if complete {
+                    Ok(FeedResult::PhaseComplete)
+                } else {
+                    Ok(FeedResult::Consumed)
+                }
+            }
+            ActiveOp::ReadParams(seq) => {
+                let actions = seq.feed(env);
+                for a in actions {
+                    seq::send_action(conn, a)?;
+                }
+                if seq.is_complete() {
+                    Ok(FeedResult::Done)
+                } else {
+                    Ok(FeedResult::Consumed)
+                }
+            }
+            ActiveOp::ScanWifi(seq) => {
+                let actions = seq.feed(env);
+                for a in actions {
+                    seq::send_action(conn, a)?;
+                }
+                if seq.is_complete() {
+                    Ok(FeedResult::Done)
+                } else {
+                    Ok(FeedResult::Consumed)
+                }
+            }
             ActiveOp::Keepalive(seq) => {
                 let actions = seq.feed(env);
                 for a in actions {
@@ -611,6 +1637,14 @@ impl<S: Read + Write> BinaryClient<S> {
                     Ok(FeedResult::Consumed)
                 }
             }
+            ActiveOp::LevelPoll(seq) => {
+                seq.feed(env);
+                if seq.is_complete() {
+                    Ok(FeedResult::Done)
+                } else {
+                    Ok(FeedResult::Consumed)
+                }
+            }
         }
     }
 
@@ -665,10 +1699,56 @@ impl<S: Read + Write> BinaryClient<S> {
                     self.device = Some(outcome.clone());
                     self.active = None;
                     self.op_deadline = None;
-                    Ok(Some(BinaryEvent::Handshake(outcome)))
+                    Ok(Some(BinaryEvent::Handshake(Box::new(outcome))))
                 }
             },
-            // Only Handshake is multi-phase now.
+            ActiveOp::Apply(mut op) => {
+                let (next, actions) = match op.phase {
+                    ApplyPhase::Disarm(_) => {
+                        self.armed = false;
+                        self.keepalive_enabled = false;
+                        match &op.write {
+                            Some(w) => {
+                                let (seq, actions) = AvrConfigSequencer::new(w.clone());
+                                (ApplyPhase::Configure(seq), actions)
+                            }
+                            None => {
+                                let (seq, actions) = ArmSequencer::new();
+                                (ApplyPhase::Arm(seq), actions)
+                            }
+                        }
+                    }
+                    ApplyPhase::Configure(_) => {
+                        let (seq, actions) = ArmSequencer::new();
+                        (ApplyPhase::Arm(seq), actions)
+                    }
+                    ApplyPhase::Arm(_) => {
+                        self.op_deadline = None;
+                        self.armed = true;
+                        self.keepalive_enabled = true;
+                        self.last_keepalive = Instant::now();
+                        self.session.armed = true;
+                        let ApplyOp { target, failure, .. } = *op;
+                        let restored = target.is_some();
+                        if let Some(target) = target {
+                            self.session.avr = Some(target.clone());
+                            self.applied = Some(target);
+                        }
+                        return Ok(Some(match failure {
+                            Some(e) if restored => BinaryEvent::SettingsRolledBack(e),
+                            Some(e) => BinaryEvent::SettingsFailed(e),
+                            None => BinaryEvent::SettingsApplied,
+                        }));
+                    }
+                };
+                for a in actions {
+                    seq::send_action(&mut self.conn, a)?;
+                }
+                op.phase = next;
+                self.op_deadline = Some(Instant::now() + self.op_timeout);
+                self.active = Some(ActiveOp::Apply(op));
+                Ok(None)
+            }
             _ => unreachable!("advance_phase called on single-phase op"),
         }
     }
@@ -684,7 +1764,15 @@ impl<S: Read + Write> BinaryClient<S> {
                 self.keepalive_enabled = false;
                 Ok(Some(BinaryEvent::Disarmed))
             }
-            ActiveOp::Configure(_) => Ok(Some(BinaryEvent::Configured)),
+            ActiveOp::Configure(ConfigurePhase::Avr(seq)) => {
+                let settings = seq.into_settings();
+                self.applied = Some(match &self.applied {
+                    Some(prev) => settings.merged_onto(prev),
+                    None => settings,
+                });
+                Ok(Some(BinaryEvent::Configured))
+            }
+            ActiveOp::Configure(ConfigurePhase::Camera(_)) => Ok(Some(BinaryEvent::Configured)),
             ActiveOp::Arm(_) => {
                 self.armed = true;
                 self.keepalive_enabled = true;
@@ -697,22 +1785,45 @@ impl<S: Read + Write> BinaryClient<S> {
                 self.last_keepalive = Instant::now();
                 Ok(Some(BinaryEvent::ShotComplete(Box::new(seq.into_result()))))
             }
-            ActiveOp::Keepalive(_) => {
+            ActiveOp::ReadParams(seq) => Ok(Some(BinaryEvent::ParamsRead(seq.into_result()))),
+            ActiveOp::ScanWifi(seq) => Ok(Some(BinaryEvent::WifiScan(seq.into_result()))),
+            ActiveOp::Keepalive(seq) => {
+                self.status.health.record_poll(seq.rtts, Instant::now());
                 self.keepalive_queued = false;
+                self.missed_keepalives = 0;
                 self.last_keepalive = Instant::now();
                 let snapshot = self.status.clone();
+                if let Some(monitor) = &mut self.alerts {
+                    let alerts = monitor.update(&snapshot);
+                    self.deferred.extend(alerts.into_iter().map(BinaryEvent::Alert));
+                }
                 // Start next queued op immediately if available.
                 if let Some(queued) = self.queue.pop_front() {
                     self.start_op(queued)?;
                 }
                 Ok(Some(BinaryEvent::Keepalive(snapshot)))
             }
-            // Only Handshake is multi-phase (uses advance_phase).
+            ActiveOp::LevelPoll(_) => {
+                self.level_queued = false;
+                self.last_level_poll = Instant::now();
+                let (Some(level), Some(avr)) = (&mut self.level, &self.status.avr) else {
+                    // Level assist stopped while the poll was in flight.
+                    return Ok(None);
+                };
+                Ok(Some(BinaryEvent::LevelReading(level.update(avr.tilt, avr.roll))))
+            }
+            // Only Handshake and Apply are multi-phase (use advance_phase).
             _ => unreachable!("finish_op called on multi-phase op"),
         }
     }
 }
 
+/// Errors that mean the connection itself is gone. Silence is detected
+/// separately, by counting missed keepalives.
+fn is_connection_lost(e: &ConnError) -> bool {
+    matches!(e, ConnError::Disconnected | ConnError::Io(_))
+}
+
 /// Internal signal from feed_active to the poll loop.
 enum FeedResult {
     /// Message consumed, nothing to emit.
@@ -723,6 +1834,44 @@ enum FeedResult {
     PhaseComplete,
     /// The entire operation finished.
     Done,
+    /// The device refused the operation.
+    Rejected(ConnError),
+}
+
+/// Error for an AVR write answered with ConfigNack.
+fn config_rejected(nack: &ConfigAck) -> ConnError {
+    ConnError::Protocol(format!(
+        "AVR rejected command 0x{:02X} (ConfigNack)",
+        nack.acked_cmd
+    ))
+}
+
+// -- Reconnect (any stream) -------------------------------------------------
+
+impl<S: Read + Write> BinaryClient<S> {
+    /// Reconnect automatically when the connection drops.
+    ///
+    /// `connect` opens a fresh stream (non-blocking, like the original).
+    /// After it succeeds the client emits [`BinaryEvent::Reconnected`] and
+    /// replays the session: handshake, the last
+    /// [`configure_avr`](Self::configure_avr) and
+    /// [`configure_cam`](Self::configure_cam) settings, and arm if
+    /// [`arm`](Self::arm) was requested. Settings changes, parameter reads
+    /// and WiFi scans that were queued or in flight when the connection
+    /// dropped run again after the replay; a rollback in flight ends with
+    /// [`BinaryEvent::SettingsRolledBack`], since the replay restores the
+    /// last applied settings. Other queued operations are superseded by
+    /// the replay. Connection hooks carry over to the new stream.
+    ///
+    /// For TCP, [`enable_reconnect()`](BinaryClient::enable_reconnect)
+    /// re-dials the current peer.
+    pub fn set_reconnect(
+        &mut self,
+        policy: ReconnectPolicy,
+        connect: impl FnMut() -> Result<S, ConnError> + 'static,
+    ) {
+        self.install_reconnect(policy, Some(Box::new(connect)));
+    }
 }
 
 // -- TcpStream convenience --------------------------------------------------
@@ -738,5 +1887,119 @@ impl BinaryClient<TcpStream> {
         conn.stream().set_nonblocking(true)?;
         Ok(Self::new(conn))
     }
+
+    /// Like [`poll()`](Self::poll), but block up to `timeout` for an event.
+    ///
+    /// Sleeps in the kernel until the socket is readable or
+    /// [`next_deadline()`](Self::next_deadline) falls due, so an idle loop
+    /// uses no CPU while keepalives, drain and operation timeouts still fire
+    /// on time. Returns `Ok(None)` once `timeout` elapses with no event.
+    ///
+    /// The stream is left in non-blocking mode, as set by
+    /// [`from_tcp()`](Self::from_tcp).
+    ///
+    /// # Errors
+    ///
+    /// Same as [`poll()`](Self::poll).
+    pub fn poll_timeout(&mut self, timeout: Duration) -> Result<Option<BinaryEvent>, ConnError> {
+        let end = Instant::now() + timeout;
+        loop {
+            if let Some(event) = self.poll()? {
+                return Ok(Some(event));
+            }
+            // Frames already split off an earlier read won't make the
+            // socket readable again.
+            if self.conn.has_pending() {
+                continue;
+            }
+            let now = Instant::now();
+            let wake = self.next_deadline().map_or(end, |d| d.min(end));
+            if wake <= now {
+                if now >= end {
+                    return Ok(None);
+                }
+                // A timer is due; the next poll() acts on it.
+                continue;
+            }
+            if self.is_reconnecting() {
+                // The old socket is dead and would report readable forever.
+                thread::sleep(wake - now);
+            } else {
+                wait_readable(self.conn.stream(), wake - now)?;
+            }
+        }
+    }
+
+    /// Reconnect to the current peer when the connection drops.
+    ///
+    /// TCP shorthand for [`set_reconnect()`](Self::set_reconnect): each
+    /// attempt connects to the same address with a 5s timeout and sets
+    /// the new stream non-blocking.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the peer address can't be read from the socket.
+    pub fn enable_reconnect(&mut self, policy: ReconnectPolicy) -> Result<(), ConnError> {
+        let addr: SocketAddr = self.conn.peer_addr()?;
+        self.set_reconnect(policy, move || {
+            let conn = BinaryConnection::connect_timeout(&addr, RECONNECT_TIMEOUT)?;
+            conn.stream().set_nonblocking(true)?;
+            Ok(conn.into_stream())
+        });
+        Ok(())
+    }
+}
+
+/// Block until `stream` has data (or EOF) or `wait` elapses.
+///
+/// std has no poll(2) wrapper, so this calls it directly. The socket stays
+/// non-blocking throughout.
+#[cfg(unix)]
+fn wait_readable(stream: &TcpStream, wait: Duration) -> Result<(), ConnError> {
+    use std::os::fd::AsRawFd;
+
+    let mut fd = libc::pollfd {
+        fd: stream.as_raw_fd(),
+        events: libc::POLLIN,
+        revents: 0,
+    };
+    // Round up so a sub-millisecond wait doesn't become a busy poll.
+    let ms = wait.as_micros().div_ceil(1000);
+    let timeout = libc::c_int::try_from(ms).unwrap_or(libc::c_int::MAX);
+    // SAFETY: `fd` is a single valid pollfd that outlives the call, and
+    // the descriptor stays open because `stream` is borrowed.
+    let ret = unsafe { libc::poll(&raw mut fd, 1, timeout) };
+    if ret < 0 {
+        let err = io::Error::last_os_error();
+        // A signal cut the wait short; the caller's loop waits again.
+        if err.kind() != io::ErrorKind::Interrupted {
+            return Err(ConnError::Io(err));
+        }
+    }
+    Ok(())
+}
+
+/// Block until `stream` has data (or EOF) or `wait` elapses.
+///
+/// Without poll(2), peek one byte in blocking mode under a read timeout:
+/// the kernel wakes the thread on readiness without consuming anything.
+/// Non-blocking mode is restored whatever the peek returns.
+#[cfg(not(unix))]
+fn wait_readable(stream: &TcpStream, wait: Duration) -> Result<(), ConnError> {
+    let prev = stream.read_timeout()?;
+    stream.set_nonblocking(false)?;
+    let peeked = stream
+        .set_read_timeout(Some(wait))
+        .and_then(|()| stream.peek(&mut [0u8; 1]));
+    let restored = stream
+        .set_read_timeout(prev)
+        .and(stream.set_nonblocking(true));
+    match peeked {
+        Ok(_) => {}
+        Err(ref e)
+            if e.kind() == io::ErrorKind::WouldBlock || e.kind() == io::ErrorKind::TimedOut => {}
+        Err(e) => return Err(ConnError::Io(e)),
+    }
+    restored.map_err(ConnError::Io)
 }
 
@@ -159,6 +159,20 @@ pub fn write_float40(buf: &mut Vec<u8>, value: f64) {
     write_int24(buf, mantissa);
 }
 
+// ---------------------------------------------------------------------------
+// Scaled write helpers
+// ---------------------------------------------------------------------------
+
+/// Multiply by a scale factor, round, and write as INT24.
+pub fn write_int24_scaled(buf: &mut Vec<u8>, val: f64, scale: f64) {
+    write_int24(buf, (val * scale).round() as i32);
+}
+
+/// Multiply by a scale factor, round, and write as INT16.
+pub fn write_int16_scaled(buf: &mut Vec<u8>, val: f64, scale: f64) {
+    write_int16(buf, (val * scale).round() as i16);
+}
+
 // ---------------------------------------------------------------------------
 // Internal
 // ---------------------------------------------------------------------------
@@ -197,6 +211,15 @@ mod tests {
         }
     }
 
+    #[test]
+    fn scaled_round_trip() {
+        let mut buf = Vec::new();
+        write_int24_scaled(&mut buf, -12.345, 1000.0);
+        write_int16_scaled(&mut buf, 3.2, 10.0);
+        assert_eq!(read_int24_scaled(&buf, 0, 1000.0).unwrap(), -12.345);
+        assert_eq!(read_int16_scaled(&buf, 3, 10.0).unwrap(), 3.2);
+    }
+
     #[test]
     fn int24_sign_extension() {
         // 0xFF_FFFF should sign-extend to -1
@@ -7,7 +7,7 @@
 use std::fmt;
 use std::io::{self, Read, Write};
 use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
-use std::time::Duration;
+use std::time::{Duration, Instant, SystemTime};
 
 use crate::addr::BusAddr;
 use crate::error::WireError;
@@ -17,6 +17,52 @@ use crate::protocol::{Command, Message};
 /// Default device address and port.
 pub const DEFAULT_ADDR: &str = "192.168.2.1:5100";
 
+/// Host clock reading taken when a frame was completed.
+///
+/// `instant` is monotonic, for latencies between frames; `system` is wall
+/// clock, for correlating with other devices and recordings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timestamp {
+    pub instant: Instant,
+    pub system: SystemTime,
+}
+
+impl Timestamp {
+    /// Read both clocks now.
+    #[must_use]
+    pub fn now() -> Self {
+        Self {
+            instant: Instant::now(),
+            system: SystemTime::now(),
+        }
+    }
+
+    /// Monotonic time elapsed since `earlier` (zero if `earlier` is later).
+    #[must_use]
+    pub fn duration_since(&self, earlier: Timestamp) -> Duration {
+        self.instant.saturating_duration_since(earlier.instant)
+    }
+}
+
+/// An event with the host time behind it: when the message that produced
+/// it was received, or when the poll ran for events with no message
+/// (timeouts, reconnects).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Timed<E> {
+    pub at: Timestamp,
+    pub event: E,
+}
+
+/// Which way a frame crossed the connection. See
+/// [`BinaryConnection::set_on_wire`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// APP→device.
+    Sent,
+    /// Device→APP.
+    Received,
+}
+
 /// A decoded device message with its source bus address and raw payload.
 #[derive(Clone)]
 pub struct Envelope {
@@ -26,6 +72,10 @@ pub struct Envelope {
     /// Unstuffed payload bytes (before decode).
     pub raw: Vec<u8>,
     pub message: Message,
+    /// When the frame was completed on the host: the read that finished it
+    /// in [`BinaryConnection::recv()`], or the packet capture time for
+    /// [`pcap`](crate::pcap) items.
+    pub received: Timestamp,
 }
 
 impl fmt::Debug for Envelope {
@@ -97,7 +147,8 @@ impl From<WireError> for ConnError {
 /// Generic over `S: Read + Write` so callers can use any stream type.
 /// For non-blocking usage, configure the stream's read timeout externally
 /// and call [`recv()`](Self::recv) — it returns `Ok(None)` on
-/// `WouldBlock`/`TimedOut` instead of blocking.
+/// `WouldBlock`/`TimedOut` instead of blocking. `H` sets the bounds on
+/// hook callbacks; see [`Hooks`].
 ///
 /// # Example (TcpStream)
 ///
@@ -115,32 +166,37 @@ impl From<WireError> for ConnError {
 /// }
 /// # Ok::<(), ConnError>(())
 /// ```
-pub struct BinaryConnection<S: Read + Write> {
+pub struct BinaryConnection<S: Read + Write, H: Hooks = LocalHooks> {
     stream: S,
     splitter: FrameSplitter,
     read_buf: [u8; 4096],
-    /// Frames split from the stream but not yet consumed by `recv()`.
-    pending: Vec<Vec<u8>>,
+    /// Frames split from the stream but not yet consumed by `recv()`, each
+    /// with the time of the read that completed it.
+    pending: Vec<(Vec<u8>, Timestamp)>,
+    /// The last read found the stream empty.
+    drained: bool,
     /// Called at the top of `send()` with the command and destination.
-    #[allow(clippy::type_complexity)]
-    on_send: Option<Box<dyn FnMut(&Command, BusAddr)>>,
+    on_send: Option<Box<H::OnSend>>,
     /// Called after each successful frame decode in `recv()`.
-    #[allow(clippy::type_complexity)]
-    on_recv: Option<Box<dyn FnMut(&Envelope)>>,
+    on_recv: Option<Box<H::OnRecv>>,
+    /// Called with every complete wire frame written or read.
+    on_wire: Option<Box<H::OnWire>>,
 }
 
 // -- Generic methods (any Read + Write stream) --------------------------------
 
-impl<S: Read + Write> BinaryConnection<S> {
-    /// Wrap any `Read + Write` stream as a binary protocol connection.
-    pub fn new(stream: S) -> Self {
+impl<S: Read + Write, H: Hooks> BinaryConnection<S, H> {
+    /// Like [`new()`](BinaryConnection::new), with hook bounds `H`.
+    pub(crate) fn with_hooks(stream: S) -> Self {
         Self {
             stream,
             splitter: FrameSplitter::new(),
             read_buf: [0u8; 4096],
             pending: Vec::new(),
+            drained: false,
             on_send: None,
             on_recv: None,
+            on_wire: None,
         }
     }
 
@@ -154,14 +210,36 @@ impl<S: Read + Write> BinaryConnection<S> {
         &mut self.stream
     }
 
-    /// Register a callback invoked at the top of every [`send()`](Self::send) call.
-    pub fn set_on_send(&mut self, f: impl FnMut(&Command, BusAddr) + 'static) {
-        self.on_send = Some(Box::new(f));
+    /// Swap in a new stream (e.g. after reconnecting), returning the old one.
+    ///
+    /// Discards partially received and buffered frames from the old stream.
+    /// Send/recv hooks are kept.
+    pub fn replace_stream(&mut self, stream: S) -> S {
+        self.splitter = FrameSplitter::new();
+        self.pending.clear();
+        std::mem::replace(&mut self.stream, stream)
     }
 
-    /// Register a callback invoked after every successful frame decode.
-    pub fn set_on_recv(&mut self, f: impl FnMut(&Envelope) + 'static) {
-        self.on_recv = Some(Box::new(f));
+    /// Consume the connection, returning the underlying stream.
+    pub fn into_stream(self) -> S {
+        self.stream
+    }
+
+    /// Whether complete frames are buffered from an earlier read.
+    ///
+    /// [`recv()`](Self::recv) returns these without touching the stream, so
+    /// callers that wait on socket readiness must drain them first.
+    #[must_use]
+    pub fn has_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Whether the last read from the stream would have blocked, i.e. the
+    /// socket had no more bytes. Edge-triggered pollers won't report it
+    /// readable again until more arrive.
+    #[must_use]
+    pub fn is_drained(&self) -> bool {
+        self.drained
     }
 
     /// Send a command to the given bus address.
@@ -177,6 +255,9 @@ impl<S: Read + Write> BinaryConnection<S> {
     /// e.g. `ClubPrc::encode_request()`).
     pub fn send_raw(&mut self, frame: &RawFrame) -> Result<(), ConnError> {
         let wire = frame.encode();
+        if let Some(cb) = self.on_wire.as_mut() {
+            cb(Direction::Sent, &wire);
+        }
         self.stream.write_all(&wire)?;
         Ok(())
     }
@@ -193,8 +274,8 @@ impl<S: Read + Write> BinaryConnection<S> {
     pub fn recv(&mut self) -> Result<Option<Envelope>, ConnError> {
         loop {
             // Drain pending frames first.
-            if let Some(wire) = self.pending.pop() {
-                let env = Self::decode_wire(&wire)?;
+            if let Some((wire, received)) = self.pending.pop() {
+                let env = Self::decode_wire(&wire, received)?;
                 if let Some(cb) = self.on_recv.as_mut() {
                     cb(&env);
                 }
@@ -202,16 +283,25 @@ impl<S: Read + Write> BinaryConnection<S> {
             }
 
             // Read from stream.
-            match self.stream.read(&mut self.read_buf) {
+            let read = self.stream.read(&mut self.read_buf);
+            self.drained = matches!(read, Err(ref e) if e.kind() == io::ErrorKind::WouldBlock);
+            match read {
                 Ok(0) => return Err(ConnError::Disconnected),
                 Ok(n) => {
+                    let received = Timestamp::now();
                     let mut frames = self.splitter.feed(&self.read_buf[..n]);
+                    if let Some(cb) = self.on_wire.as_mut() {
+                        for f in &frames {
+                            cb(Direction::Received, f);
+                        }
+                    }
+                    // Reverse so pop() yields frames in arrival order: the
+                    // first goes out now, the rest stay in `pending`.
+                    frames.reverse();
                     if let Some(first) = frames.pop() {
-                        // Stash extras (in arrival order — frames was built
-                        // left-to-right, pop takes from the end, so reverse).
-                        frames.reverse();
-                        self.pending.extend(frames);
-                        let env = Self::decode_wire(&first)?;
+                        self.pending
+                            .extend(frames.into_iter().map(|f| (f, received)));
+                        let env = Self::decode_wire(&first, received)?;
                         if let Some(cb) = self.on_recv.as_mut() {
                             cb(&env);
                         }
@@ -232,7 +322,7 @@ impl<S: Read + Write> BinaryConnection<S> {
 
     // -- Internal -------------------------------------------------------------
 
-    fn decode_wire(wire: &[u8]) -> Result<Envelope, ConnError> {
+    fn decode_wire(wire: &[u8], received: Timestamp) -> Result<Envelope, ConnError> {
         let frame = RawFrame::parse(wire)?;
         let src = frame.src;
         let type_id = frame.type_id;
@@ -244,10 +334,97 @@ impl<S: Read + Write> BinaryConnection<S> {
             type_id,
             raw,
             message,
+            received,
         })
     }
 }
 
+// -- Hooks --------------------------------------------------------------------
+
+/// Bounds on the callbacks a connection (or a client wrapping one) holds.
+///
+/// Sealed. [`LocalHooks`], the default, accepts any `'static` closure. The
+/// async clients use a variant that requires `Send` closures, so that they
+/// can move between tasks.
+pub trait Hooks: sealed::Sealed {
+    #[doc(hidden)]
+    type OnSend: ?Sized + FnMut(&Command, BusAddr);
+    #[doc(hidden)]
+    type OnRecv: ?Sized + FnMut(&Envelope);
+    #[doc(hidden)]
+    type OnWire: ?Sized + FnMut(Direction, &[u8]);
+    #[doc(hidden)]
+    type Connect<S>: ?Sized + FnMut() -> Result<S, ConnError>;
+}
+
+/// Callbacks need not be `Send`. See [`Hooks`].
+#[derive(Debug)]
+pub struct LocalHooks;
+
+/// Callbacks must be `Send`, keeping the connection `Send`.
+#[cfg(feature = "async")]
+#[derive(Debug)]
+pub(crate) struct SendHooks;
+
+impl Hooks for LocalHooks {
+    type OnSend = dyn FnMut(&Command, BusAddr);
+    type OnRecv = dyn FnMut(&Envelope);
+    type OnWire = dyn FnMut(Direction, &[u8]);
+    type Connect<S> = dyn FnMut() -> Result<S, ConnError>;
+}
+
+#[cfg(feature = "async")]
+impl Hooks for SendHooks {
+    type OnSend = dyn FnMut(&Command, BusAddr) + Send;
+    type OnRecv = dyn FnMut(&Envelope) + Send;
+    type OnWire = dyn FnMut(Direction, &[u8]) + Send;
+    type Connect<S> = dyn FnMut() -> Result<S, ConnError> + Send;
+}
+
+pub(crate) mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for super::LocalHooks {}
+    #[cfg(feature = "async")]
+    impl Sealed for super::SendHooks {}
+}
+
+impl<S: Read + Write> BinaryConnection<S> {
+    /// Wrap any `Read + Write` stream as a binary protocol connection.
+    pub fn new(stream: S) -> Self {
+        Self::with_hooks(stream)
+    }
+
+    /// Register a callback invoked at the top of every [`send()`](Self::send) call.
+    pub fn set_on_send(&mut self, f: impl FnMut(&Command, BusAddr) + 'static) {
+        self.on_send = Some(Box::new(f));
+    }
+
+    /// Register a callback invoked after every successful frame decode.
+    pub fn set_on_recv(&mut self, f: impl FnMut(&Envelope) + 'static) {
+        self.on_recv = Some(Box::new(f));
+    }
+
+    /// Register a callback invoked with every complete wire frame
+    /// (F0 .. F1) exactly as it crosses the stream: frames written by
+    /// [`send()`](Self::send) and [`send_raw()`](Self::send_raw), and frames
+    /// read, before decoding (so including any that fail to decode).
+    pub fn set_on_wire(&mut self, f: impl FnMut(Direction, &[u8]) + 'static) {
+        self.on_wire = Some(Box::new(f));
+    }
+}
+
+#[cfg(feature = "async")]
+impl<S: Read + Write> BinaryConnection<S, SendHooks> {
+    pub(crate) fn set_on_send(&mut self, f: impl FnMut(&Command, BusAddr) + Send + 'static) {
+        self.on_send = Some(Box::new(f));
+    }
+
+    pub(crate) fn set_on_recv(&mut self, f: impl FnMut(&Envelope) + Send + 'static) {
+        self.on_recv = Some(Box::new(f));
+    }
+}
+
 // -- TcpStream convenience methods --------------------------------------------
 
 impl BinaryConnection<TcpStream> {
@@ -0,0 +1,173 @@
+//! Finding devices on a network.
+//!
+//! In AP mode a Mevo is always at [`DEFAULT_ADDR`](crate::conn::DEFAULT_ADDR),
+//! but units bridged onto a venue LAN in station mode get whatever address
+//! the DHCP server hands out. [`discover()`] probes a list of hosts (see
+//! [`subnet()`]) for the binary port, identifies each responder with an
+//! [`IdentifySequencer`] exchange, and checks for the camera port.
+//!
+//! Probes run on up to [`DiscoverOptions::parallelism`] threads, so a /24
+//! takes about one connect timeout rather than 254 of them.
+//!
+//! ```no_run
+//! use ironsight::discover::{discover, subnet, DiscoverOptions};
+//!
+//! let hosts = subnet("10.20.0.0".parse().unwrap(), 24);
+//! for dev in discover(&hosts, &DiscoverOptions::default()) {
+//!     let fw = dev.firmware.as_ref().map_or("?", |v| v.as_str());
+//!     println!("{} {} {} fw {fw}", dev.addr, dev.ssid, dev.generation);
+//! }
+//! ```
+//!
+//! The device serves one binary client at a time; a unit that is already
+//! in use may refuse or ignore the probe and will not be listed.
+//!
+//! On current firmware the SSID is only readable through the query that
+//! also returns the WiFi password, so each probe pulls the password across
+//! the network in cleartext. It is discarded, not reported; see
+//! [`IdentifySequencer`].
+
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, TcpStream};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::addr::BusAddr;
+use crate::conn::{ConnError, Connection};
+use crate::protocol::handshake::{DeviceGen, FirmwareVersion};
+use crate::seq::{IdentifySequencer, drive};
+
+/// Discovery settings.
+#[derive(Debug, Clone)]
+pub struct DiscoverOptions {
+    /// Binary protocol port (default: 5100).
+    pub port: u16,
+    /// GVP camera port checked on each device found (default: 1258).
+    pub camera_port: u16,
+    /// TCP connect timeout per port (default: 300ms).
+    pub connect_timeout: Duration,
+    /// Time allowed for the identification exchange (default: 2s).
+    pub query_timeout: Duration,
+    /// Hosts probed concurrently (default: 64).
+    pub parallelism: usize,
+}
+
+impl Default for DiscoverOptions {
+    fn default() -> Self {
+        Self {
+            port: 5100,
+            camera_port: 1258,
+            connect_timeout: Duration::from_millis(300),
+            query_timeout: Duration::from_secs(2),
+            parallelism: 64,
+        }
+    }
+}
+
+/// A device that answered a discovery probe.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiscoveredDevice {
+    /// Binary protocol address.
+    pub addr: SocketAddr,
+    /// Configured WiFi network name.
+    pub ssid: String,
+    pub generation: DeviceGen,
+    /// PI firmware revision, if it parses.
+    pub firmware: Option<FirmwareVersion>,
+    /// The GVP camera port accepted a connection.
+    pub has_camera_port: bool,
+}
+
+/// Probe `hosts` and return the devices found, in `hosts` order.
+///
+/// Hosts that refuse, time out or don't speak the protocol are skipped;
+/// use [`probe()`] to see why a particular host was not found.
+#[must_use]
+pub fn discover(hosts: &[IpAddr], options: &DiscoverOptions) -> Vec<DiscoveredDevice> {
+    let next = AtomicUsize::new(0);
+    let found = Mutex::new(Vec::new());
+    let workers = options.parallelism.clamp(1, hosts.len().max(1));
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| {
+                while let Some(&ip) = hosts.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    if let Ok(dev) = probe(ip, options) {
+                        found.lock().unwrap().push(dev);
+                    }
+                }
+            });
+        }
+    });
+    let mut found = found.into_inner().unwrap();
+    found.sort_by_key(|d| hosts.iter().position(|&ip| ip == d.addr.ip()));
+    found
+}
+
+/// Probe one host.
+///
+/// # Errors
+///
+/// [`ConnError::Io`] if the binary port can't be reached,
+/// [`ConnError::Timeout`] if the host doesn't answer the identification
+/// exchange in time, or any error from the exchange itself.
+pub fn probe(ip: IpAddr, options: &DiscoverOptions) -> Result<DiscoveredDevice, ConnError> {
+    let addr = SocketAddr::new(ip, options.port);
+    let mut conn = Connection::connect_timeout(&addr, options.connect_timeout)?;
+    conn.stream_mut()
+        .set_read_timeout(Some(Duration::from_millis(100)))?;
+    let (mut seq, actions) = IdentifySequencer::new();
+    drive(
+        &mut conn,
+        &mut seq,
+        actions,
+        Instant::now() + options.query_timeout,
+    )?;
+    let identity = seq.into_result();
+    drop(conn);
+
+    let camera = SocketAddr::new(ip, options.camera_port);
+    let has_camera_port = TcpStream::connect_timeout(&camera, options.connect_timeout).is_ok();
+    Ok(DiscoveredDevice {
+        addr,
+        ssid: identity.ssid,
+        generation: identity.hw_info.device_gen(),
+        firmware: identity.pi_info.info(BusAddr::Pi).firmware,
+        has_camera_port,
+    })
+}
+
+/// Host addresses of an IPv4 subnet, excluding the network and broadcast
+/// addresses (except for /31 and /32, which have none).
+///
+/// # Panics
+///
+/// If `prefix` is greater than 32.
+#[must_use]
+pub fn subnet(network: Ipv4Addr, prefix: u8) -> Vec<IpAddr> {
+    assert!(prefix <= 32, "IPv4 prefix length {prefix} > 32");
+    let mask = u32::MAX.checked_shl(32 - u32::from(prefix)).unwrap_or(0);
+    let first = u32::from(network) & mask;
+    let last = first | !mask;
+    let range = if prefix >= 31 {
+        first..=last
+    } else {
+        first + 1..=last - 1
+    };
+    range.map(|a| IpAddr::V4(Ipv4Addr::from(a))).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subnet_hosts() {
+        let hosts = subnet(Ipv4Addr::new(192, 168, 2, 77), 24);
+        assert_eq!(hosts.len(), 254);
+        assert_eq!(hosts[0], IpAddr::V4(Ipv4Addr::new(192, 168, 2, 1)));
+        assert_eq!(hosts[253], IpAddr::V4(Ipv4Addr::new(192, 168, 2, 254)));
+        assert_eq!(subnet(Ipv4Addr::new(10, 0, 0, 5), 32).len(), 1);
+        assert_eq!(subnet(Ipv4Addr::new(10, 0, 0, 5), 31).len(), 2);
+        assert_eq!(subnet(Ipv4Addr::new(10, 0, 0, 5), 30).len(), 2);
+    }
+}
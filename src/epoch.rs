@@ -0,0 +1,351 @@
+//! Mapping device epochs to host time.
+//!
+//! The AVR stamps shot narration with its own clock: "BALL TRIGGER: N ms
+//! back, at Epoch E" and "Clubimpact at Epoch E". A stamp was taken no
+//! later than we received the message and, allowing for the trigger
+//! lookback, processing and transit ([`EpochMapPolicy::max_delay`]), not
+//! much earlier, so each one bounds the device-minus-host offset to an
+//! interval about one second (the stamp resolution) plus `max_delay`
+//! wide. [`EpochMap`] intersects the intervals of recent samples, so the
+//! bound narrows as stamps land at different points within the second. A
+//! sample that contradicts the others (device clock reset) starts a new
+//! run.
+//!
+//! This is not clock synchronisation. There is nothing to map before the
+//! first stamped shot, the bound stays in the hundreds of milliseconds
+//! until several shots have landed, and drift is not tracked: at this
+//! resolution it would take hours of shots to see. TimeSync (0x9B) can't
+//! do better: the AVR echoes the epoch the APP sends, so the reply says
+//! nothing about the device clock.
+//!
+//! Enable with [`BinaryClient::enable_epoch_map`]; each stamped message
+//! then emits [`BinaryEvent::EpochOffset`]. Use [`EpochMap::to_host`] to
+//! place device epochs or GVP `epochTime` values on the host clock.
+//!
+//! [`BinaryClient::enable_epoch_map`]: crate::client::BinaryClient::enable_epoch_map
+//! [`BinaryEvent::EpochOffset`]: crate::client::BinaryEvent::EpochOffset
+
+use std::collections::VecDeque;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::conn::Timestamp;
+use crate::protocol::notice::DeviceNotice;
+
+/// Epoch mapping settings.
+#[derive(Debug, Clone)]
+pub struct EpochMapPolicy {
+    /// Samples kept for the estimate (default: 16).
+    pub window: usize,
+    /// Longest a device stamp can predate the message's arrival, beyond
+    /// any lookback the message states (default: 200ms).
+    pub max_delay: Duration,
+}
+
+impl Default for EpochMapPolicy {
+    fn default() -> Self {
+        Self {
+            window: 16,
+            max_delay: Duration::from_millis(200),
+        }
+    }
+}
+
+/// One device timestamp and when it arrived.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EpochSample {
+    /// When the message carrying the stamp was received.
+    pub received: Timestamp,
+    /// Device clock reading (Unix seconds).
+    pub device_epoch: f64,
+    /// Longest the reading can predate `received`.
+    pub max_delay: Duration,
+    /// Reading resolution: the device clock was in
+    /// `[device_epoch, device_epoch + resolution)` (1s for whole seconds).
+    pub resolution: Duration,
+}
+
+impl EpochSample {
+    /// The stamp in a "BALL TRIGGER" or "Clubimpact" notice received at
+    /// `received`. A trigger's lookback is added to `max_delay`. `None`
+    /// for other notices or a missing epoch.
+    #[must_use]
+    pub fn from_notice(
+        notice: &DeviceNotice,
+        received: Timestamp,
+        max_delay: Duration,
+    ) -> Option<Self> {
+        let (epoch, lookback_ms) = match *notice {
+            DeviceNotice::BallTrigger { ms_back, epoch } => (epoch?, ms_back.unwrap_or(0)),
+            DeviceNotice::ClubImpact { epoch } => (epoch?, 0),
+            _ => return None,
+        };
+        if !epoch.is_finite() {
+            return None;
+        }
+        let resolution = if epoch.fract() == 0.0 {
+            Duration::from_secs(1)
+        } else {
+            Duration::from_millis(1)
+        };
+        Some(Self {
+            received,
+            device_epoch: epoch,
+            max_delay: max_delay + Duration::from_millis(u64::from(lookback_ms)),
+            resolution,
+        })
+    }
+
+    /// Range of device-minus-host offsets (seconds) consistent with this
+    /// sample.
+    fn bounds(&self) -> (f64, f64) {
+        let received = unix_secs(self.received.system);
+        (
+            self.device_epoch - received,
+            self.device_epoch + self.resolution.as_secs_f64() + self.max_delay.as_secs_f64()
+                - received,
+        )
+    }
+}
+
+/// Bound on the device-minus-host clock offset.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct EpochOffset {
+    /// Device clock minus host clock (seconds).
+    pub offset: f64,
+    /// Half-width of the offset interval: the true offset is within
+    /// `offset ± uncertainty` (seconds).
+    pub uncertainty: f64,
+    /// Samples in the current run.
+    pub samples: usize,
+}
+
+/// Maps device epochs to host time from the stamps on shot text.
+#[derive(Debug, Clone)]
+pub struct EpochMap {
+    policy: EpochMapPolicy,
+    /// Recent samples, oldest first.
+    samples: VecDeque<EpochSample>,
+    /// Reception time of the newest sample.
+    reference: Option<Timestamp>,
+    estimate: Option<EpochOffset>,
+}
+
+impl Default for EpochMap {
+    fn default() -> Self {
+        Self::new(EpochMapPolicy::default())
+    }
+}
+
+impl EpochMap {
+    #[must_use]
+    pub fn new(policy: EpochMapPolicy) -> Self {
+        Self {
+            policy,
+            samples: VecDeque::new(),
+            reference: None,
+            estimate: None,
+        }
+    }
+
+    #[must_use]
+    pub fn policy(&self) -> &EpochMapPolicy {
+        &self.policy
+    }
+
+    /// The current offset bound, `None` before the first sample.
+    #[must_use]
+    pub fn estimate(&self) -> Option<EpochOffset> {
+        self.estimate
+    }
+
+    /// Add a sample and return the updated offset bound.
+    pub fn add(&mut self, sample: EpochSample) -> EpochOffset {
+        self.samples.push_back(sample);
+        while self.samples.len() > self.policy.window.max(1) {
+            self.samples.pop_front();
+        }
+
+        // Intersect from the newest back, stopping at the first sample
+        // that contradicts the newer ones; anything older is stale.
+        let (mut lo, mut hi) = sample.bounds();
+        let mut run = 1;
+        for older in self.samples.iter().rev().skip(1) {
+            let (l, h) = older.bounds();
+            if l.max(lo) > h.min(hi) {
+                break;
+            }
+            (lo, hi) = (l.max(lo), h.min(hi));
+            run += 1;
+        }
+        let stale = self.samples.len() - run;
+        self.samples.drain(..stale);
+
+        let estimate = EpochOffset {
+            offset: (lo + hi) / 2.0,
+            uncertainty: (hi - lo) / 2.0,
+            samples: run,
+        };
+        self.reference = Some(sample.received);
+        self.estimate = Some(estimate);
+        estimate
+    }
+
+    /// Convert a device timestamp (Unix seconds on the device clock) to
+    /// host time. `None` before the first sample, or if `device_secs` is
+    /// not finite or out of range.
+    #[must_use]
+    pub fn to_host(&self, device_secs: f64) -> Option<Timestamp> {
+        let reference = self.reference?;
+        let system = from_unix_secs(device_secs - self.estimate?.offset)?;
+        let instant = match system.duration_since(reference.system) {
+            Ok(ahead) => reference.instant.checked_add(ahead),
+            Err(e) => reference.instant.checked_sub(e.duration()),
+        }?;
+        Some(Timestamp { instant, system })
+    }
+
+    /// Convert a host time to device Unix seconds. `None` before the first
+    /// sample.
+    #[must_use]
+    pub fn to_device(&self, at: Timestamp) -> Option<f64> {
+        Some(unix_secs(at.system) + self.estimate?.offset)
+    }
+}
+
+fn unix_secs(t: SystemTime) -> f64 {
+    match t.duration_since(UNIX_EPOCH) {
+        Ok(d) => d.as_secs_f64(),
+        Err(e) => -e.duration().as_secs_f64(),
+    }
+}
+
+/// `None` for NaN, infinite, or unrepresentable times.
+fn from_unix_secs(secs: f64) -> Option<SystemTime> {
+    let d = Duration::try_from_secs_f64(secs.abs()).ok()?;
+    if secs >= 0.0 {
+        UNIX_EPOCH.checked_add(d)
+    } else {
+        UNIX_EPOCH.checked_sub(d)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    /// A host clock starting at a fixed Unix time.
+    struct Host {
+        base: Timestamp,
+    }
+
+    impl Host {
+        fn new() -> Self {
+            Self {
+                base: Timestamp {
+                    instant: Instant::now(),
+                    system: UNIX_EPOCH + Duration::from_secs(1_700_000_000),
+                },
+            }
+        }
+
+        fn at(&self, secs: f64) -> Timestamp {
+            let d = Duration::from_secs_f64(secs);
+            Timestamp {
+                instant: self.base.instant + d,
+                system: self.base.system + d,
+            }
+        }
+
+        /// A whole-second stamp taken at host time `t` by a device clock
+        /// running `offset` seconds ahead, received 20 ms later.
+        fn stamp(&self, t: f64, offset: f64) -> EpochSample {
+            let device = 1_700_000_000.0 + t + offset;
+            EpochSample {
+                received: self.at(t + 0.02),
+                device_epoch: device.floor(),
+                max_delay: Duration::from_millis(50),
+                resolution: Duration::from_secs(1),
+            }
+        }
+    }
+
+    #[test]
+    fn intersection_narrows_below_one_second() {
+        let host = Host::new();
+        let mut map = EpochMap::default();
+        let first = map.add(host.stamp(0.0, 2.3));
+        assert!(first.uncertainty > 0.5);
+        assert!((first.offset - 2.3).abs() <= first.uncertainty);
+
+        // Stamps at varying phases cut the interval from both sides, down
+        // to about the delay bound.
+        for (i, phase) in [0.25, 0.5, 0.75, 0.1, 0.6, 0.9].into_iter().enumerate() {
+            map.add(host.stamp(30.0 * (i + 1) as f64 + phase, 2.3));
+        }
+        let est = map.estimate().unwrap();
+        assert_eq!(est.samples, 7);
+        assert!(est.uncertainty < 0.2, "{est:?}");
+        assert!((est.offset - 2.3).abs() <= est.uncertainty);
+    }
+
+    #[test]
+    fn clock_step_starts_a_new_run() {
+        let host = Host::new();
+        let mut map = EpochMap::default();
+        for i in 0..4 {
+            map.add(host.stamp(f64::from(i) * 10.3, 0.0));
+        }
+        let est = map.add(host.stamp(50.0, -5.0));
+        assert_eq!(est.samples, 1);
+        assert!((est.offset + 5.0).abs() <= est.uncertainty);
+    }
+
+    #[test]
+    fn converts_between_device_and_host_time() {
+        let host = Host::new();
+        let mut map = EpochMap::default();
+        assert!(map.to_host(1_700_000_000.0).is_none());
+        for (i, phase) in [0.0, 0.3, 0.6, 0.9].into_iter().enumerate() {
+            map.add(host.stamp(10.0 * i as f64 + phase, 100.0));
+        }
+        let est = map.estimate().unwrap();
+
+        let t = host.at(45.0);
+        let device = map.to_device(t).unwrap();
+        assert!((device - (1_700_000_045.0 + 100.0)).abs() <= est.uncertainty);
+        let back = map.to_host(device).unwrap();
+        let err = back.instant.max(t.instant) - back.instant.min(t.instant);
+        assert!(err < Duration::from_millis(1), "{err:?}");
+    }
+
+    #[test]
+    fn to_host_rejects_unrepresentable_times() {
+        let host = Host::new();
+        let mut map = EpochMap::default();
+        map.add(host.stamp(0.0, 0.0));
+        for secs in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY, 1e300, -1e300] {
+            assert!(map.to_host(secs).is_none(), "{secs}");
+        }
+    }
+
+    #[test]
+    fn samples_from_notices() {
+        let at = Host::new().at(0.0);
+        let delay = Duration::from_millis(200);
+        let trigger = DeviceNotice::parse("BALL TRIGGER: 12 ms back, at Epoch 1700000000");
+        let s = EpochSample::from_notice(&trigger, at, delay).unwrap();
+        assert_eq!(s.device_epoch, 1_700_000_000.0);
+        assert_eq!(s.max_delay, Duration::from_millis(212));
+        assert_eq!(s.resolution, Duration::from_secs(1));
+
+        let impact = DeviceNotice::parse("Clubimpact at Epoch 1700000000.25");
+        let s = EpochSample::from_notice(&impact, at, delay).unwrap();
+        assert_eq!(s.resolution, Duration::from_millis(1));
+
+        assert!(EpochSample::from_notice(&DeviceNotice::Idle, at, delay).is_none());
+        let bare = DeviceNotice::parse("BALL TRIGGER");
+        assert!(EpochSample::from_notice(&bare, at, delay).is_none());
+    }
+}
@@ -36,6 +36,16 @@ pub fn detection_mode_to_avr(mode: DetectionMode) -> u8 {
     }
 }
 
+/// Map an FRP [`DetectionMode`] to the typed ironsight detection mode.
+#[must_use]
+pub fn detection_mode_to_avr_mode(mode: DetectionMode) -> config::DetectionMode {
+    match mode {
+        DetectionMode::Full => config::DetectionMode::Indoor,
+        DetectionMode::Putting => config::DetectionMode::Putting,
+        DetectionMode::Chipping => config::DetectionMode::Chipping,
+    }
+}
+
 /// An FRP device server backed by an ironsight connection.
 ///
 /// Manages the FRP listener and converts [`BinaryEvent`]s into FRP envelopes.
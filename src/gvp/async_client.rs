@@ -0,0 +1,170 @@
+//! Async GVP client for tokio (`async` + `gvp` features).
+//!
+//! [`AsyncGvpClient`] wraps [`GvpClient`] the same way
+//! [`AsyncBinaryClient`](crate::async_client::AsyncBinaryClient) wraps the
+//! binary client. GVP has no keepalive or operation timers, so
+//! [`next_event()`](AsyncGvpClient::next_event) simply waits for data.
+
+use std::io;
+use std::net::SocketAddr;
+
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::{TcpStream, ToSocketAddrs};
+
+use super::client::{GvpClient, GvpEvent};
+use super::config::GvpConfig;
+use super::conn::GvpConnection;
+use super::track::ExpectedTrack;
+use super::trigger::Trigger;
+use super::{GvpCommand, GvpError, GvpMessage};
+use crate::async_client::Bridge;
+use crate::conn::{SendHooks, Timed};
+
+/// Async wrapper around [`GvpClient`] for tokio streams.
+///
+/// ```no_run
+/// use ironsight::gvp::async_client::AsyncGvpClient;
+/// use ironsight::gvp::GvpEvent;
+///
+/// # async fn run() -> Result<(), ironsight::gvp::GvpError> {
+/// let mut client = AsyncGvpClient::connect("192.168.2.1:1258").await?;
+/// client.query_config().await?;
+/// while let GvpEvent::Config(cfg) = client.next_event().await? {
+///     println!("{}x{}", cfg.camera_configuration.roi_width, cfg.camera_configuration.roi_height);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct AsyncGvpClient<S> {
+    io: S,
+    client: GvpClient<Bridge, SendHooks>,
+    read_buf: Box<[u8; 8192]>,
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> AsyncGvpClient<S> {
+    /// Wrap a connected async stream.
+    pub fn new(io: S) -> Self {
+        Self {
+            io,
+            client: GvpClient::new(GvpConnection::with_hooks(Bridge::default())),
+            read_buf: Box::new([0u8; 8192]),
+        }
+    }
+
+    /// Wait for the next GVP event. Cancel-safe.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`GvpClient::poll`]. A closed socket is
+    /// [`GvpError::Disconnected`].
+    pub async fn next_event(&mut self) -> Result<GvpEvent, GvpError> {
+        Ok(self.next_event_timed().await?.event)
+    }
+
+    /// Like [`next_event()`](Self::next_event), with the host time at
+    /// which the message behind the event was received.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`next_event()`](Self::next_event).
+    pub async fn next_event_timed(&mut self) -> Result<Timed<GvpEvent>, GvpError> {
+        loop {
+            self.bridge().begin_poll();
+            if let Some(event) = self.client.poll_timed()? {
+                return Ok(event);
+            }
+            if !self.bridge().is_starved() {
+                continue;
+            }
+            let bridge = self.client.conn_mut().stream_mut();
+            match bridge
+                .fill_from(&mut self.io, &mut self.read_buf[..], None)
+                .await
+            {
+                Ok(_) => {}
+                Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => {
+                    return Err(GvpError::Disconnected);
+                }
+                Err(e) => return Err(GvpError::Io(e)),
+            }
+        }
+    }
+
+    /// Register a callback invoked for every command sent.
+    pub fn set_on_send(&mut self, f: impl FnMut(&GvpCommand) + Send + 'static) {
+        self.client.conn_mut().set_on_send(f);
+    }
+
+    /// Register a callback invoked after every successful message decode.
+    pub fn set_on_recv(&mut self, f: impl FnMut(&str, &GvpMessage) + Send + 'static) {
+        self.client.conn_mut().set_on_recv(f);
+    }
+
+    /// Borrow the underlying async stream.
+    pub fn stream(&self) -> &S {
+        &self.io
+    }
+
+    fn bridge(&mut self) -> &mut Bridge {
+        self.client.conn_mut().stream_mut()
+    }
+
+    /// Write anything the sync client buffered.
+    async fn flush(&mut self) -> Result<(), GvpError> {
+        let bridge = self.client.conn_mut().stream_mut();
+        bridge.flush_to(&mut self.io).await?;
+        Ok(())
+    }
+
+    // -- Send methods (see GvpClient) -----------------------------------------
+
+    /// Request the current camera configuration from the GVP.
+    pub async fn query_config(&mut self) -> Result<(), GvpError> {
+        self.client.query_config()?;
+        self.flush().await
+    }
+
+    /// Push a camera configuration to the GVP.
+    pub async fn send_config(&mut self, config: &GvpConfig) -> Result<(), GvpError> {
+        self.client.send_config(config)?;
+        self.flush().await
+    }
+
+    /// Send a shot trigger to the GVP.
+    pub async fn send_trigger(&mut self, trigger: &Trigger) -> Result<(), GvpError> {
+        self.client.send_trigger(trigger)?;
+        self.flush().await
+    }
+
+    /// Send a club head trajectory hint to the GVP.
+    pub async fn send_club_track(&mut self, track: &ExpectedTrack) -> Result<(), GvpError> {
+        self.client.send_club_track(track)?;
+        self.flush().await
+    }
+
+    /// Send a ball trajectory hint to the GVP.
+    pub async fn send_ball_track(&mut self, track: &ExpectedTrack) -> Result<(), GvpError> {
+        self.client.send_ball_track(track)?;
+        self.flush().await
+    }
+
+    /// Latest camera configuration, cached from the most recent CONFIG message.
+    #[must_use]
+    pub fn config(&self) -> Option<&GvpConfig> {
+        self.client.config()
+    }
+}
+
+impl AsyncGvpClient<TcpStream> {
+    /// Connect to the GVP camera processor over tokio TCP.
+    pub async fn connect(addr: impl ToSocketAddrs) -> Result<Self, GvpError> {
+        let stream = TcpStream::connect(addr).await?;
+        let _ = stream.set_nodelay(true);
+        Ok(Self::new(stream))
+    }
+
+    /// The peer address of the underlying TCP connection.
+    pub fn peer_addr(&self) -> Result<SocketAddr, GvpError> {
+        Ok(self.io.peer_addr()?)
+    }
+}
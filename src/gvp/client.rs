@@ -13,7 +13,7 @@ use std::io::{Read, Write};
 use std::net::TcpStream;
 
 use super::config::GvpConfig;
-use super::conn::GvpConnection;
+use super::conn::{GvpConnection, GvpHooks};
 use super::log::GvpLog;
 use super::result::BallTrackerResult;
 use super::status::GvpStatus;
@@ -21,6 +21,7 @@ use super::track::ExpectedTrack;
 use super::trigger::Trigger;
 use super::video::VideoAvailable;
 use super::{GvpCommand, GvpError, GvpMessage};
+use crate::conn::{LocalHooks, Timed};
 
 // ---------------------------------------------------------------------------
 // Public types
@@ -77,12 +78,12 @@ pub enum GvpEvent {
 /// }
 /// # Ok::<(), ironsight::gvp::GvpError>(())
 /// ```
-pub struct GvpClient<S: Read + Write> {
-    conn: GvpConnection<S>,
+pub struct GvpClient<S: Read + Write, H: GvpHooks = LocalHooks> {
+    conn: GvpConnection<S, H>,
     config: Option<GvpConfig>,
 }
 
-impl<S: Read + Write> GvpClient<S> {
+impl<S: Read + Write, H: GvpHooks> GvpClient<S, H> {
     /// Create a new GVP client wrapping the given connection.
     ///
     /// The underlying stream **must** be non-blocking (or have a short read
@@ -92,7 +93,7 @@ impl<S: Read + Write> GvpClient<S> {
     ///
     /// For `TcpStream`, use [`from_tcp()`](Self::from_tcp) which sets
     /// non-blocking mode automatically.
-    pub fn new(conn: GvpConnection<S>) -> Self {
+    pub fn new(conn: GvpConnection<S, H>) -> Self {
         Self { conn, config: None }
     }
 
@@ -100,8 +101,14 @@ impl<S: Read + Write> GvpClient<S> {
     ///
     /// Returns `Ok(None)` when no data is available (non-blocking).
     pub fn poll(&mut self) -> Result<Option<GvpEvent>, GvpError> {
-        let msg = match self.conn.recv()? {
-            Some(msg) => msg,
+        Ok(self.poll_timed()?.map(|timed| timed.event))
+    }
+
+    /// Like [`poll()`](Self::poll), with the host time at which the
+    /// message behind the event was received.
+    pub fn poll_timed(&mut self) -> Result<Option<Timed<GvpEvent>>, GvpError> {
+        let Timed { at, event: msg } = match self.conn.recv_timed()? {
+            Some(timed) => timed,
             None => return Ok(None),
         };
 
@@ -117,7 +124,7 @@ impl<S: Read + Write> GvpClient<S> {
             GvpMessage::Unknown { msg_type, raw } => GvpEvent::Unknown { msg_type, raw },
         };
 
-        Ok(Some(event))
+        Ok(Some(Timed { at, event }))
     }
 
     // -- Send methods (immediate, no queuing) -------------------------------
@@ -155,6 +162,12 @@ impl<S: Read + Write> GvpClient<S> {
     pub fn config(&self) -> Option<&GvpConfig> {
         self.config.as_ref()
     }
+
+    /// The wrapped connection (for adapters that own the byte transport).
+    #[cfg(feature = "async")]
+    pub(crate) fn conn_mut(&mut self) -> &mut GvpConnection<S, H> {
+        &mut self.conn
+    }
 }
 
 // -- TcpStream convenience --------------------------------------------------
@@ -10,6 +10,10 @@ use std::time::Duration;
 
 use super::splitter::NullSplitter;
 use super::{GvpCommand, GvpError, GvpMessage};
+#[cfg(feature = "async")]
+use crate::conn::SendHooks;
+use crate::conn::sealed::Sealed;
+use crate::conn::{LocalHooks, Timed, Timestamp};
 
 /// Default GVP camera port.
 pub const DEFAULT_PORT: u16 = 1258;
@@ -21,28 +25,28 @@ pub const DEFAULT_PORT: u16 = 1258;
 /// and call [`recv()`](Self::recv) — it returns `Ok(None)` on
 /// `WouldBlock`/`TimedOut`.
 ///
-/// Mirrors [`crate::conn::BinaryConnection`] for the binary protocol.
-pub struct GvpConnection<S: Read + Write> {
+/// Mirrors [`crate::conn::BinaryConnection`] for the binary protocol,
+/// including the hook bounds `H`.
+pub struct GvpConnection<S: Read + Write, H: GvpHooks = LocalHooks> {
     stream: S,
     splitter: NullSplitter,
     read_buf: [u8; 8192],
-    /// JSON strings split from the stream but not yet consumed by `recv()`.
-    pending: Vec<String>,
+    /// JSON strings split from the stream but not yet consumed by `recv()`,
+    /// each with the time of the read that completed it.
+    pending: Vec<(String, Timestamp)>,
     /// Called at the top of `send()` with the command.
-    #[allow(clippy::type_complexity)]
-    on_send: Option<Box<dyn FnMut(&GvpCommand)>>,
+    on_send: Option<Box<H::OnSend>>,
     /// Called after each successful message decode in `recv()`.
     /// Receives the raw JSON string (hex-first: for JSON protocol, raw JSON
     /// IS the canonical form).
-    #[allow(clippy::type_complexity)]
-    on_recv: Option<Box<dyn FnMut(&str, &GvpMessage)>>,
+    on_recv: Option<Box<H::OnRecv>>,
 }
 
 // -- Generic methods (any Read + Write stream) --------------------------------
 
-impl<S: Read + Write> GvpConnection<S> {
-    /// Wrap any `Read + Write` stream as a GVP connection.
-    pub fn new(stream: S) -> Self {
+impl<S: Read + Write, H: GvpHooks> GvpConnection<S, H> {
+    /// Like [`new()`](GvpConnection::new), with hook bounds `H`.
+    pub(crate) fn with_hooks(stream: S) -> Self {
         Self {
             stream,
             splitter: NullSplitter::new(),
@@ -63,20 +67,6 @@ impl<S: Read + Write> GvpConnection<S> {
         &mut self.stream
     }
 
-    /// Register a callback invoked at the top of every [`send()`](Self::send) call.
-    pub fn set_on_send(&mut self, f: impl FnMut(&GvpCommand) + 'static) {
-        self.on_send = Some(Box::new(f));
-    }
-
-    /// Register a callback invoked after every successful message decode.
-    ///
-    /// The callback receives the raw JSON string and the decoded message.
-    /// Per the hex-first logging policy: for JSON protocol, the raw JSON
-    /// string IS the canonical representation.
-    pub fn set_on_recv(&mut self, f: impl FnMut(&str, &GvpMessage) + 'static) {
-        self.on_recv = Some(Box::new(f));
-    }
-
     /// Send a command to the GVP.
     pub fn send(&mut self, cmd: &GvpCommand) -> Result<(), GvpError> {
         if let Some(cb) = self.on_send.as_mut() {
@@ -94,31 +84,31 @@ impl<S: Read + Write> GvpConnection<S> {
     /// - `Err(Disconnected)` — stream closed by peer.
     /// - `Err(Json|Io)` — decode or I/O error.
     pub fn recv(&mut self) -> Result<Option<GvpMessage>, GvpError> {
+        Ok(self.recv_timed()?.map(|timed| timed.event))
+    }
+
+    /// Like [`recv()`](Self::recv), with the host time at which the
+    /// message was completed on the stream.
+    pub fn recv_timed(&mut self) -> Result<Option<Timed<GvpMessage>>, GvpError> {
         loop {
             // Drain pending messages first.
-            if let Some(json) = self.pending.pop() {
-                let msg = GvpMessage::decode(&json)?;
-                if let Some(cb) = self.on_recv.as_mut() {
-                    cb(&json, &msg);
-                }
-                return Ok(Some(msg));
+            if let Some((json, received)) = self.pending.pop() {
+                return self.decode(&json, received).map(Some);
             }
 
             // Read from stream.
             match self.stream.read(&mut self.read_buf) {
                 Ok(0) => return Err(GvpError::Disconnected),
                 Ok(n) => {
+                    let received = Timestamp::now();
                     let mut messages = self.splitter.feed(&self.read_buf[..n]);
+                    // Reverse so pop() yields messages in arrival order: the
+                    // first goes out now, the rest stay in `pending`.
+                    messages.reverse();
                     if let Some(first) = messages.pop() {
-                        // Stash extras (in arrival order — messages was built
-                        // left-to-right, pop takes from the end, so reverse).
-                        messages.reverse();
-                        self.pending.extend(messages);
-                        let msg = GvpMessage::decode(&first)?;
-                        if let Some(cb) = self.on_recv.as_mut() {
-                            cb(&first, &msg);
-                        }
-                        return Ok(Some(msg));
+                        self.pending
+                            .extend(messages.into_iter().map(|m| (m, received)));
+                        return self.decode(&first, received).map(Some);
                     }
                     // No complete message yet — loop for more data.
                 }
@@ -132,6 +122,70 @@ impl<S: Read + Write> GvpConnection<S> {
             }
         }
     }
+
+    fn decode(&mut self, json: &str, received: Timestamp) -> Result<Timed<GvpMessage>, GvpError> {
+        let msg = GvpMessage::decode(json)?;
+        if let Some(cb) = self.on_recv.as_mut() {
+            cb(json, &msg);
+        }
+        Ok(Timed {
+            at: received,
+            event: msg,
+        })
+    }
+}
+
+// -- Hooks --------------------------------------------------------------------
+
+/// [`Hooks`](crate::conn::Hooks) for the GVP callbacks. Sealed.
+pub trait GvpHooks: Sealed {
+    #[doc(hidden)]
+    type OnSend: ?Sized + FnMut(&GvpCommand);
+    #[doc(hidden)]
+    type OnRecv: ?Sized + FnMut(&str, &GvpMessage);
+}
+
+impl GvpHooks for LocalHooks {
+    type OnSend = dyn FnMut(&GvpCommand);
+    type OnRecv = dyn FnMut(&str, &GvpMessage);
+}
+
+#[cfg(feature = "async")]
+impl GvpHooks for SendHooks {
+    type OnSend = dyn FnMut(&GvpCommand) + Send;
+    type OnRecv = dyn FnMut(&str, &GvpMessage) + Send;
+}
+
+impl<S: Read + Write> GvpConnection<S> {
+    /// Wrap any `Read + Write` stream as a GVP connection.
+    pub fn new(stream: S) -> Self {
+        Self::with_hooks(stream)
+    }
+
+    /// Register a callback invoked at the top of every [`send()`](Self::send) call.
+    pub fn set_on_send(&mut self, f: impl FnMut(&GvpCommand) + 'static) {
+        self.on_send = Some(Box::new(f));
+    }
+
+    /// Register a callback invoked after every successful message decode.
+    ///
+    /// The callback receives the raw JSON string and the decoded message.
+    /// Per the hex-first logging policy: for JSON protocol, the raw JSON
+    /// string IS the canonical representation.
+    pub fn set_on_recv(&mut self, f: impl FnMut(&str, &GvpMessage) + 'static) {
+        self.on_recv = Some(Box::new(f));
+    }
+}
+
+#[cfg(feature = "async")]
+impl<S: Read + Write> GvpConnection<S, SendHooks> {
+    pub(crate) fn set_on_send(&mut self, f: impl FnMut(&GvpCommand) + Send + 'static) {
+        self.on_send = Some(Box::new(f));
+    }
+
+    pub(crate) fn set_on_recv(&mut self, f: impl FnMut(&str, &GvpMessage) + Send + 'static) {
+        self.on_recv = Some(Box::new(f));
+    }
 }
 
 // -- TcpStream convenience methods --------------------------------------------
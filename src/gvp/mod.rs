@@ -19,6 +19,8 @@
 //! | `RESULT` | GVP → APP | [`GvpMessage::Result`] |
 //! | `MT_VIDEO_AVAILABLE` | GVP → APP | [`GvpMessage::VideoAvailable`] |
 
+#[cfg(feature = "async")]
+pub mod async_client;
 pub mod client;
 pub mod config;
 pub mod conn;
@@ -33,6 +35,8 @@ pub mod video;
 use serde_json::Value;
 use thiserror::Error;
 
+#[cfg(feature = "async")]
+pub use async_client::AsyncGvpClient;
 pub use client::{GvpClient, GvpEvent};
 pub use config::GvpConfig;
 pub use conn::GvpConnection;
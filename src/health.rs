@@ -0,0 +1,181 @@
+//! Link health metrics derived from keepalive polls.
+//!
+//! Each keepalive sends a StatusPoll to the DSP, AVR and PI and times the
+//! three responses. [`LinkHealth`] keeps per-node round-trip time, jitter
+//! and miss counters, plus overall message throughput, so a degrading WiFi
+//! link shows up (rising RTT/jitter, sporadic misses) before it starts
+//! losing shots. Available from [`BinaryClient::health()`] and in every
+//! [`BinaryEvent::Keepalive`].
+//!
+//! [`BinaryClient::health()`]: crate::client::BinaryClient::health
+//! [`BinaryEvent::Keepalive`]: crate::client::BinaryEvent::Keepalive
+
+use std::time::{Duration, Instant};
+
+use crate::addr::BusAddr;
+
+/// Round-trip statistics for one bus node's keepalive status responses.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NodeHealth {
+    /// RTT of the most recent response.
+    pub last_rtt: Option<Duration>,
+    /// Smoothed RTT (exponential average, gain 1/8 as in TCP SRTT).
+    pub avg_rtt: Option<Duration>,
+    /// Largest RTT seen.
+    pub max_rtt: Option<Duration>,
+    /// Smoothed variation between consecutive RTTs (RFC 3550 interarrival
+    /// jitter, gain 1/16).
+    pub jitter: Duration,
+    /// Status responses received.
+    pub responses: u64,
+    /// Keepalive polls this node never answered.
+    pub missed: u64,
+    /// Misses since the last response.
+    pub consecutive_missed: u32,
+}
+
+impl NodeHealth {
+    /// Fraction of polls missed, 0.0-1.0 (0.0 before the first poll).
+    #[must_use]
+    pub fn miss_rate(&self) -> f64 {
+        let total = self.responses + self.missed;
+        if total == 0 {
+            0.0
+        } else {
+            self.missed as f64 / total as f64
+        }
+    }
+
+    fn record_rtt(&mut self, rtt: Duration) {
+        if let Some(last) = self.last_rtt {
+            let delta = rtt.abs_diff(last);
+            self.jitter = if delta > self.jitter {
+                self.jitter + (delta - self.jitter) / 16
+            } else {
+                self.jitter - (self.jitter - delta) / 16
+            };
+        }
+        self.avg_rtt = Some(match self.avg_rtt {
+            Some(avg) if rtt > avg => avg + (rtt - avg) / 8,
+            Some(avg) => avg - (avg - rtt) / 8,
+            None => rtt,
+        });
+        self.max_rtt = self.max_rtt.max(Some(rtt));
+        self.last_rtt = Some(rtt);
+        self.responses += 1;
+        self.consecutive_missed = 0;
+    }
+
+    fn record_miss(&mut self) {
+        self.missed += 1;
+        self.consecutive_missed += 1;
+    }
+}
+
+/// Link health across the three polled nodes.
+#[derive(Debug, Clone, Default)]
+pub struct LinkHealth {
+    pub dsp: NodeHealth,
+    pub avr: NodeHealth,
+    pub pi: NodeHealth,
+    /// Messages received from the device since the client was created.
+    pub messages_received: u64,
+    /// Messages per second between the last two keepalive polls.
+    pub messages_per_sec: f64,
+    window_start: Option<Instant>,
+    window_count: u64,
+}
+
+impl LinkHealth {
+    /// Stats for one node. `None` for buses that are not polled.
+    #[must_use]
+    pub fn node(&self, addr: BusAddr) -> Option<&NodeHealth> {
+        match addr {
+            BusAddr::Dsp => Some(&self.dsp),
+            BusAddr::Avr => Some(&self.avr),
+            BusAddr::Pi => Some(&self.pi),
+            _ => None,
+        }
+    }
+
+    /// Highest consecutive-miss count across the nodes.
+    #[must_use]
+    pub fn consecutive_missed(&self) -> u32 {
+        self.dsp
+            .consecutive_missed
+            .max(self.avr.consecutive_missed)
+            .max(self.pi.consecutive_missed)
+    }
+
+    /// Count one received message.
+    pub(crate) fn on_message(&mut self) {
+        self.messages_received += 1;
+        self.window_count += 1;
+    }
+
+    /// Record one finished (or timed-out) keepalive poll: `Some(rtt)` for
+    /// nodes that answered, `None` for misses. Also closes the throughput
+    /// window.
+    pub(crate) fn record_poll(&mut self, rtts: [Option<Duration>; 3], now: Instant) {
+        for (node, rtt) in [&mut self.dsp, &mut self.avr, &mut self.pi]
+            .into_iter()
+            .zip(rtts)
+        {
+            match rtt {
+                Some(rtt) => node.record_rtt(rtt),
+                None => node.record_miss(),
+            }
+        }
+        if let Some(start) = self.window_start {
+            let secs = now.duration_since(start).as_secs_f64();
+            if secs > 0.0 {
+                self.messages_per_sec = self.window_count as f64 / secs;
+            }
+        }
+        self.window_start = Some(now);
+        self.window_count = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MS: Duration = Duration::from_millis(1);
+
+    #[test]
+    fn rtt_jitter_and_misses() {
+        let mut h = LinkHealth::default();
+        let t0 = Instant::now();
+        h.record_poll([Some(20 * MS), Some(30 * MS), None], t0);
+        h.record_poll([Some(36 * MS), Some(30 * MS), None], t0 + MS);
+
+        assert_eq!(h.dsp.last_rtt, Some(36 * MS));
+        assert_eq!(h.dsp.avg_rtt, Some(22 * MS));
+        assert_eq!(h.dsp.max_rtt, Some(36 * MS));
+        assert_eq!(h.dsp.jitter, MS);
+        assert_eq!(h.avr.jitter, Duration::ZERO);
+        assert_eq!(h.pi.responses, 0);
+        assert_eq!(h.pi.missed, 2);
+        assert_eq!(h.consecutive_missed(), 2);
+        assert_eq!(h.pi.miss_rate(), 1.0);
+
+        h.record_poll([Some(20 * MS); 3], t0 + 2 * MS);
+        assert_eq!(h.consecutive_missed(), 0);
+        assert_eq!(h.node(BusAddr::Pi).unwrap().miss_rate(), 2.0 / 3.0);
+        assert!(h.node(BusAddr::App).is_none());
+    }
+
+    #[test]
+    fn throughput_between_polls() {
+        let mut h = LinkHealth::default();
+        let t0 = Instant::now();
+        h.record_poll([None; 3], t0);
+        for _ in 0..50 {
+            h.on_message();
+        }
+        h.record_poll([None; 3], t0 + Duration::from_millis(500));
+        assert_eq!(h.messages_received, 50);
+        assert_eq!(h.messages_per_sec, 100.0);
+    }
+}
@@ -0,0 +1,94 @@
+//! Level assist: fast, smoothed tilt/roll readings for device setup.
+//!
+//! Keepalives refresh [`AvrStatus`](crate::protocol::status::AvrStatus)
+//! once a second, and only while armed. With level assist on
+//! ([`BinaryClient::start_level_assist`]), the client polls the AVR at
+//! [`LevelAssist::interval`] while the device is connected but not armed,
+//! smooths tilt and roll, and emits a [`LevelReading`] per poll — enough
+//! to drive an on-screen bubble level.
+//!
+//! [`BinaryClient::start_level_assist`]: crate::client::BinaryClient::start_level_assist
+
+use std::time::Duration;
+
+/// Level assist settings.
+#[derive(Debug, Clone)]
+pub struct LevelAssist {
+    /// AVR status poll interval (default: 100ms).
+    pub interval: Duration,
+    /// Weight of each new sample in the exponential moving average,
+    /// 0.0-1.0; 1.0 disables smoothing (default: 0.3).
+    pub smoothing: f64,
+    /// Largest |tilt| and |roll| in degrees that counts as level
+    /// (default: 1.0).
+    pub tolerance_deg: f64,
+}
+
+impl Default for LevelAssist {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_millis(100),
+            smoothing: 0.3,
+            tolerance_deg: 1.0,
+        }
+    }
+}
+
+/// One smoothed tilt/roll sample.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct LevelReading {
+    /// Smoothed tilt (degrees).
+    pub tilt: f64,
+    /// Smoothed roll (degrees, same sign convention as `AvrStatus::roll`).
+    pub roll: f64,
+    /// Both within [`LevelAssist::tolerance_deg`] of zero.
+    pub within_tolerance: bool,
+}
+
+/// Exponential smoothing of raw AVR tilt/roll samples.
+#[derive(Debug, Clone)]
+pub(crate) struct LevelFilter {
+    pub(crate) config: LevelAssist,
+    last: Option<(f64, f64)>,
+}
+
+impl LevelFilter {
+    pub(crate) fn new(config: LevelAssist) -> Self {
+        Self { config, last: None }
+    }
+
+    pub(crate) fn update(&mut self, tilt: f64, roll: f64) -> LevelReading {
+        let a = self.config.smoothing.clamp(0.0, 1.0);
+        let (tilt, roll) = match self.last {
+            Some((t, r)) => (t + a * (tilt - t), r + a * (roll - r)),
+            None => (tilt, roll),
+        };
+        self.last = Some((tilt, roll));
+        let tol = self.config.tolerance_deg;
+        LevelReading {
+            tilt,
+            roll,
+            within_tolerance: tilt.abs() <= tol && roll.abs() <= tol,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn smooths_and_checks_tolerance() {
+        let mut f = LevelFilter::new(LevelAssist {
+            smoothing: 0.5,
+            ..LevelAssist::default()
+        });
+        let r = f.update(4.0, -2.0);
+        assert_eq!((r.tilt, r.roll, r.within_tolerance), (4.0, -2.0, false));
+        let r = f.update(0.0, 0.0);
+        assert_eq!((r.tilt, r.roll), (2.0, -1.0));
+        let r = f.update(0.0, 0.0);
+        assert_eq!((r.tilt, r.roll, r.within_tolerance), (1.0, -0.5, true));
+    }
+}
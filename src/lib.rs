@@ -1,23 +1,51 @@
 pub mod addr;
+pub mod alert;
+#[cfg(feature = "async")]
+pub mod async_client;
+pub mod capabilities;
 pub mod client;
 pub mod codec;
 pub mod conn;
+pub mod discover;
+pub mod epoch;
 pub mod error;
 pub mod frame;
 #[cfg(feature = "frp")]
 pub mod frp;
 #[cfg(feature = "gvp")]
 pub mod gvp;
+pub mod health;
+pub mod level;
+#[cfg(feature = "manager")]
+pub mod manager;
+pub mod pcap;
 pub mod protocol;
 pub mod seq;
+pub mod session;
+pub mod settings;
+pub mod sim;
 
 pub use addr::BusAddr;
-pub use client::{BinaryClient, BinaryEvent, HandshakeOutcome, StatusSnapshot};
-pub use conn::{BinaryConnection, ConnError, Connection, Envelope};
+pub use alert::{Alert, AlertMonitor, AlertThresholds};
+#[cfg(feature = "async")]
+pub use async_client::AsyncBinaryClient;
+pub use capabilities::Capabilities;
+pub use client::{
+    BinaryClient, BinaryEvent, ClientState, HandshakeOutcome, ReconnectPolicy, ShotAbortReason,
+    StatusSnapshot,
+};
+pub use conn::{BinaryConnection, ConnError, Connection, Envelope, Timed, Timestamp};
+pub use discover::{DiscoverOptions, DiscoveredDevice};
+pub use epoch::{EpochMap, EpochMapPolicy, EpochOffset};
 pub use error::WireError;
 pub use frame::{FrameSplitter, RawFrame};
+pub use health::{LinkHealth, NodeHealth};
+pub use level::{LevelAssist, LevelReading};
+#[cfg(feature = "manager")]
+pub use manager::{DeviceId, DeviceManager};
 pub use protocol::{Command, Message};
 pub use seq::{
     Action, ArmSequencer, AvrConfigSequencer, AvrSequencer, CameraConfigSequencer,
-    DisarmSequencer, DspSequencer, PiSequencer, Sequence, ShotDatum, ShotSequencer,
+    DeviceIdentity, DisarmSequencer, DspSequencer, IdentifySequencer, ParamReadSequencer,
+    PiSequencer, Sequence, ShotDatum, ShotSequencer, WifiScanSequencer,
 };
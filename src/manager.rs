@@ -0,0 +1,425 @@
+//! Several devices driven from one thread.
+//!
+//! [`DeviceManager`] owns one [`BinaryClient`] per device, keyed by a
+//! [`DeviceId`] (bay name, SSID, ...), and multiplexes their sockets on a
+//! single readiness poller. [`poll_timeout()`](DeviceManager::poll_timeout)
+//! sleeps until any socket is readable or any client's
+//! [`next_deadline()`](BinaryClient::next_deadline) falls due, then yields
+//! the next `(DeviceId, BinaryEvent)`. Devices are polled round-robin so a
+//! busy one cannot starve the others.
+//!
+//! Requires the `manager` feature (adds `mio`).
+//!
+//! ```no_run
+//! use std::time::Duration;
+//! use ironsight::manager::DeviceManager;
+//!
+//! let mut devices = DeviceManager::new()?;
+//! for (bay, addr) in [("bay1", "10.0.1.2:5100"), ("bay2", "10.0.2.2:5100")] {
+//!     devices.connect(bay, &addr.parse().unwrap())?.handshake();
+//! }
+//! loop {
+//!     if let Some((id, event)) = devices.poll_timeout(Duration::from_secs(1))? {
+//!         println!("{id}: {event:?}");
+//!     }
+//! }
+//! # Ok::<(), ironsight::manager::ManagerError>(())
+//! ```
+
+use std::fmt;
+use std::io;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+
+use mio::net::TcpStream;
+use mio::{Events, Interest, Poll, Token};
+use thiserror::Error;
+
+use crate::client::{BinaryClient, BinaryEvent, ReconnectPolicy};
+use crate::conn::{BinaryConnection, ConnError, Timed};
+
+/// Timeout for [`DeviceManager::connect`] and reconnect attempts.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A client owned by a [`DeviceManager`].
+pub type ManagedClient = BinaryClient<TcpStream>;
+
+/// Name of a managed device.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct DeviceId(String);
+
+impl DeviceId {
+    #[must_use]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self(name.into())
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for DeviceId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<&str> for DeviceId {
+    fn from(name: &str) -> Self {
+        Self::new(name)
+    }
+}
+
+impl From<String> for DeviceId {
+    fn from(name: String) -> Self {
+        Self(name)
+    }
+}
+
+/// Errors from [`DeviceManager`].
+#[derive(Debug, Error)]
+pub enum ManagerError {
+    /// A device's connect or [`poll()`](BinaryClient::poll) failed. Other
+    /// devices are unaffected. A device whose connection is lost (and not
+    /// being reconnected) is no longer polled until replaced.
+    #[error("{id}: {error}")]
+    Device {
+        id: DeviceId,
+        #[source]
+        error: ConnError,
+    },
+
+    #[error("no device named {0:?}")]
+    UnknownDevice(String),
+
+    #[error("poller I/O error: {0}")]
+    Io(#[from] io::Error),
+}
+
+struct Slot {
+    id: DeviceId,
+    client: ManagedClient,
+    /// Connection lost with no reconnect in progress.
+    dead: bool,
+    /// The poller reported the socket readable and the client hasn't
+    /// read it dry since.
+    readable: bool,
+}
+
+/// Owns several [`BinaryClient`]s and polls them on one readiness poller.
+pub struct DeviceManager {
+    poller: Poll,
+    events: Events,
+    /// Indexed by poller token; `None` where a device was removed.
+    slots: Vec<Option<Slot>>,
+    /// Slot polled first next round.
+    next: usize,
+}
+
+impl DeviceManager {
+    /// Create an empty manager.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the OS poller can't be created.
+    pub fn new() -> io::Result<Self> {
+        Ok(Self {
+            poller: Poll::new()?,
+            events: Events::with_capacity(64),
+            slots: Vec::new(),
+            next: 0,
+        })
+    }
+
+    /// Connect to a device (5s timeout) and manage it as `id`. See
+    /// [`add()`](Self::add).
+    ///
+    /// # Errors
+    ///
+    /// [`ManagerError::Device`] if the connection fails.
+    pub fn connect(
+        &mut self,
+        id: impl Into<DeviceId>,
+        addr: &SocketAddr,
+    ) -> Result<&mut ManagedClient, ManagerError> {
+        let id = id.into();
+        match std::net::TcpStream::connect_timeout(addr, CONNECT_TIMEOUT) {
+            Ok(stream) => self.add(id, stream),
+            Err(e) => Err(ManagerError::Device {
+                id,
+                error: ConnError::Io(e),
+            }),
+        }
+    }
+
+    /// Manage a connected stream as `id`, replacing any device already
+    /// registered under that name. Returns the new client for queuing
+    /// operations (`handshake()`, `arm()`, ...).
+    ///
+    /// # Errors
+    ///
+    /// Fails if the stream can't be made non-blocking or registered.
+    pub fn add(
+        &mut self,
+        id: impl Into<DeviceId>,
+        stream: std::net::TcpStream,
+    ) -> Result<&mut ManagedClient, ManagerError> {
+        let id = id.into();
+        self.remove(id.as_str());
+        let mut client = BinaryClient::new(BinaryConnection::new(managed(stream)?));
+        let index = match self.slots.iter().position(Option::is_none) {
+            Some(i) => i,
+            None => {
+                self.slots.push(None);
+                self.slots.len() - 1
+            }
+        };
+        self.poller.registry().register(
+            client.conn_mut().stream_mut(),
+            Token(index),
+            Interest::READABLE,
+        )?;
+        let slot = self.slots[index].insert(Slot {
+            id,
+            client,
+            dead: false,
+            readable: true,
+        });
+        Ok(&mut slot.client)
+    }
+
+    /// Stop managing `id`, returning its client.
+    pub fn remove(&mut self, id: &str) -> Option<ManagedClient> {
+        let index = self.index(id)?;
+        let mut slot = self.slots[index].take()?;
+        let _ = self
+            .poller
+            .registry()
+            .deregister(slot.client.conn_mut().stream_mut());
+        Some(slot.client)
+    }
+
+    /// Re-dial `id` at its current address with exponential backoff when
+    /// its connection drops. See [`BinaryClient::set_reconnect`].
+    ///
+    /// # Errors
+    ///
+    /// [`ManagerError::UnknownDevice`], or [`ManagerError::Device`] if the
+    /// peer address can't be read.
+    pub fn enable_reconnect(
+        &mut self,
+        id: &str,
+        policy: ReconnectPolicy,
+    ) -> Result<(), ManagerError> {
+        let client = self
+            .client_mut(id)
+            .ok_or_else(|| ManagerError::UnknownDevice(id.to_owned()))?;
+        let addr = client
+            .conn_mut()
+            .stream()
+            .peer_addr()
+            .map_err(|e| ManagerError::Device {
+                id: DeviceId::new(id),
+                error: ConnError::Io(e),
+            })?;
+        client.set_reconnect(policy, move || {
+            let stream = std::net::TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT)?;
+            Ok(managed(stream)?)
+        });
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn client(&self, id: &str) -> Option<&ManagedClient> {
+        self.slots
+            .iter()
+            .flatten()
+            .find(|s| s.id.as_str() == id)
+            .map(|s| &s.client)
+    }
+
+    pub fn client_mut(&mut self, id: &str) -> Option<&mut ManagedClient> {
+        self.slots
+            .iter_mut()
+            .flatten()
+            .find(|s| s.id.as_str() == id)
+            .map(|s| &mut s.client)
+    }
+
+    /// Managed device IDs, in no particular order.
+    pub fn ids(&self) -> impl Iterator<Item = &DeviceId> {
+        self.slots.iter().flatten().map(|s| &s.id)
+    }
+
+    /// Managed clients with their IDs (e.g. to arm every bay).
+    pub fn clients_mut(&mut self) -> impl Iterator<Item = (&DeviceId, &mut ManagedClient)> {
+        self.slots
+            .iter_mut()
+            .flatten()
+            .map(|s| (&s.id, &mut s.client))
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.slots.iter().flatten().count()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Poll each device once, round-robin, returning the first event.
+    /// `Ok(None)` when no device has anything to report right now.
+    ///
+    /// # Errors
+    ///
+    /// [`ManagerError::Device`] for a device whose poll failed; the next
+    /// call carries on with the other devices.
+    pub fn poll(&mut self) -> Result<Option<(DeviceId, BinaryEvent)>, ManagerError> {
+        Ok(self.poll_timed()?.map(|(id, timed)| (id, timed.event)))
+    }
+
+    /// Like [`poll()`](Self::poll), with the host time behind the event.
+    /// See [`BinaryClient::poll_timed`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`poll()`](Self::poll).
+    pub fn poll_timed(&mut self) -> Result<Option<(DeviceId, Timed<BinaryEvent>)>, ManagerError> {
+        let n = self.slots.len();
+        for k in 0..n {
+            let index = (self.next + k) % n;
+            let Some(slot) = self.slots[index].as_mut().filter(|s| !s.dead) else {
+                continue;
+            };
+            match slot.client.poll_timed() {
+                Ok(None) => {
+                    if slot.client.conn_mut().is_drained() {
+                        slot.readable = false;
+                    }
+                }
+                Ok(Some(event)) => {
+                    self.next = (index + 1) % n;
+                    if matches!(event.event, BinaryEvent::Reconnected) {
+                        // A new socket replaced the dropped one.
+                        self.poller.registry().register(
+                            slot.client.conn_mut().stream_mut(),
+                            Token(index),
+                            Interest::READABLE,
+                        )?;
+                        slot.readable = true;
+                    }
+                    return Ok(Some((slot.id.clone(), event)));
+                }
+                Err(error) => {
+                    self.next = (index + 1) % n;
+                    slot.dead = !slot.client.is_reconnecting()
+                        && matches!(error, ConnError::Disconnected | ConnError::Io(_));
+                    return Err(ManagerError::Device {
+                        id: slot.id.clone(),
+                        error,
+                    });
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Like [`poll()`](Self::poll), but block up to `timeout` for an event.
+    ///
+    /// Sleeps on the poller until a device socket is readable or the
+    /// earliest [`next_deadline()`](Self::next_deadline) falls due, so
+    /// keepalives and timeouts fire on time for every device. Returns
+    /// `Ok(None)` once `timeout` elapses with no event.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`poll()`](Self::poll), plus [`ManagerError::Io`] if the
+    /// poller fails.
+    pub fn poll_timeout(
+        &mut self,
+        timeout: Duration,
+    ) -> Result<Option<(DeviceId, BinaryEvent)>, ManagerError> {
+        Ok(self
+            .poll_timeout_timed(timeout)?
+            .map(|(id, timed)| (id, timed.event)))
+    }
+
+    /// Like [`poll_timeout()`](Self::poll_timeout), with the host time
+    /// behind the event.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`poll_timeout()`](Self::poll_timeout).
+    pub fn poll_timeout_timed(
+        &mut self,
+        timeout: Duration,
+    ) -> Result<Option<(DeviceId, Timed<BinaryEvent>)>, ManagerError> {
+        let end = Instant::now() + timeout;
+        loop {
+            if let Some(event) = self.poll_timed()? {
+                return Ok(Some(event));
+            }
+            // Readiness is edge-triggered: a socket with bytes left over
+            // from an earlier wake-up won't be reported again.
+            if self.has_unread() {
+                continue;
+            }
+            let now = Instant::now();
+            let wake = self.next_deadline().map_or(end, |d| d.min(end));
+            if wake <= now {
+                if now >= end {
+                    return Ok(None);
+                }
+                continue;
+            }
+            match self.poller.poll(&mut self.events, Some(wake - now)) {
+                Ok(()) => {}
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e.into()),
+            }
+            for event in &self.events {
+                if let Some(Some(slot)) = self.slots.get_mut(event.token().0) {
+                    slot.readable = true;
+                }
+            }
+        }
+    }
+
+    /// Earliest [`BinaryClient::next_deadline`] across live devices.
+    #[must_use]
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.slots
+            .iter()
+            .flatten()
+            .filter(|s| !s.dead)
+            .filter_map(|s| s.client.next_deadline())
+            .min()
+    }
+
+    /// Whether any live, connected device has data its client hasn't read.
+    fn has_unread(&mut self) -> bool {
+        self.slots
+            .iter_mut()
+            .flatten()
+            .filter(|s| !s.dead && !s.client.is_reconnecting())
+            .any(|s| s.readable || s.client.conn_mut().has_pending())
+    }
+
+    fn index(&self, id: &str) -> Option<usize> {
+        self.slots
+            .iter()
+            .position(|s| s.as_ref().is_some_and(|s| s.id.as_str() == id))
+    }
+}
+
+/// Convert a std stream for registration with the poller.
+fn managed(stream: std::net::TcpStream) -> io::Result<TcpStream> {
+    // Best-effort, as in `BinaryClient::from_tcp`.
+    let _ = stream.set_nodelay(true);
+    stream.set_nonblocking(true)?;
+    Ok(TcpStream::from_std(stream))
+}
@@ -0,0 +1,892 @@
+//! Offline capture reader: pcap/pcapng → typed protocol traffic.
+//!
+//! Parses classic pcap and pcapng files, reassembles the IPv4 TCP streams
+//! on the device port (5100), and feeds each direction through
+//! [`FrameSplitter`] and [`RawFrame::parse`]. APP→device frames decode as
+//! [`Command`], device→APP frames as [`Message`] (wrapped in an
+//! [`Envelope`], same as [`BinaryConnection::recv`](crate::BinaryConnection::recv)).
+//!
+//! Pure Rust, no libpcap. Supported link types: Ethernet (incl. 802.1Q),
+//! BSD loopback, raw IPv4, and Linux cooked (SLL/SLL2). Packets on other
+//! link types, IPv6, and IP fragments are skipped.
+//!
+//! ```no_run
+//! use ironsight::pcap::{self, CapturedFrame};
+//!
+//! for item in pcap::read_file("session.pcapng")? {
+//!     match item.frame {
+//!         CapturedFrame::Command { dest, command, .. } => {
+//!             println!("{:.6} APP→{dest} {command:?}", item.timestamp.as_secs_f64())
+//!         }
+//!         CapturedFrame::Message(env) => println!("{:.6} {env:?}", item.timestamp.as_secs_f64()),
+//!         CapturedFrame::Invalid { error, .. } => println!("bad frame: {error}"),
+//!     }
+//! }
+//! # Ok::<(), ironsight::pcap::PcapError>(())
+//! ```
+
+use std::collections::HashMap;
+use std::io;
+use std::net::Ipv4Addr;
+use std::path::Path;
+use std::time::{Duration, Instant, UNIX_EPOCH};
+
+use thiserror::Error;
+
+use crate::addr::BusAddr;
+use crate::conn::{Envelope, Timestamp};
+use crate::error::WireError;
+use crate::frame::{FrameSplitter, RawFrame};
+use crate::protocol::{Command, Message};
+
+/// TCP port of the binary protocol on the device.
+pub const DEVICE_PORT: u16 = 5100;
+
+/// Out-of-order segments buffered per direction before the reader gives up
+/// on a gap (packet missing from the capture) and skips ahead.
+const MAX_PENDING_SEGMENTS: usize = 64;
+
+/// Errors from reading a capture file.
+///
+/// Only container-level problems are errors. Frames that fail to parse or
+/// decode are reported inline as [`CapturedFrame::Invalid`].
+#[derive(Debug, Error)]
+pub enum PcapError {
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+
+    #[error("not a pcap or pcapng file (magic 0x{magic:08X})")]
+    BadMagic { magic: u32 },
+
+    #[error("capture truncated at offset {offset}")]
+    Truncated { offset: usize },
+
+    #[error("malformed pcapng block 0x{block_type:08X} at offset {offset}")]
+    BadBlock { block_type: u32, offset: usize },
+}
+
+/// One protocol frame recovered from a capture.
+#[derive(Debug)]
+pub struct CaptureItem {
+    /// Capture timestamp of the packet that completed the frame (since the
+    /// Unix epoch).
+    pub timestamp: Duration,
+    /// TCP connection index within the capture (0 = first seen). A new
+    /// index is assigned on each SYN, so reconnects are distinguishable.
+    pub stream: usize,
+    pub frame: CapturedFrame,
+}
+
+/// A frame decoded in either direction.
+#[derive(Debug)]
+pub enum CapturedFrame {
+    /// APP→device frame.
+    Command {
+        dest: BusAddr,
+        command: Command,
+        /// Unstuffed payload bytes (before decode).
+        raw: Vec<u8>,
+    },
+    /// Device→APP frame.
+    Message(Box<Envelope>),
+    /// Frame that failed to parse or decode.
+    Invalid {
+        /// `true` for APP→device.
+        to_device: bool,
+        /// Stuffed wire bytes (F0 .. F1).
+        wire: Vec<u8>,
+        error: WireError,
+    },
+}
+
+/// Read a pcap or pcapng file and decode all port-5100 traffic.
+pub fn read_file(path: impl AsRef<Path>) -> Result<Vec<CaptureItem>, PcapError> {
+    let data = std::fs::read(path)?;
+    parse(&data)
+}
+
+/// Decode all port-5100 traffic from an in-memory pcap or pcapng capture.
+pub fn parse(data: &[u8]) -> Result<Vec<CaptureItem>, PcapError> {
+    parse_port(data, DEVICE_PORT)
+}
+
+/// Like [`parse`], for a device listening on a non-standard port
+/// (e.g. a [`SimServer`](crate::sim::SimServer) capture).
+pub fn parse_port(data: &[u8], port: u16) -> Result<Vec<CaptureItem>, PcapError> {
+    let mut asm = Reassembler::new(port);
+    let magic = read_u32(data, 0, false).ok_or(PcapError::Truncated { offset: 0 })?;
+    match magic {
+        PCAPNG_SHB => read_pcapng(data, &mut asm)?,
+        _ => read_pcap(data, magic, &mut asm)?,
+    }
+    Ok(asm.finish())
+}
+
+// ---------------------------------------------------------------------------
+// Container formats
+// ---------------------------------------------------------------------------
+
+const PCAP_MAGIC_US: u32 = 0xA1B2_C3D4;
+const PCAP_MAGIC_NS: u32 = 0xA1B2_3C4D;
+const PCAPNG_SHB: u32 = 0x0A0D_0D0A;
+const PCAPNG_BOM: u32 = 0x1A2B_3C4D;
+const PCAPNG_IDB: u32 = 0x0000_0001;
+const PCAPNG_SPB: u32 = 0x0000_0003;
+const PCAPNG_EPB: u32 = 0x0000_0006;
+
+fn read_u16(data: &[u8], off: usize, be: bool) -> Option<u16> {
+    let b: [u8; 2] = data.get(off..off + 2)?.try_into().ok()?;
+    Some(if be {
+        u16::from_be_bytes(b)
+    } else {
+        u16::from_le_bytes(b)
+    })
+}
+
+fn read_u32(data: &[u8], off: usize, be: bool) -> Option<u32> {
+    let b: [u8; 4] = data.get(off..off + 4)?.try_into().ok()?;
+    Some(if be {
+        u32::from_be_bytes(b)
+    } else {
+        u32::from_le_bytes(b)
+    })
+}
+
+fn read_pcap(data: &[u8], magic: u32, asm: &mut Reassembler) -> Result<(), PcapError> {
+    let (be, nanos) = match magic {
+        PCAP_MAGIC_US => (false, false),
+        PCAP_MAGIC_NS => (false, true),
+        m if m.swap_bytes() == PCAP_MAGIC_US => (true, false),
+        m if m.swap_bytes() == PCAP_MAGIC_NS => (true, true),
+        m => return Err(PcapError::BadMagic { magic: m }),
+    };
+    // The upper bits of the link-type word carry FCS info in newer files.
+    let linktype = read_u32(data, 20, be).ok_or(PcapError::Truncated { offset: 0 })? & 0xFFFF;
+
+    let mut off = 24;
+    // A truncated trailing record (capture killed mid-write) ends the file.
+    while let (Some(sec), Some(frac), Some(caplen)) = (
+        read_u32(data, off, be),
+        read_u32(data, off + 4, be),
+        read_u32(data, off + 8, be),
+    ) {
+        let start = off + 16;
+        let Some(pkt) = data.get(start..start + caplen as usize) else {
+            break;
+        };
+        let sub = if nanos {
+            frac
+        } else {
+            frac.saturating_mul(1000)
+        };
+        let ts = Duration::new(u64::from(sec), sub.min(999_999_999));
+        asm.packet(ts, linktype as u16, pkt);
+        off = start + caplen as usize;
+    }
+    Ok(())
+}
+
+/// Per-interface state from a pcapng Interface Description Block.
+struct Interface {
+    linktype: u16,
+    /// `if_tsresol` raw value (default 6 = microseconds).
+    tsresol: u8,
+    /// `if_tsoffset` in seconds.
+    tsoffset: u64,
+}
+
+impl Interface {
+    fn timestamp(&self, ts: u64) -> Duration {
+        let ts = u128::from(ts);
+        let nanos = if self.tsresol & 0x80 != 0 {
+            (ts * 1_000_000_000) >> (self.tsresol & 0x7F)
+        } else {
+            let units = 10u128.pow(u32::from(self.tsresol).min(30));
+            ts * 1_000_000_000 / units
+        };
+        let offset = u128::from(self.tsoffset) * 1_000_000_000;
+        let total = (nanos + offset).min(u128::from(u64::MAX) * 1_000_000_000);
+        Duration::new(
+            (total / 1_000_000_000) as u64,
+            (total % 1_000_000_000) as u32,
+        )
+    }
+}
+
+fn read_pcapng(data: &[u8], asm: &mut Reassembler) -> Result<(), PcapError> {
+    let mut be = false;
+    let mut interfaces: Vec<Interface> = Vec::new();
+    let mut last_ts = Duration::ZERO;
+    let mut off = 0;
+
+    while off + 12 <= data.len() {
+        let raw_type = read_u32(data, off, be).unwrap_or(0);
+        if raw_type == PCAPNG_SHB {
+            // Each section header sets the byte order for what follows.
+            be = match read_u32(data, off + 8, false) {
+                Some(PCAPNG_BOM) => false,
+                Some(m) if m.swap_bytes() == PCAPNG_BOM => true,
+                Some(m) => return Err(PcapError::BadMagic { magic: m }),
+                None => return Err(PcapError::Truncated { offset: off }),
+            };
+            interfaces.clear();
+        }
+        let block_type = read_u32(data, off, be).unwrap_or(0);
+        let len = read_u32(data, off + 4, be).unwrap_or(0) as usize;
+        if len < 12 || !len.is_multiple_of(4) {
+            return Err(PcapError::BadBlock {
+                block_type,
+                offset: off,
+            });
+        }
+        let Some(block) = data.get(off..off + len) else {
+            // Truncated trailing block.
+            break;
+        };
+        let body = &block[8..len - 4];
+
+        match block_type {
+            PCAPNG_IDB if body.len() >= 8 => {
+                let mut iface = Interface {
+                    linktype: read_u16(body, 0, be).unwrap_or(0),
+                    tsresol: 6,
+                    tsoffset: 0,
+                };
+                let mut opt = 8;
+                while let (Some(code), Some(olen)) =
+                    (read_u16(body, opt, be), read_u16(body, opt + 2, be))
+                {
+                    let olen = olen as usize;
+                    let Some(val) = body.get(opt + 4..opt + 4 + olen) else {
+                        break;
+                    };
+                    match code {
+                        0 => break,
+                        9 if olen >= 1 => iface.tsresol = val[0],
+                        14 if olen >= 8 => {
+                            let b: [u8; 8] = val[..8].try_into().unwrap_or_default();
+                            let v = if be {
+                                i64::from_be_bytes(b)
+                            } else {
+                                i64::from_le_bytes(b)
+                            };
+                            iface.tsoffset = v.max(0) as u64;
+                        }
+                        _ => {}
+                    }
+                    opt += 4 + olen.div_ceil(4) * 4;
+                }
+                interfaces.push(iface);
+            }
+            PCAPNG_EPB if body.len() >= 20 => {
+                let id = read_u32(body, 0, be).unwrap_or(0) as usize;
+                let hi = u64::from(read_u32(body, 4, be).unwrap_or(0));
+                let lo = u64::from(read_u32(body, 8, be).unwrap_or(0));
+                let caplen = read_u32(body, 12, be).unwrap_or(0) as usize;
+                let Some(pkt) = body.get(20..20 + caplen) else {
+                    return Err(PcapError::BadBlock {
+                        block_type,
+                        offset: off,
+                    });
+                };
+                if let Some(iface) = interfaces.get(id) {
+                    last_ts = iface.timestamp((hi << 32) | lo);
+                    asm.packet(last_ts, iface.linktype, pkt);
+                }
+            }
+            PCAPNG_SPB if body.len() >= 4 => {
+                // Simple packets carry no timestamp; reuse the last one seen.
+                let orig = read_u32(body, 0, be).unwrap_or(0) as usize;
+                let pkt = &body[4..];
+                let pkt = &pkt[..orig.min(pkt.len())];
+                if let Some(iface) = interfaces.first() {
+                    asm.packet(last_ts, iface.linktype, pkt);
+                }
+            }
+            _ => {}
+        }
+        off += len;
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Link / IPv4 / TCP
+// ---------------------------------------------------------------------------
+
+const LINKTYPE_NULL: u16 = 0;
+const LINKTYPE_ETHERNET: u16 = 1;
+const LINKTYPE_RAW: u16 = 101;
+const LINKTYPE_LINUX_SLL: u16 = 113;
+const LINKTYPE_IPV4: u16 = 228;
+const LINKTYPE_LINUX_SLL2: u16 = 276;
+
+const ETHERTYPE_IPV4: u16 = 0x0800;
+
+/// Strip the link-layer header, returning the IPv4 packet (if any).
+fn ipv4_payload(linktype: u16, pkt: &[u8]) -> Option<&[u8]> {
+    match linktype {
+        LINKTYPE_ETHERNET => {
+            let mut off = 12;
+            let mut ethertype = read_u16(pkt, off, true)?;
+            // 802.1Q / 802.1ad VLAN tags.
+            while ethertype == 0x8100 || ethertype == 0x88A8 {
+                off += 4;
+                ethertype = read_u16(pkt, off, true)?;
+            }
+            (ethertype == ETHERTYPE_IPV4)
+                .then(|| pkt.get(off + 2..))
+                .flatten()
+        }
+        LINKTYPE_NULL => {
+            // Address family in host byte order of the capturing machine.
+            let family = read_u32(pkt, 0, false)?;
+            (family == 2 || family.swap_bytes() == 2).then(|| &pkt[4..])
+        }
+        LINKTYPE_RAW | LINKTYPE_IPV4 => Some(pkt),
+        LINKTYPE_LINUX_SLL => (read_u16(pkt, 14, true)? == ETHERTYPE_IPV4)
+            .then(|| pkt.get(16..))
+            .flatten(),
+        LINKTYPE_LINUX_SLL2 => (read_u16(pkt, 0, true)? == ETHERTYPE_IPV4)
+            .then(|| pkt.get(20..))
+            .flatten(),
+        _ => None,
+    }
+}
+
+/// One endpoint pair, oriented (source → destination).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct FlowKey {
+    src: (Ipv4Addr, u16),
+    dst: (Ipv4Addr, u16),
+}
+
+impl FlowKey {
+    fn reversed(self) -> Self {
+        Self {
+            src: self.dst,
+            dst: self.src,
+        }
+    }
+}
+
+struct Segment<'a> {
+    key: FlowKey,
+    seq: u32,
+    syn: bool,
+    ack: bool,
+    payload: &'a [u8],
+}
+
+const TCP_SYN: u8 = 0x02;
+const TCP_ACK: u8 = 0x10;
+
+fn tcp_segment(ip: &[u8]) -> Option<Segment<'_>> {
+    if ip.first()? >> 4 != 4 {
+        return None;
+    }
+    let ihl = usize::from(ip[0] & 0x0F) * 4;
+    let total = usize::from(read_u16(ip, 2, true)?);
+    let frag = read_u16(ip, 6, true)?;
+    // Skip non-TCP and fragments (MF set or non-zero offset).
+    if ip.get(9)? != &6 || frag & 0x3FFF != 0 {
+        return None;
+    }
+    // Trim Ethernet padding; tolerate snaplen-truncated packets.
+    let ip = &ip[..total.min(ip.len())];
+    let addr = |o: usize| {
+        ip.get(o..o + 4)
+            .map(|b| Ipv4Addr::new(b[0], b[1], b[2], b[3]))
+    };
+    let (src_ip, dst_ip) = (addr(12)?, addr(16)?);
+
+    let tcp = ip.get(ihl..)?;
+    let src_port = read_u16(tcp, 0, true)?;
+    let dst_port = read_u16(tcp, 2, true)?;
+    let seq = read_u32(tcp, 4, true)?;
+    let data_off = usize::from(tcp.get(12)? >> 4) * 4;
+    let flags = *tcp.get(13)?;
+    Some(Segment {
+        key: FlowKey {
+            src: (src_ip, src_port),
+            dst: (dst_ip, dst_port),
+        },
+        seq,
+        syn: flags & TCP_SYN != 0,
+        ack: flags & TCP_ACK != 0,
+        payload: tcp.get(data_off..)?,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// TCP reassembly
+// ---------------------------------------------------------------------------
+
+/// One direction of a TCP connection.
+struct Flow {
+    stream: usize,
+    to_device: bool,
+    /// Next expected sequence number (`None` until the first segment).
+    next_seq: Option<u32>,
+    /// Out-of-order segments waiting for a gap to fill.
+    pending: Vec<(u32, Duration, Vec<u8>)>,
+    splitter: FrameSplitter,
+}
+
+struct Reassembler {
+    port: u16,
+    flows: HashMap<FlowKey, Flow>,
+    streams: usize,
+    items: Vec<CaptureItem>,
+}
+
+impl Reassembler {
+    fn new(port: u16) -> Self {
+        Self {
+            port,
+            flows: HashMap::new(),
+            streams: 0,
+            items: Vec::new(),
+        }
+    }
+
+    fn packet(&mut self, ts: Duration, linktype: u16, pkt: &[u8]) {
+        let Some(seg) = ipv4_payload(linktype, pkt).and_then(tcp_segment) else {
+            return;
+        };
+        let to_device = seg.key.dst.1 == self.port;
+        if !to_device && seg.key.src.1 != self.port {
+            return;
+        }
+
+        // A SYN starts a fresh connection on this 4-tuple. The client's SYN
+        // allocates the stream index; the SYN-ACK joins it.
+        if seg.syn {
+            let stream = match self.flows.get(&seg.key.reversed()) {
+                Some(rev) if seg.ack => rev.stream,
+                _ => self.next_stream(),
+            };
+            self.flows.insert(
+                seg.key,
+                Flow::new(stream, to_device, Some(seg.seq.wrapping_add(1))),
+            );
+            return;
+        }
+        if !self.flows.contains_key(&seg.key) {
+            // Capture started mid-connection: sync to the first segment.
+            let stream = match self.flows.get(&seg.key.reversed()) {
+                Some(rev) => rev.stream,
+                None => self.next_stream(),
+            };
+            self.flows
+                .insert(seg.key, Flow::new(stream, to_device, None));
+        }
+        if seg.payload.is_empty() {
+            return;
+        }
+        let flow = self.flows.get_mut(&seg.key).expect("flow inserted above");
+        flow.segment(seg.seq, ts, seg.payload, &mut self.items);
+    }
+
+    fn next_stream(&mut self) -> usize {
+        self.streams += 1;
+        self.streams - 1
+    }
+
+    /// Flush buffered segments past any unfilled gaps and return all items.
+    fn finish(mut self) -> Vec<CaptureItem> {
+        let mut flows: Vec<_> = self.flows.into_values().collect();
+        flows.sort_by_key(|f| (f.stream, !f.to_device));
+        for flow in &mut flows {
+            while flow.skip_gap(&mut self.items) {}
+        }
+        self.items
+    }
+}
+
+impl Flow {
+    fn new(stream: usize, to_device: bool, next_seq: Option<u32>) -> Self {
+        Self {
+            stream,
+            to_device,
+            next_seq,
+            pending: Vec::new(),
+            splitter: FrameSplitter::new(),
+        }
+    }
+
+    fn segment(&mut self, seq: u32, ts: Duration, data: &[u8], out: &mut Vec<CaptureItem>) {
+        let next = *self.next_seq.get_or_insert(seq);
+        if (seq.wrapping_sub(next) as i32) > 0 {
+            self.pending.push((seq, ts, data.to_vec()));
+            if self.pending.len() > MAX_PENDING_SEGMENTS {
+                self.skip_gap(out);
+            }
+            return;
+        }
+        self.deliver(seq, ts, data, out);
+        self.drain_pending(out);
+    }
+
+    /// Deliver in-order bytes starting at `seq`, trimming any retransmitted
+    /// prefix.
+    fn deliver(&mut self, seq: u32, ts: Duration, data: &[u8], out: &mut Vec<CaptureItem>) {
+        let next = self.next_seq.unwrap_or(seq);
+        let skip = next.wrapping_sub(seq) as usize;
+        if skip >= data.len() {
+            return;
+        }
+        let data = &data[skip..];
+        self.next_seq = Some(next.wrapping_add(data.len() as u32));
+        for wire in self.splitter.feed(data) {
+            out.push(CaptureItem {
+                timestamp: ts,
+                stream: self.stream,
+                frame: decode(&wire, self.to_device, ts),
+            });
+        }
+    }
+
+    fn drain_pending(&mut self, out: &mut Vec<CaptureItem>) {
+        while let Some(next) = self.next_seq {
+            let Some(i) = self
+                .pending
+                .iter()
+                .position(|(s, _, _)| (s.wrapping_sub(next) as i32) <= 0)
+            else {
+                break;
+            };
+            let (seq, ts, data) = self.pending.swap_remove(i);
+            self.deliver(seq, ts, &data, out);
+        }
+    }
+
+    /// Jump over a gap to the earliest buffered segment. The splitter is
+    /// reset since any partial frame spans the missing bytes.
+    fn skip_gap(&mut self, out: &mut Vec<CaptureItem>) -> bool {
+        let Some(next) = self.next_seq else {
+            return false;
+        };
+        let Some(seq) = self
+            .pending
+            .iter()
+            .map(|(s, _, _)| *s)
+            .min_by_key(|s| s.wrapping_sub(next))
+        else {
+            return false;
+        };
+        self.next_seq = Some(seq);
+        self.splitter = FrameSplitter::new();
+        self.drain_pending(out);
+        true
+    }
+}
+
+/// `ts` is the capture time since the Unix epoch.
+fn decode(wire: &[u8], to_device: bool, ts: Duration) -> CapturedFrame {
+    let invalid = |error| CapturedFrame::Invalid {
+        to_device,
+        wire: wire.to_vec(),
+        error,
+    };
+    let frame = match RawFrame::parse(wire) {
+        Ok(f) => f,
+        Err(e) => return invalid(e),
+    };
+    if to_device {
+        match Command::decode(&frame) {
+            Ok(command) => CapturedFrame::Command {
+                dest: frame.dest,
+                command,
+                raw: frame.payload,
+            },
+            Err(e) => invalid(e.with_raw(&frame.payload)),
+        }
+    } else {
+        match Message::decode(&frame) {
+            Ok(message) => CapturedFrame::Message(Box::new(Envelope {
+                src: frame.src,
+                type_id: frame.type_id,
+                raw: frame.payload,
+                message,
+                // Only the wall-clock half is meaningful offline.
+                received: Timestamp {
+                    instant: Instant::now(),
+                    system: UNIX_EPOCH + ts,
+                },
+            })),
+            Err(e) => invalid(e.with_raw(&frame.payload)),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::config::{MODE_CHIPPING, ModeSet};
+    use crate::protocol::status::{ConfigAck, StatusPoll, Text};
+
+    const APP: ([u8; 4], u16) = ([192, 168, 2, 10], 50123);
+    const DEV: ([u8; 4], u16) = ([192, 168, 2, 1], DEVICE_PORT);
+
+    fn tcp_eth(
+        src: ([u8; 4], u16),
+        dst: ([u8; 4], u16),
+        seq: u32,
+        flags: u8,
+        data: &[u8],
+    ) -> Vec<u8> {
+        let mut pkt = vec![0u8; 12];
+        pkt.extend_from_slice(&ETHERTYPE_IPV4.to_be_bytes());
+        let total = (20 + 20 + data.len()) as u16;
+        pkt.extend_from_slice(&[0x45, 0]);
+        pkt.extend_from_slice(&total.to_be_bytes());
+        pkt.extend_from_slice(&[0, 0, 0x40, 0, 64, 6, 0, 0]);
+        pkt.extend_from_slice(&src.0);
+        pkt.extend_from_slice(&dst.0);
+        pkt.extend_from_slice(&src.1.to_be_bytes());
+        pkt.extend_from_slice(&dst.1.to_be_bytes());
+        pkt.extend_from_slice(&seq.to_be_bytes());
+        pkt.extend_from_slice(&[0, 0, 0, 0, 0x50, flags, 0xFF, 0xFF, 0, 0, 0, 0]);
+        pkt.extend_from_slice(data);
+        pkt
+    }
+
+    fn pcap_file(packets: &[(u32, Vec<u8>)]) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(&PCAP_MAGIC_US.to_le_bytes());
+        out.extend_from_slice(&[2, 0, 4, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xFF, 0xFF, 0, 0]);
+        out.extend_from_slice(&u32::from(LINKTYPE_ETHERNET).to_le_bytes());
+        for (usec, pkt) in packets {
+            out.extend_from_slice(&1_700_000_000u32.to_le_bytes());
+            out.extend_from_slice(&usec.to_le_bytes());
+            out.extend_from_slice(&(pkt.len() as u32).to_le_bytes());
+            out.extend_from_slice(&(pkt.len() as u32).to_le_bytes());
+            out.extend_from_slice(pkt);
+        }
+        out
+    }
+
+    fn pcapng_block(out: &mut Vec<u8>, block_type: u32, body: &[u8]) {
+        let padded = body.len().div_ceil(4) * 4;
+        let len = (12 + padded) as u32;
+        out.extend_from_slice(&block_type.to_be_bytes());
+        out.extend_from_slice(&len.to_be_bytes());
+        out.extend_from_slice(body);
+        out.resize(out.len() + padded - body.len(), 0);
+        out.extend_from_slice(&len.to_be_bytes());
+    }
+
+    fn poll() -> Vec<u8> {
+        Command::StatusPoll(StatusPoll { pi_mode: false })
+            .encode(BusAddr::Avr)
+            .encode()
+    }
+
+    fn mode_set() -> Vec<u8> {
+        Command::ModeSet(ModeSet {
+            mode: MODE_CHIPPING,
+        })
+        .encode(BusAddr::Avr)
+        .encode()
+    }
+
+    fn ack() -> Vec<u8> {
+        let ack = ConfigAck {
+            bus_addr: 0x30,
+            acked_cmd: 0x25,
+        };
+        Message::ConfigAck(ack)
+            .encode(BusAddr::Avr, BusAddr::App)
+            .encode()
+    }
+
+    fn text(s: &str) -> Vec<u8> {
+        let text = Text {
+            text: s.to_string(),
+        };
+        Message::Text(text)
+            .encode(BusAddr::Avr, BusAddr::App)
+            .encode()
+    }
+
+    fn text_of(item: &CaptureItem) -> Option<&str> {
+        match &item.frame {
+            CapturedFrame::Message(env) => match &env.message {
+                Message::Text(t) => Some(t.text.as_str()),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn pcap_both_directions() {
+        let (poll, mode, ack, armed) = (poll(), mode_set(), ack(), text("ARMED"));
+        let mut both = poll.clone();
+        both.extend_from_slice(&mode);
+        let packets = [
+            (0, tcp_eth(APP, DEV, 999, TCP_SYN, &[])),
+            (1, tcp_eth(DEV, APP, 4999, TCP_SYN | TCP_ACK, &[])),
+            // Two frames in one segment.
+            (10, tcp_eth(APP, DEV, 1000, TCP_ACK, &both)),
+            // Device reply split across segments.
+            (20, tcp_eth(DEV, APP, 5000, TCP_ACK, &ack[..4])),
+            (30, tcp_eth(DEV, APP, 5000 + 4, TCP_ACK, &ack[4..])),
+            (
+                40,
+                tcp_eth(DEV, APP, 5000 + ack.len() as u32, TCP_ACK, &armed),
+            ),
+        ];
+        let items = parse(&pcap_file(&packets)).unwrap();
+        assert_eq!(items.len(), 4);
+        assert!(items.iter().all(|i| i.stream == 0));
+        assert!(matches!(
+            items[0].frame,
+            CapturedFrame::Command {
+                dest: BusAddr::Avr,
+                command: Command::StatusPoll(_),
+                ..
+            }
+        ));
+        assert!(matches!(
+            items[1].frame,
+            CapturedFrame::Command {
+                command: Command::ModeSet(ModeSet {
+                    mode: MODE_CHIPPING
+                }),
+                ..
+            }
+        ));
+        // Completed by the second segment, so it takes that timestamp.
+        assert_eq!(items[2].timestamp, Duration::new(1_700_000_000, 30_000));
+        let CapturedFrame::Message(env) = &items[2].frame else {
+            panic!("expected device message");
+        };
+        assert_eq!(env.src, BusAddr::Avr);
+        assert!(matches!(env.message, Message::ConfigAck(_)));
+        assert_eq!(env.received.system, UNIX_EPOCH + items[2].timestamp);
+        assert_eq!(text_of(&items[3]), Some("ARMED"));
+    }
+
+    #[test]
+    fn reorder_and_retransmit() {
+        let (a, b) = (text("ONE"), text("TWO"));
+        let mut stream = a.clone();
+        stream.extend_from_slice(&b);
+        let mid = a.len() + 2;
+        let packets = [
+            // No SYN: capture started mid-connection.
+            (
+                0,
+                tcp_eth(DEV, APP, 100 + mid as u32, TCP_ACK, &stream[mid..]),
+            ),
+            (1, tcp_eth(DEV, APP, 100, TCP_ACK, &stream[..mid])),
+            (2, tcp_eth(DEV, APP, 100, TCP_ACK, &stream[..mid])),
+        ];
+        // The first-seen segment anchors the stream, so the earlier bytes
+        // read as a retransmit and the partial frame is dropped.
+        let items = parse(&pcap_file(&packets)).unwrap();
+        assert!(items.is_empty());
+
+        let packets = [
+            (0, tcp_eth(DEV, APP, 99, TCP_SYN, &[])),
+            (
+                1,
+                tcp_eth(DEV, APP, 100 + mid as u32, TCP_ACK, &stream[mid..]),
+            ),
+            (2, tcp_eth(DEV, APP, 100, TCP_ACK, &stream[..mid])),
+            (3, tcp_eth(DEV, APP, 100, TCP_ACK, &stream[..mid])),
+        ];
+        let items = parse(&pcap_file(&packets)).unwrap();
+        let texts: Vec<_> = items.iter().filter_map(text_of).collect();
+        assert_eq!(texts, ["ONE", "TWO"]);
+    }
+
+    #[test]
+    fn gap_is_skipped_at_end() {
+        let (a, b) = (text("LOST"), text("KEPT"));
+        let packets = [
+            (0, tcp_eth(DEV, APP, 99, TCP_SYN, &[])),
+            // Bytes 100.. never captured.
+            (1, tcp_eth(DEV, APP, 100 + a.len() as u32, TCP_ACK, &b)),
+        ];
+        let items = parse(&pcap_file(&packets)).unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(text_of(&items[0]), Some("KEPT"));
+    }
+
+    #[test]
+    fn pcapng_big_endian_nanos() {
+        let mut file = Vec::new();
+        let mut shb = PCAPNG_BOM.to_be_bytes().to_vec();
+        shb.extend_from_slice(&[0, 1, 0, 0]);
+        shb.extend_from_slice(&(-1i64).to_be_bytes());
+        pcapng_block(&mut file, PCAPNG_SHB, &shb);
+
+        let mut idb = LINKTYPE_ETHERNET.to_be_bytes().to_vec();
+        idb.extend_from_slice(&[0, 0, 0, 0, 0xFF, 0xFF]);
+        // if_tsresol = 9 (nanoseconds), then opt_endofopt.
+        idb.extend_from_slice(&[0, 9, 0, 1, 9, 0, 0, 0, 0, 0, 0, 0]);
+        pcapng_block(&mut file, PCAPNG_IDB, &idb);
+
+        let pkt = tcp_eth(APP, DEV, 1, TCP_ACK, &poll());
+        let ts: u64 = 1_700_000_000_123_456_789;
+        let mut epb = 0u32.to_be_bytes().to_vec();
+        epb.extend_from_slice(&((ts >> 32) as u32).to_be_bytes());
+        epb.extend_from_slice(&(ts as u32).to_be_bytes());
+        epb.extend_from_slice(&(pkt.len() as u32).to_be_bytes());
+        epb.extend_from_slice(&(pkt.len() as u32).to_be_bytes());
+        epb.extend_from_slice(&pkt);
+        pcapng_block(&mut file, PCAPNG_EPB, &epb);
+
+        let items = parse(&file).unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(
+            items[0].timestamp,
+            Duration::new(1_700_000_000, 123_456_789)
+        );
+        assert!(matches!(
+            items[0].frame,
+            CapturedFrame::Command {
+                command: Command::StatusPoll(_),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn other_ports_and_bad_frames() {
+        let other = ([192, 168, 2, 1], 1258);
+        let mut bad = ack();
+        let n = bad.len();
+        bad[n - 2] ^= 0x01; // corrupt checksum
+        let packets = [
+            (0, tcp_eth(APP, other, 1, TCP_ACK, &poll())),
+            (1, tcp_eth(DEV, APP, 1, TCP_ACK, &bad)),
+        ];
+        let items = parse(&pcap_file(&packets)).unwrap();
+        assert_eq!(items.len(), 1);
+        assert!(matches!(
+            items[0].frame,
+            CapturedFrame::Invalid {
+                to_device: false,
+                error: WireError::ChecksumMismatch { .. },
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn bad_magic() {
+        assert!(matches!(
+            parse(&[0u8; 24]),
+            Err(PcapError::BadMagic { magic: 0 })
+        ));
+        assert!(matches!(parse(&[0xD4]), Err(PcapError::Truncated { .. })));
+    }
+}
@@ -2,6 +2,7 @@
 
 use crate::codec;
 use crate::error::{Result, WireError};
+use crate::protocol::handshake::FirmwareVersion;
 
 /// Camera start/stop (2 bytes). Type 0x81.
 ///
@@ -179,6 +180,27 @@ impl CamConfig {
         }
     }
 
+    /// The Fusion preset for a PI firmware version (see
+    /// [`PiSync::info()`](crate::seq::PiSync::info)):
+    /// [`raw_fusion_preset()`](Self::raw_fusion_preset) on BM17.04 and
+    /// later, [`fusion_preset()`](Self::fusion_preset) otherwise or if the
+    /// version is unknown.
+    #[must_use]
+    pub fn fusion_preset_for(pi_firmware: Option<&FirmwareVersion>) -> Self {
+        if Self::supports_raw_fusion(pi_firmware) {
+            Self::raw_fusion_preset()
+        } else {
+            Self::fusion_preset()
+        }
+    }
+
+    /// Whether a PI firmware version runs raw Fusion (BM17.04 and later).
+    #[must_use]
+    pub fn supports_raw_fusion(pi_firmware: Option<&FirmwareVersion>) -> bool {
+        let first = FirmwareVersion::parse("BM17.04");
+        pi_firmware.is_some_and(|v| Some(v) >= first.as_ref())
+    }
+
     /// Whether this config uses Fusion mode.
     ///
     /// Checks for either high-res Fusion (1640x1232, older firmware) or
@@ -327,6 +349,29 @@ impl CamImageAvail {
             })
         }
     }
+
+    /// Encode to the long form (67 bytes) when either timestamp is set or
+    /// fusion/video is flagged, otherwise to the 2-byte short form.
+    pub fn encode(&self) -> Vec<u8> {
+        let long = self.fusion_available
+            || self.video_available
+            || self.streaming_timestamp.is_some()
+            || self.fusion_timestamp.is_some();
+        if !long {
+            return vec![0x01, u8::from(self.streaming_available)];
+        }
+        let mut buf = vec![0u8; 67];
+        buf[0] = 0x42;
+        buf[1] = u8::from(self.streaming_available);
+        buf[2] = u8::from(self.fusion_available) | (u8::from(self.video_available) << 1);
+        for (ts, offset) in [(&self.streaming_timestamp, 3), (&self.fusion_timestamp, 35)] {
+            if let Some(ts) = ts {
+                let n = ts.len().min(32);
+                buf[offset..offset + n].copy_from_slice(&ts.as_bytes()[..n]);
+            }
+        }
+        buf
+    }
 }
 
 /// Sensor activation data (APP→PI). Type 0x90.
@@ -363,6 +408,53 @@ impl SensorActResp {
             payload: payload.to_vec(),
         })
     }
+
+    pub fn encode(&self) -> Vec<u8> {
+        self.payload.clone()
+    }
+}
+
+/// WiFi scan page request (4 bytes). Type 0x87 (APP→PI).
+///
+/// `[03 00 XX 02]` where `XX` is the page index. The official app asks for
+/// pages 0-7 during PI sync.
+#[derive(Debug, Clone)]
+pub struct WifiScanReq {
+    pub page: u8,
+}
+
+impl WifiScanReq {
+    pub fn decode(payload: &[u8]) -> Result<Self> {
+        if payload.len() < 4 {
+            return Err(WireError::payload_too_short("WifiScanReq", 4, payload.len()));
+        }
+        Ok(Self { page: payload[2] })
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        vec![0x03, 0x00, self.page, 0x02]
+    }
+}
+
+/// WiFi scan results page (variable). Type 0x87 (PI→APP).
+///
+/// Kept raw: the network entry layout hasn't been decoded from a capture
+/// yet, so the page is passed on as received.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WifiScan {
+    pub payload: Vec<u8>,
+}
+
+impl WifiScan {
+    pub fn decode(payload: &[u8]) -> Result<Self> {
+        Ok(Self {
+            payload: payload.to_vec(),
+        })
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        self.payload.clone()
+    }
 }
 
 /// Parse a null-padded fixed-width string, returning None if empty.
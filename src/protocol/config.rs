@@ -19,6 +19,78 @@ pub const MODE_PUTTING_DEDICATED: u8 = 14;
 pub const MODE_CHIP_IN: u8 = 15;
 pub const MODE_CHIP_OUT: u8 = 16;
 
+/// Typed detection mode. Discriminants are the commsIndex values above.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum DetectionMode {
+    Indoor = MODE_INDOOR,
+    LongIndoor = MODE_LONG_INDOOR,
+    Putting = MODE_PUTTING,
+    ClubSwing = MODE_CLUB_SWING,
+    Chipping = MODE_CHIPPING,
+    SimPutting = MODE_SIM_PUTTING,
+    Outdoor = MODE_OUTDOOR,
+    RawSampling = MODE_RAW_SAMPLING,
+    PuttingDedicated = MODE_PUTTING_DEDICATED,
+    ChipIn = MODE_CHIP_IN,
+    ChipOut = MODE_CHIP_OUT,
+}
+
+impl DetectionMode {
+    /// The commsIndex sent in 0xA5.
+    #[must_use]
+    pub fn comms_index(self) -> u8 {
+        self as u8
+    }
+
+    /// Look up a mode by commsIndex. `None` for unassigned values.
+    #[must_use]
+    pub fn from_comms_index(index: u8) -> Option<Self> {
+        Some(match index {
+            MODE_INDOOR => Self::Indoor,
+            MODE_LONG_INDOOR => Self::LongIndoor,
+            MODE_PUTTING => Self::Putting,
+            MODE_CLUB_SWING => Self::ClubSwing,
+            MODE_CHIPPING => Self::Chipping,
+            MODE_SIM_PUTTING => Self::SimPutting,
+            MODE_OUTDOOR => Self::Outdoor,
+            MODE_RAW_SAMPLING => Self::RawSampling,
+            MODE_PUTTING_DEDICATED => Self::PuttingDedicated,
+            MODE_CHIP_IN => Self::ChipIn,
+            MODE_CHIP_OUT => Self::ChipOut,
+            _ => return None,
+        })
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Writable AVR parameter IDs (0xBF)
+// ---------------------------------------------------------------------------
+
+/// Ball type (INT24, see [`BallType`]).
+pub const PARAM_BALL_TYPE: u8 = 0x06;
+/// Radar config (INT24, mode-dependent).
+pub const PARAM_RADAR_CONFIG: u8 = 0x07;
+/// Surface firmness index (INT24).
+pub const PARAM_SURFACE_FIRMNESS: u8 = 0x08;
+/// Outdoor minimum track percentage (FLOAT40, 0.6-1.0).
+pub const PARAM_MIN_TRACK_PCT: u8 = 0x0F;
+/// Mode sub-index (INT24).
+pub const PARAM_MODE_SUB_INDEX: u8 = 0x16;
+/// Config flags (INT24).
+pub const PARAM_CONFIG_FLAGS: u8 = 0x25;
+/// Driver tee height in meters (FLOAT40).
+pub const PARAM_TEE_HEIGHT: u8 = 0x26;
+
+/// Ball type, written to [`PARAM_BALL_TYPE`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BallType {
+    /// RCT (Radar Capture Technology) ball, for indoor spin measurement.
+    Rct = 0,
+    /// Standard golf ball.
+    Standard = 1,
+}
+
 /// Set detection mode (3 bytes). Type 0xA5.
 ///
 /// Payload: `[02 00 XX]` where XX is the commsIndex.
@@ -39,6 +111,12 @@ impl ModeSet {
     pub fn encode(&self) -> Vec<u8> {
         vec![0x02, 0x00, self.mode]
     }
+
+    /// The typed mode, if the commsIndex is a known one.
+    #[must_use]
+    pub fn detection_mode(&self) -> Option<DetectionMode> {
+        DetectionMode::from_comms_index(self.mode)
+    }
 }
 
 /// AVR configuration control (2 bytes). Type 0xB0.
@@ -93,7 +171,7 @@ impl ParamReadReq {
 ///
 /// INT24 format (7B): `[06 00 00 param_id val_hi val_mid val_lo]`
 /// FLOAT40 format (9B): `[08 00 00 param_id exp_hi exp_lo mant_hi mant_mid mant_lo]`
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ParamValue {
     /// Parameter ID
     pub param_id: u8,
@@ -102,7 +180,7 @@ pub struct ParamValue {
 }
 
 /// The data portion of a parameter value.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum ParamData {
     Int24(i32),
     Float40(f64),
@@ -157,7 +235,7 @@ impl ParamValue {
 /// Radar calibration (7 bytes). Type 0xA4. Bidirectional.
 ///
 /// Format: `[06 range_hi range_lo 00 height_mm 00 00]`
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct RadarCal {
     /// Sensor-to-tee distance (mm)
     pub range_mm: u16,
@@ -207,6 +285,14 @@ impl ConfigResp {
         }
         Ok(Self { params })
     }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buf = vec![0x44];
+        for &p in &self.params {
+            codec::write_int16(&mut buf, p);
+        }
+        buf
+    }
 }
 
 /// AVR config response (17 bytes). Type 0xA2.
@@ -225,6 +311,10 @@ impl AvrConfigResp {
         })
     }
 
+    pub fn encode(&self) -> Vec<u8> {
+        self.payload.clone()
+    }
+
     /// Wire format version: 1 = Mevo+ (Gen1), 2 = Mevo Gen2.
     pub fn version(&self) -> u8 {
         self.payload.get(1).copied().unwrap_or(0)
@@ -0,0 +1,282 @@
+//! Decoder for Gen2 0xF0 DSP_DEBUG output.
+//!
+//! The Gen2 DSP streams its console as VT100 terminal text, split across
+//! frames at arbitrary byte boundaries. [`DspDebugDecoder`] models a single
+//! terminal line: printable bytes are written at the cursor, `\r`,
+//! backspace and CSI cursor movement reposition it, erase-in-line clears,
+//! and `\n` completes the line. Colour codes are dropped but used as a
+//! severity hint alongside the line's content.
+
+/// Lines longer than this are emitted early rather than buffered forever.
+/// Cursor movement is clamped to it too.
+const MAX_LINE: usize = 1024;
+
+/// CSI parameter bytes kept; longer sequences are garbage.
+const MAX_CSI_PARAMS: usize = 32;
+
+/// Guessed severity of a [`DspDebugLine`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    /// Guess from line content ("error"/"fail"/"fault" → `Error`,
+    /// "warn"/"timeout"/"retry" → `Warning`).
+    #[must_use]
+    pub fn guess(text: &str) -> Self {
+        let lower = text.to_ascii_lowercase();
+        let has = |words: &[&str]| words.iter().any(|w| lower.contains(w));
+        if has(&["error", "fail", "fault", "abort", "overflow"]) {
+            Self::Error
+        } else if has(&["warn", "timeout", "retry", "invalid"]) {
+            Self::Warning
+        } else {
+            Self::Info
+        }
+    }
+}
+
+/// One complete line of DSP console output, escape sequences removed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct DspDebugLine {
+    pub severity: Severity,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Default)]
+enum Escape {
+    #[default]
+    None,
+    /// Saw ESC.
+    Esc,
+    /// Saw `ESC (` or `ESC )`: the next byte selects a charset.
+    Charset,
+    /// Inside `ESC [`, collecting parameter bytes.
+    Csi(String),
+}
+
+/// Stateful VT100 decoder for [`Message::DspDebug`](super::Message::DspDebug)
+/// payloads. Feed every payload in arrival order; partial lines and escape
+/// sequences carry over between calls.
+#[derive(Debug, Clone, Default)]
+pub struct DspDebugDecoder {
+    line: Vec<u8>,
+    cursor: usize,
+    escape: Escape,
+    /// Severity implied by the current SGR colour (red/yellow).
+    color: Option<Severity>,
+    /// Highest colour severity seen on the current line.
+    line_color: Option<Severity>,
+}
+
+impl DspDebugDecoder {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed one payload, returning every line it completes.
+    pub fn feed(&mut self, bytes: &[u8]) -> Vec<DspDebugLine> {
+        let mut out = Vec::new();
+        for &b in bytes {
+            self.byte(b, &mut out);
+        }
+        out
+    }
+
+    /// Take the buffered partial line, if it has any text.
+    pub fn flush(&mut self) -> Option<DspDebugLine> {
+        let text = String::from_utf8_lossy(&self.line).trim_end().to_owned();
+        let color = self.line_color.take();
+        self.line.clear();
+        self.cursor = 0;
+        if text.trim().is_empty() {
+            return None;
+        }
+        let severity = Severity::guess(&text).max(color.unwrap_or(Severity::Info));
+        Some(DspDebugLine { severity, text })
+    }
+
+    fn byte(&mut self, b: u8, out: &mut Vec<DspDebugLine>) {
+        match std::mem::take(&mut self.escape) {
+            Escape::None => {}
+            Escape::Esc => {
+                self.escape = match b {
+                    b'[' => Escape::Csi(String::new()),
+                    b'(' | b')' => Escape::Charset,
+                    _ => Escape::None,
+                };
+                return;
+            }
+            Escape::Charset => return,
+            Escape::Csi(mut params) => {
+                if (0x40..=0x7E).contains(&b) {
+                    self.csi(&params, b, out);
+                } else {
+                    if params.len() < MAX_CSI_PARAMS {
+                        params.push(char::from(b));
+                    }
+                    self.escape = Escape::Csi(params);
+                }
+                return;
+            }
+        }
+        match b {
+            0x1B => self.escape = Escape::Esc,
+            b'\n' => out.extend(self.flush()),
+            b'\r' => self.cursor = 0,
+            0x08 => self.cursor = self.cursor.saturating_sub(1),
+            b'\t' => {
+                let next = (self.cursor / 8 + 1) * 8;
+                while self.cursor < next {
+                    self.put(b' ');
+                }
+            }
+            0x20..=0x7E => {
+                self.put(b);
+                if let Some(color) = self.color {
+                    self.line_color = self.line_color.max(Some(color));
+                }
+                if self.line.len() >= MAX_LINE {
+                    out.extend(self.flush());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Write a byte at the cursor, overwriting or extending the line.
+    fn put(&mut self, b: u8) {
+        while self.line.len() < self.cursor {
+            self.line.push(b' ');
+        }
+        if self.cursor < self.line.len() {
+            self.line[self.cursor] = b;
+        } else {
+            self.line.push(b);
+        }
+        self.cursor += 1;
+    }
+
+    fn csi(&mut self, params: &str, cmd: u8, out: &mut Vec<DspDebugLine>) {
+        let mut args = params
+            .trim_start_matches('?')
+            .split(';')
+            .map(|p| p.parse::<usize>().ok());
+        let n = args.next().flatten();
+        let count = n.unwrap_or(1).max(1);
+        match cmd {
+            // Cursor forward / back / to column. Parameters come from the
+            // device, so keep the cursor within a line's worth.
+            b'C' => self.cursor = self.cursor.saturating_add(count).min(MAX_LINE - 1),
+            b'D' => self.cursor = self.cursor.saturating_sub(count),
+            b'G' => self.cursor = (count - 1).min(MAX_LINE - 1),
+            // Erase in line: 0 = to end, 1 = to start, 2 = whole line.
+            b'K' => match n.unwrap_or(0) {
+                0 => self.line.truncate(self.cursor),
+                1 => {
+                    let end = (self.cursor + 1).min(self.line.len());
+                    self.line[..end].fill(b' ');
+                }
+                _ => self.line.clear(),
+            },
+            // Erase display: only the current line is modelled.
+            b'J' if n == Some(2) => {
+                self.line.clear();
+                self.cursor = 0;
+            }
+            // Any vertical movement ends the current line.
+            b'A' | b'B' | b'E' | b'F' => out.extend(self.flush()),
+            b'H' | b'f' => {
+                out.extend(self.flush());
+                let column = args.next().flatten().unwrap_or(1).max(1) - 1;
+                self.cursor = column.min(MAX_LINE - 1);
+            }
+            b'm' => self.sgr(params),
+            _ => {}
+        }
+    }
+
+    fn sgr(&mut self, params: &str) {
+        for p in params.split(';') {
+            match p.parse::<u8>().unwrap_or(0) {
+                0 | 39 => self.color = None,
+                31 | 91 => self.color = Some(Severity::Error),
+                33 | 93 => self.color = Some(Severity::Warning),
+                30..=37 | 90..=97 => self.color = None,
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(lines: Vec<DspDebugLine>) -> Vec<String> {
+        lines.into_iter().map(|l| l.text).collect()
+    }
+
+    #[test]
+    fn lines_span_frames() {
+        let mut d = DspDebugDecoder::new();
+        assert!(d.feed(b"Track sta").is_empty());
+        assert_eq!(texts(d.feed(b"rt\r\nv=42\r\nTr")), ["Track start", "v=42"]);
+        assert_eq!(d.flush().unwrap().text, "Tr");
+        assert!(d.flush().is_none());
+    }
+
+    #[test]
+    fn strips_escapes_and_applies_cursor_moves() {
+        let mut d = DspDebugDecoder::new();
+        // Colour, split CSI, carriage-return overwrite, erase to end.
+        let lines = d.feed(b"\x1b[1;32mOK\x1b[0m done\r\n\x1b[");
+        assert_eq!(texts(lines), ["OK done"]);
+        let lines = d.feed(b"2Kprogress 10%\rprogress 100%\r\nabcdef\x1b[3D\x1b[KXY\n");
+        assert_eq!(texts(lines), ["progress 100%", "abcXY"]);
+        // Backspace, tab, cursor home flushes.
+        let lines = d.feed(b"ab\x08C\tz\x1b[2;5HE");
+        assert_eq!(texts(lines), ["aC      z"]);
+        assert_eq!(d.flush().unwrap().text, "    E");
+    }
+
+    #[test]
+    fn huge_cursor_parameters_are_clamped() {
+        let mut d = DspDebugDecoder::new();
+        assert!(d.feed(b"\x1b[18446744073709551615C\x1b[5C").is_empty());
+        let lines = d.feed(b"\x1b[200000000Cx\n");
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].text.len(), MAX_LINE);
+        assert!(lines[0].text.ends_with('x'));
+
+        let lines = d.feed(b"\x1b[99999999999G\x1b[1;99999999999Hy\n");
+        assert_eq!(lines[0].text.len(), MAX_LINE);
+        let long_params = [b"\x1b[".as_slice(), &[b'9'; 100_000], b"Cz\n"].concat();
+        assert!(d.feed(&long_params)[0].text.len() <= MAX_LINE);
+    }
+
+    #[test]
+    fn severity_from_content_and_colour() {
+        let mut d = DspDebugDecoder::new();
+        let lines = d.feed(
+            b"ADC overflow on ch 2\n\x1b[33mcal pending\x1b[0m\nwarn: retry\n\x1b[31mbad\x1b[m\nok\n",
+        );
+        let sev: Vec<Severity> = lines.iter().map(|l| l.severity).collect();
+        assert_eq!(
+            sev,
+            [
+                Severity::Error,
+                Severity::Warning,
+                Severity::Warning,
+                Severity::Error,
+                Severity::Info,
+            ]
+        );
+    }
+}
@@ -1,5 +1,6 @@
 //! Handshake request/response messages.
 
+use crate::addr::BusAddr;
 use crate::codec;
 use crate::error::{Result, WireError};
 
@@ -9,6 +10,12 @@ fn decode_cstr(slot: &[u8]) -> String {
     String::from_utf8_lossy(&slot[..end]).into_owned()
 }
 
+/// Write a string into a fixed-width slot, leaving at least one NUL.
+fn encode_cstr(slot: &mut [u8], s: &str) {
+    let n = s.len().min(slot.len().saturating_sub(1));
+    slot[..n].copy_from_slice(&s.as_bytes()[..n]);
+}
+
 /// Device hardware generation, detected from the C8 `dspType` byte.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DeviceGen {
@@ -73,6 +80,10 @@ impl DspQueryResp {
         })
     }
 
+    pub fn encode(&self) -> Vec<u8> {
+        vec![self.version, self.dsp_type, self.pcb]
+    }
+
     /// Detect device generation from the dspType byte.
     pub fn device_gen(&self) -> DeviceGen {
         DeviceGen::from_dsp_type(self.dsp_type)
@@ -88,30 +99,228 @@ impl DspQueryResp {
 ///   DSP: version, serial, firmware tag
 ///   AVR: version + model, build date, build time
 ///   PI:  firmware rev, build date, build time
+///
+/// [`info()`](Self::info) parses the slots into a [`DevInfo`].
 #[derive(Debug, Clone)]
 pub struct DevInfoResp {
     /// Concatenation of all non-empty text slots, separated by spaces.
     pub text: String,
+    /// The three text slots, empty where unused.
+    pub slots: [String; 3],
 }
 
 impl DevInfoResp {
+    /// Build from slot text, filling `text` the way `decode` does.
+    #[must_use]
+    pub fn from_slots(slots: [&str; 3]) -> Self {
+        let slots = slots.map(str::to_string);
+        let text = slots
+            .iter()
+            .filter(|s| !s.is_empty())
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(" ");
+        Self { text, slots }
+    }
+
     pub fn decode(payload: &[u8]) -> Result<Self> {
         // byte[0] = len(payload) - 1.  DSP: 76B (len=75), AVR/PI: 75B (len=74).
         let slot_start = if payload.len() >= 76 { 28 } else { 27 };
-        let mut parts = Vec::new();
-        for i in 0..3 {
+        let slots: [String; 3] = std::array::from_fn(|i| {
             let offset = slot_start + i * 16;
-            if offset + 16 <= payload.len() {
-                let s = decode_cstr(&payload[offset..offset + 16]);
-                if !s.is_empty() {
-                    parts.push(s);
+            payload
+                .get(offset..offset + 16)
+                .map(decode_cstr)
+                .unwrap_or_default()
+        });
+        Ok(Self::from_slots([&slots[0], &slots[1], &slots[2]]))
+    }
+
+    /// Encode `slots` with a zeroed binary header. The layout depends on
+    /// the responding bus: 76 bytes for the DSP, 75 for AVR/PI. Slots are
+    /// truncated to 15 bytes; `text` is not used.
+    pub fn encode(&self, src: BusAddr) -> Vec<u8> {
+        let (len, slot_start) = if src == BusAddr::Dsp { (76, 28) } else { (75, 27) };
+        let mut buf = vec![0u8; len];
+        buf[0] = (len - 1) as u8;
+        for (i, slot) in self.slots.iter().enumerate() {
+            encode_cstr(&mut buf[slot_start + i * 16..slot_start + (i + 1) * 16], slot);
+        }
+        buf
+    }
+
+    /// Parse the slots according to the layout used by `src`.
+    #[must_use]
+    pub fn info(&self, src: BusAddr) -> DevInfo {
+        let [first, second, third] = &self.slots;
+        let non_empty = |s: &String| (!s.is_empty()).then(|| s.clone());
+        match src {
+            BusAddr::Dsp => DevInfo {
+                firmware: FirmwareVersion::parse(first),
+                serial: non_empty(second),
+                tag: non_empty(third),
+                ..DevInfo::default()
+            },
+            BusAddr::Avr => {
+                let (version, model) = first.split_once(' ').unwrap_or((first, ""));
+                DevInfo {
+                    model: non_empty(&model.trim().to_string()),
+                    firmware: FirmwareVersion::parse(version),
+                    build: BuildTime::parse(second, third),
+                    ..DevInfo::default()
                 }
             }
+            BusAddr::Pi | BusAddr::App => DevInfo {
+                firmware: FirmwareVersion::parse(first),
+                build: BuildTime::parse(second, third),
+                ..DevInfo::default()
+            },
         }
-        Ok(Self {
-            text: parts.join(" "),
+    }
+}
+
+/// Fields of a [`DevInfoResp`]. Each bus reports a different subset;
+/// fields it doesn't report, or that don't parse, are `None`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DevInfo {
+    /// Model name (AVR, e.g. "MEVO+").
+    pub model: Option<String>,
+    /// Firmware version (all buses).
+    pub firmware: Option<FirmwareVersion>,
+    /// Serial number (DSP).
+    pub serial: Option<String>,
+    /// Firmware build tag (DSP, e.g. "REL").
+    pub tag: Option<String>,
+    /// Firmware build date and time (AVR, PI).
+    pub build: Option<BuildTime>,
+}
+
+/// A firmware version string such as "2.4.1" or "BM17.04": an optional
+/// alphabetic prefix followed by dot-separated numbers.
+///
+/// Versions compare numerically ("BM17.10" > "BM17.9", "1.2" == "1.2.0"),
+/// but only when their prefixes match; "BM17.04" and "2.4.1" are
+/// unordered.
+#[derive(Debug, Clone)]
+pub struct FirmwareVersion {
+    /// Alphabetic prefix ("BM"), empty for plain versions.
+    pub prefix: String,
+    /// Numeric components, up to the first non-numeric suffix.
+    pub numbers: Vec<u32>,
+    raw: String,
+}
+
+impl FirmwareVersion {
+    /// Parse a version string. `None` if it has no leading number after
+    /// the prefix.
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        let raw = s.trim();
+        let digits_at = raw.find(|c: char| !c.is_ascii_alphabetic())?;
+        let (prefix, rest) = raw.split_at(digits_at);
+        let mut numbers = Vec::new();
+        for part in rest.split('.') {
+            let end = part.find(|c: char| !c.is_ascii_digit()).unwrap_or(part.len());
+            let Ok(n) = part[..end].parse() else { break };
+            numbers.push(n);
+            if end < part.len() {
+                break;
+            }
+        }
+        if numbers.is_empty() {
+            return None;
+        }
+        Some(Self {
+            prefix: prefix.to_string(),
+            numbers,
+            raw: raw.to_string(),
         })
     }
+
+    /// The version as reported by the device.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+}
+
+impl std::fmt::Display for FirmwareVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.raw)
+    }
+}
+
+impl Eq for FirmwareVersion {}
+
+impl PartialEq for FirmwareVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(std::cmp::Ordering::Equal)
+    }
+}
+
+impl PartialOrd for FirmwareVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        if !self.prefix.eq_ignore_ascii_case(&other.prefix) {
+            return None;
+        }
+        let len = self.numbers.len().max(other.numbers.len());
+        let pad = |v: &[u32]| (0..len).map(|i| v.get(i).copied().unwrap_or(0)).collect::<Vec<_>>();
+        Some(pad(&self.numbers).cmp(&pad(&other.numbers)))
+    }
+}
+
+/// Firmware build date and time, as reported in [`DevInfoResp`] slots.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct BuildTime {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+}
+
+impl BuildTime {
+    /// Parse a date ("2024-01-12", or C `__DATE__` style "Jan 12 2024")
+    /// and an optional "HH:MM:SS" time (midnight if empty).
+    #[must_use]
+    pub fn parse(date: &str, time: &str) -> Option<Self> {
+        const MONTHS: [&str; 12] = [
+            "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+        ];
+        let (year, month, day) = if let Some((y, md)) = date.trim().split_once('-') {
+            let (m, d) = md.split_once('-')?;
+            (y.parse().ok()?, m.parse().ok()?, d.parse().ok()?)
+        } else {
+            let mut parts = date.split_whitespace();
+            let m = parts.next()?;
+            let month = MONTHS.iter().position(|n| n.eq_ignore_ascii_case(m))? as u8 + 1;
+            let day = parts.next()?.parse().ok()?;
+            (parts.next()?.parse().ok()?, month, day)
+        };
+        let (hour, minute, second) = if time.trim().is_empty() {
+            (0, 0, 0)
+        } else {
+            let mut parts = time.trim().splitn(3, ':').map(str::parse::<u8>);
+            (parts.next()?.ok()?, parts.next()?.ok()?, parts.next()?.ok()?)
+        };
+        let valid = (1..=12).contains(&month)
+            && (1..=31).contains(&day)
+            && hour < 24
+            && minute < 60
+            && second < 60;
+        valid.then_some(Self { year, month, day, hour, minute, second })
+    }
+}
+
+impl std::fmt::Display for BuildTime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )
+    }
 }
 
 /// Product info request (2 bytes). Type 0xFD (APP→DSP).
@@ -139,7 +348,7 @@ impl ProdInfoReq {
 
 /// Product info response (34 bytes ASCII). Type 0xFD (DSP→APP).
 ///
-/// Contains Pi hardware ID, camera model.
+/// One string per sub-query; see [`ProductInfo`].
 #[derive(Debug, Clone)]
 pub struct ProdInfoResp {
     pub text: String,
@@ -149,6 +358,37 @@ impl ProdInfoResp {
     pub fn decode(payload: &[u8]) -> Result<Self> {
         Ok(Self { text: decode_cstr(payload) })
     }
+
+    /// Encode as 34 bytes of NUL-padded ASCII.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buf = vec![0u8; 34];
+        encode_cstr(&mut buf, &self.text);
+        buf
+    }
+}
+
+/// The three [`ProdInfoResp`] strings from the DSP handshake.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProductInfo {
+    /// Pi hardware ID (sub-query 0x00, e.g. "RPI-3B+").
+    pub pi_hardware: String,
+    /// Camera sensor model (sub-query 0x08, e.g. "IMX219").
+    pub camera_model: String,
+    /// Product identifier (sub-query 0x09, e.g. "FS-MEVO-PLUS").
+    pub product: String,
+}
+
+impl ProductInfo {
+    /// From the responses to sub-queries 0x00, 0x08 and 0x09, in that order.
+    #[must_use]
+    pub fn from_responses(resp: &[ProdInfoResp; 3]) -> Self {
+        let [pi, cam, product] = resp.each_ref().map(|r| r.text.trim().to_string());
+        Self {
+            pi_hardware: pi,
+            camera_model: cam,
+            product,
+        }
+    }
 }
 
 /// Network config request (2 bytes). Type 0xDE (APP→PI).
@@ -182,25 +422,43 @@ impl NetConfigReq {
 /// The password query (sub 0x08) returns both SSID and password.
 #[derive(Debug, Clone)]
 pub struct NetConfigResp {
+    /// The text slots by position, `ssid\0password`; an empty password
+    /// slot is left off, an empty SSID slot is kept.
     pub text: String,
 }
 
 impl NetConfigResp {
     pub fn decode(payload: &[u8]) -> Result<Self> {
-        // Extract the first non-empty text slot (SSID at 21, password at 37).
-        // Callers distinguish SSID vs password by which sub-query was sent.
-        let mut parts = Vec::new();
-        for offset in [21, 37] {
-            if offset + 16 <= payload.len() {
-                let s = decode_cstr(&payload[offset..offset + 16]);
-                if !s.is_empty() {
-                    parts.push(s);
-                }
-            }
+        let slot = |offset: usize| payload.get(offset..offset + 16).map(decode_cstr);
+        let ssid = slot(21).unwrap_or_default();
+        let text = match slot(37) {
+            Some(password) if !password.is_empty() => format!("{ssid}\0{password}"),
+            _ => ssid,
+        };
+        Ok(Self { text })
+    }
+
+    /// The SSID slot (empty in a reply to the SSID query on some firmware).
+    #[must_use]
+    pub fn ssid(&self) -> &str {
+        self.text.split('\0').next().unwrap_or_default()
+    }
+
+    /// The password slot (empty unless the password was queried).
+    #[must_use]
+    pub fn password(&self) -> &str {
+        self.text.split_once('\0').map_or("", |(_, password)| password)
+    }
+
+    /// Encode to the 54-byte layout with a zeroed binary header. `text` is
+    /// split on `\0` into the SSID and password slots.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buf = vec![0u8; 54];
+        buf[1] = 0x35;
+        for (part, offset) in self.text.splitn(2, '\0').zip([21, 37]) {
+            encode_cstr(&mut buf[offset..offset + 16], part);
         }
-        Ok(Self {
-            text: parts.join("\0"),
-        })
+        buf
     }
 }
 
@@ -235,6 +493,10 @@ impl CalParamResp {
             payload: payload.to_vec(),
         })
     }
+
+    pub fn encode(&self) -> Vec<u8> {
+        self.payload.clone()
+    }
 }
 
 /// Calibration data request. Type 0xD2.
@@ -284,6 +546,10 @@ impl CalDataResp {
             payload: payload.to_vec(),
         })
     }
+
+    pub fn encode(&self) -> Vec<u8> {
+        self.payload.clone()
+    }
 }
 
 /// Time synchronization (9 bytes). Type 0x9B.
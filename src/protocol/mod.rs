@@ -24,7 +24,9 @@
 pub mod ack;
 pub mod camera;
 pub mod config;
+pub mod debug;
 pub mod handshake;
+pub mod notice;
 pub mod shot;
 pub mod status;
 
@@ -117,13 +119,68 @@ pub enum Command {
     CamConfig(camera::CamConfig),
     CamConfigReq(camera::CamConfigReq),
     SensorAct(camera::SensorAct),
+    WifiScanReq(camera::WifiScanReq),
 
     // -- Shot ack (empty payloads) --
     ShotDataAck,
     ShotResultReq,
+
+    // -- Forward compat --
+    /// APP→device frame with no typed variant (e.g. 0xEC/0xEE page requests).
+    Unknown { type_id: u8, payload: Vec<u8> },
 }
 
 impl Command {
+    /// Decode an APP→device `RawFrame` into a typed `Command`.
+    ///
+    /// Mirror of [`Message::decode`] for the other direction, so a capture
+    /// of both sides can be fully typed. Dispatches on type ID only; the
+    /// caller decides which frames are APP-originated (`src == App`).
+    pub fn decode(frame: &RawFrame) -> Result<Self> {
+        let p = &frame.payload;
+        match frame.type_id {
+            TYPE_STATUS => Ok(Command::StatusPoll(status::StatusPoll::decode(p)?)),
+
+            // -- Configuration --
+            TYPE_MODE_SET => Ok(Command::ModeSet(config::ModeSet::decode(p)?)),
+            TYPE_AVR_CONFIG_CMD => Ok(Command::AvrConfigCmd(config::AvrConfigCmd::decode(p)?)),
+            TYPE_PARAM_READ_REQ => Ok(Command::ParamReadReq(config::ParamReadReq::decode(p)?)),
+            TYPE_PARAM_VALUE => Ok(Command::ParamValue(config::ParamValue::decode(p)?)),
+            TYPE_RADAR_CAL => Ok(Command::RadarCal(config::RadarCal::decode(p)?)),
+
+            // -- Handshake --
+            TYPE_DSP_QUERY => Ok(Command::DspQuery),
+            TYPE_CONFIG_QUERY => Ok(Command::ConfigQuery),
+            TYPE_AVR_CONFIG_QUERY => Ok(Command::AvrConfigQuery),
+            TYPE_DEV_INFO_REQ => Ok(Command::DevInfoReq),
+            TYPE_PROD_INFO => Ok(Command::ProdInfoReq(handshake::ProdInfoReq::decode(p)?)),
+            TYPE_NET_CONFIG => Ok(Command::NetConfigReq(handshake::NetConfigReq::decode(p)?)),
+            TYPE_CAL_PARAM_REQ => {
+                Ok(Command::CalParamReq(handshake::CalParamReq::decode(p)?))
+            }
+            TYPE_CAL_DATA_REQ => Ok(Command::CalDataReq(handshake::CalDataReq::decode(p)?)),
+            TYPE_TIME_SYNC => Ok(Command::TimeSync(handshake::TimeSync::decode(p)?)),
+
+            // -- Camera --
+            TYPE_CAM_STATE => Ok(Command::CamState(camera::CamState::decode(p)?)),
+            TYPE_CAM_CONFIG => Ok(Command::CamConfig(camera::CamConfig::decode(p)?)),
+            TYPE_CAM_CONFIG_REQ => {
+                Ok(Command::CamConfigReq(camera::CamConfigReq::decode(p)?))
+            }
+            TYPE_SENSOR_ACT => Ok(Command::SensorAct(camera::SensorAct::decode(p)?)),
+            TYPE_WIFI_SCAN => Ok(Command::WifiScanReq(camera::WifiScanReq::decode(p)?)),
+
+            // -- Shot ack --
+            TYPE_SHOT_DATA_ACK => Ok(Command::ShotDataAck),
+            TYPE_SHOT_RESULT_REQ => Ok(Command::ShotResultReq),
+
+            _ => Ok(Command::Unknown {
+                type_id: frame.type_id,
+                payload: p.to_vec(),
+            }),
+        }
+    }
+
     /// Format as a hex debug line: `"APP→DSP 0xAA 2B | 01 01"`.
     pub fn debug_hex(&self, dest: BusAddr) -> String {
         let frame = self.encode(dest);
@@ -167,8 +224,10 @@ impl Command {
             Command::CamConfig(m) => (TYPE_CAM_CONFIG, m.encode()),
             Command::CamConfigReq(_) => (TYPE_CAM_CONFIG_REQ, vec![0x02, 0x01, 0x05]),
             Command::SensorAct(m) => (TYPE_SENSOR_ACT, m.encode()),
+            Command::WifiScanReq(m) => (TYPE_WIFI_SCAN, m.encode()),
             Command::ShotDataAck => (TYPE_SHOT_DATA_ACK, vec![]),
             Command::ShotResultReq => (TYPE_SHOT_RESULT_REQ, vec![]),
+            Command::Unknown { type_id, payload } => (*type_id, payload.clone()),
         };
 
         RawFrame {
@@ -220,7 +279,7 @@ pub enum Message {
     CamConfig(camera::CamConfig),
     CamImageAvail(camera::CamImageAvail),
     SensorActResp(camera::SensorActResp),
-    WifiScan { payload: Vec<u8> },
+    WifiScan(camera::WifiScan),
 
     // -- Shot results --
     FlightResult(shot::FlightResult),
@@ -304,9 +363,7 @@ impl Message {
             TYPE_SENSOR_ACT_RESP => {
                 Ok(Message::SensorActResp(camera::SensorActResp::decode(p)?))
             }
-            TYPE_WIFI_SCAN => Ok(Message::WifiScan {
-                payload: p.to_vec(),
-            }),
+            TYPE_WIFI_SCAN => Ok(Message::WifiScan(camera::WifiScan::decode(p)?)),
 
             // -- Shot results --
             TYPE_FLIGHT_RESULT => {
@@ -335,4 +392,509 @@ impl Message {
             }),
         }
     }
+
+    /// Encode into a `RawFrame` from `src` to `dest` (normally
+    /// [`BusAddr::App`]). Mirror of [`Command::encode`], used to build
+    /// fixtures and fake devices.
+    ///
+    /// `src` also selects the bus-specific layout where one exists
+    /// (DevInfoResp is 76 bytes from the DSP, 75 from AVR/PI).
+    pub fn encode(&self, src: BusAddr, dest: BusAddr) -> RawFrame {
+        let (type_id, payload) = match self {
+            Message::AvrStatus(m) => (TYPE_STATUS, m.encode()),
+            Message::DspStatus(m) => (TYPE_STATUS, m.encode()),
+            Message::PiStatus(m) => (TYPE_STATUS, m.encode()),
+            Message::ConfigAck(m) => (TYPE_CONFIG_ACK, m.encode()),
+            Message::ConfigNack(m) => (TYPE_CONFIG_NACK, m.encode()),
+            Message::ModeAck(m) => (TYPE_MODE_ACK, m.encode()),
+            Message::Text(m) => (TYPE_TEXT, m.encode()),
+            Message::ModeSet(m) => (TYPE_MODE_SET, m.encode()),
+            Message::ParamValue(m) => (TYPE_PARAM_VALUE, m.encode()),
+            Message::RadarCal(m) => (TYPE_RADAR_CAL, m.encode()),
+            Message::ConfigResp(m) => (TYPE_CONFIG_RESP, m.encode()),
+            Message::AvrConfigResp(m) => (TYPE_AVR_CONFIG_RESP, m.encode()),
+            Message::DspQueryResp(m) => (TYPE_DSP_QUERY_RESP, m.encode()),
+            Message::DevInfoResp(m) => (TYPE_DEV_INFO_RESP, m.encode(src)),
+            Message::ProdInfoResp(m) => (TYPE_PROD_INFO, m.encode()),
+            Message::NetConfigResp(m) => (TYPE_NET_CONFIG, m.encode()),
+            Message::CalParamResp(m) => (TYPE_CAL_PARAM_RESP, m.encode()),
+            Message::CalDataResp(m) => (TYPE_CAL_DATA_RESP, m.encode()),
+            Message::TimeSync(m) => (TYPE_TIME_SYNC, m.encode()),
+            Message::CamState(m) => (TYPE_CAM_STATE, m.encode()),
+            Message::CamConfig(m) => (TYPE_CAM_CONFIG, m.encode()),
+            Message::CamImageAvail(m) => (TYPE_CAM_IMAGE_AVAIL, m.encode()),
+            Message::SensorActResp(m) => (TYPE_SENSOR_ACT_RESP, m.encode()),
+            Message::WifiScan(m) => (TYPE_WIFI_SCAN, m.encode()),
+            Message::FlightResult(m) => (TYPE_FLIGHT_RESULT, m.encode()),
+            Message::FlightResultV1(m) => (TYPE_FLIGHT_RESULT_V1, m.encode()),
+            Message::ClubResult(m) => (TYPE_CLUB_RESULT, m.encode()),
+            Message::SpinResult(m) => (TYPE_SPIN_RESULT, m.encode()),
+            Message::SpeedProfile(m) => (TYPE_SPEED_PROFILE, m.encode()),
+            Message::TrackingStatus(m) => (TYPE_TRACKING_STATUS, m.encode()),
+            Message::PrcData(m) => (TYPE_PRC_DATA, m.encode()),
+            Message::ClubPrc(m) => (TYPE_CLUB_PRC, m.encode()),
+            Message::ShotText(m) => (TYPE_SHOT_TEXT, m.encode()),
+            Message::DspDebug(payload) => (TYPE_DSP_DEBUG, payload.clone()),
+            Message::Unknown {
+                type_id, payload, ..
+            } => (*type_id, payload.clone()),
+        };
+
+        RawFrame {
+            dest,
+            src,
+            type_id,
+            payload,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Encode → wire → decode → encode, asserting the payload is stable.
+    fn round_trip(msg: &Message, src: BusAddr) -> Message {
+        let frame = msg.encode(src, BusAddr::App);
+        let parsed = RawFrame::parse(&frame.encode()).unwrap();
+        assert_eq!(parsed.src, src);
+        assert_eq!(parsed.type_id, frame.type_id);
+        let decoded = Message::decode(&parsed).unwrap();
+        let again = decoded.encode(src, BusAddr::App);
+        assert_eq!(again.payload, frame.payload, "{msg:?}");
+        decoded
+    }
+
+    #[test]
+    fn status_round_trip() {
+        let avr = status::AvrStatus {
+            version: 0x18,
+            state: 1,
+            hw_id_hi: 0x01,
+            hw_id_lo: 0x07,
+            full_app_id: 0,
+            temperature: 38.5,
+            tilt: 0.25,
+            roll: -1.5,
+        };
+        let Message::AvrStatus(d) = round_trip(&Message::AvrStatus(avr), BusAddr::Avr) else {
+            panic!("expected AvrStatus");
+        };
+        assert_eq!(d.state, 1);
+        assert_eq!(d.temperature, 38.5);
+        assert_eq!(d.roll, -1.5);
+
+        let dsp = status::DspStatus80 {
+            state: 6,
+            input_voltage_usb: 4900,
+            system_voltage: 3300,
+            battery_current: -350,
+            temperature_raw: 3850,
+            battery_voltage: 4012,
+            battery_voltage_2: 4010,
+            power_level: 87 << 8,
+            external_power: true,
+        };
+        let msg = Message::DspStatus(status::DspStatus::V80(dsp));
+        let Message::DspStatus(d) = round_trip(&msg, BusAddr::Dsp) else {
+            panic!("expected DspStatus");
+        };
+        assert_eq!(d.state(), 6);
+        assert_eq!(d.battery_percent(), 87);
+        assert!(d.external_power());
+        assert_eq!(msg.encode(BusAddr::Dsp, BusAddr::App).payload.len(), 129);
+
+        let mut raw = vec![0x46, 5];
+        raw.resize(71, 0xAB);
+        let gen2 = status::DspStatus::decode(&raw).unwrap();
+        assert_eq!(gen2.encode(), raw);
+
+        let pi = Message::PiStatus(status::PiStatus { payload: vec![0x10, 0x01, 0x02] });
+        round_trip(&pi, BusAddr::Pi);
+    }
+
+    #[test]
+    fn ack_and_text_round_trip() {
+        let ack = status::ConfigAck { bus_addr: 0x30, acked_cmd: 0x3F };
+        assert_eq!(ack.encode(), [0x02, 0x30, 0x3F]);
+        round_trip(&Message::ConfigAck(ack.clone()), BusAddr::Avr);
+        let nack = Message::ConfigNack(ack).encode(BusAddr::Avr, BusAddr::App);
+        assert_eq!(nack.type_id, TYPE_CONFIG_NACK);
+        round_trip(&Message::ModeAck(status::ModeAck), BusAddr::Avr);
+
+        let text = Message::Text(status::Text { text: "ARMED DetectionMode=9".into() });
+        let Message::Text(t) = round_trip(&text, BusAddr::Avr) else {
+            panic!("expected Text");
+        };
+        assert_eq!(t.text, "ARMED DetectionMode=9");
+
+        let shot = Message::ShotText(shot::ShotText { text: "PROCESSED".into() });
+        let Message::ShotText(t) = round_trip(&shot, BusAddr::Avr) else {
+            panic!("expected ShotText");
+        };
+        assert!(t.is_processed());
+    }
+
+    #[test]
+    fn handshake_round_trip() {
+        let q = handshake::DspQueryResp { version: 2, dsp_type: 0xC0, pcb: 0x0E };
+        assert_eq!(q.encode(), [0x02, 0xC0, 0x0E]);
+
+        let info = handshake::DevInfoResp::from_slots(["2.4.1", "012345", "REL"]);
+        assert_eq!(info.encode(BusAddr::Dsp).len(), 76);
+        assert_eq!(info.encode(BusAddr::Avr).len(), 75);
+        for src in [BusAddr::Dsp, BusAddr::Avr, BusAddr::Pi] {
+            let Message::DevInfoResp(d) = round_trip(&Message::DevInfoResp(info.clone()), src)
+            else {
+                panic!("expected DevInfoResp");
+            };
+            assert_eq!(d.text, info.text);
+            assert_eq!(d.slots, info.slots);
+        }
+
+        let prod = handshake::ProdInfoResp { text: "IMX219".into() };
+        assert_eq!(prod.encode().len(), 34);
+        round_trip(&Message::ProdInfoResp(prod), BusAddr::Dsp);
+
+        let net = handshake::NetConfigResp { text: "FS-M2\0secret".into() };
+        let Message::NetConfigResp(n) = round_trip(&Message::NetConfigResp(net), BusAddr::Pi)
+        else {
+            panic!("expected NetConfigResp");
+        };
+        assert_eq!(n.text, "FS-M2\0secret");
+        assert_eq!((n.ssid(), n.password()), ("FS-M2", "secret"));
+
+        // Slots are positional: an empty SSID slot doesn't promote the
+        // password into it.
+        let net = handshake::NetConfigResp { text: "\0secret".into() };
+        let Message::NetConfigResp(n) = round_trip(&Message::NetConfigResp(net), BusAddr::Pi)
+        else {
+            panic!("expected NetConfigResp");
+        };
+        assert_eq!((n.ssid(), n.password()), ("", "secret"));
+
+        let cfg = config::ConfigResp { params: std::array::from_fn(|i| i as i16 * -7) };
+        let Message::ConfigResp(c) = round_trip(&Message::ConfigResp(cfg), BusAddr::Avr) else {
+            panic!("expected ConfigResp");
+        };
+        assert_eq!(c.params[33], -231);
+    }
+
+    #[test]
+    fn dev_info_fields() {
+        use handshake::{BuildTime, DevInfoResp, FirmwareVersion};
+
+        let dsp = DevInfoResp::from_slots(["2.4.1", "012345", "REL"]).info(BusAddr::Dsp);
+        assert_eq!(dsp.firmware.unwrap().numbers, [2, 4, 1]);
+        assert_eq!(dsp.serial.as_deref(), Some("012345"));
+        assert_eq!(dsp.tag.as_deref(), Some("REL"));
+        assert!(dsp.build.is_none());
+
+        let avr = DevInfoResp::from_slots(["1.9.3 MEVO+", "Jan 12 2024", "10:31:07"]);
+        let avr = avr.info(BusAddr::Avr);
+        assert_eq!(avr.model.as_deref(), Some("MEVO+"));
+        assert_eq!(avr.firmware.unwrap().to_string(), "1.9.3");
+        assert_eq!(avr.build.unwrap().to_string(), "2024-01-12 10:31:07");
+
+        let pi = DevInfoResp::from_slots(["BM17.04", "2026-01-20", ""]).info(BusAddr::Pi);
+        let fw = pi.firmware.unwrap();
+        assert_eq!((fw.prefix.as_str(), fw.as_str()), ("BM", "BM17.04"));
+        assert_eq!(
+            pi.build,
+            Some(BuildTime { year: 2026, month: 1, day: 20, hour: 0, minute: 0, second: 0 })
+        );
+
+        let v = |s| FirmwareVersion::parse(s).unwrap();
+        assert!(v("BM17.10") > v("BM17.04"));
+        assert!(v("BM17.4") == v("BM17.04.0"));
+        assert!(v("1.9.3-rc1") < v("1.10"));
+        assert_eq!(v("BM17.04").partial_cmp(&v("2.4.1")), None);
+        assert!(FirmwareVersion::parse("REL").is_none());
+        assert!(BuildTime::parse("2024-13-01", "").is_none());
+        assert!(BuildTime::parse("2024-01-12", "25:00:00").is_none());
+
+        let fusion = |s| camera::CamConfig::fusion_preset_for(FirmwareVersion::parse(s).as_ref());
+        assert_eq!(fusion("BM17.04").framerate, 180);
+        assert_eq!(fusion("BM17.10").framerate, 180);
+        assert_eq!(fusion("BM16.9").framerate, 10);
+        assert_eq!(fusion("").framerate, 10);
+    }
+
+    #[test]
+    fn camera_round_trip() {
+        let long = camera::CamImageAvail {
+            streaming_available: true,
+            fusion_available: true,
+            video_available: false,
+            streaming_timestamp: Some("2026-01-20T09:12:44.123Z".into()),
+            fusion_timestamp: None,
+        };
+        assert_eq!(long.encode().len(), 67);
+        let Message::CamImageAvail(d) = round_trip(&Message::CamImageAvail(long), BusAddr::Pi)
+        else {
+            panic!("expected CamImageAvail");
+        };
+        assert!(d.fusion_available && !d.video_available);
+        assert_eq!(d.streaming_timestamp.as_deref(), Some("2026-01-20T09:12:44.123Z"));
+
+        let short = camera::CamImageAvail {
+            streaming_available: true,
+            fusion_available: false,
+            video_available: false,
+            streaming_timestamp: None,
+            fusion_timestamp: None,
+        };
+        assert_eq!(short.encode().len(), 2);
+        round_trip(&Message::CamImageAvail(short), BusAddr::Pi);
+
+        // WiFi scan pages are kept raw, byte for byte.
+        let page = vec![0x08, 0x00, 0x02, 0x01, 0xAA, 0xBB, 0xCC, 0xDD, 0xEE];
+        let scan = camera::WifiScan { payload: page.clone() };
+        let Message::WifiScan(w) = round_trip(&Message::WifiScan(scan), BusAddr::Pi) else {
+            panic!("expected WifiScan");
+        };
+        assert_eq!(w.payload, page);
+    }
+
+    #[test]
+    fn shot_results_round_trip() {
+        let mut fr = vec![0u8; 158];
+        fr[0] = 0x9D;
+        for (i, b) in fr.iter_mut().enumerate().skip(1) {
+            *b = (i * 37 % 251) as u8;
+        }
+        // poly_scale must be non-zero for coefficients to survive exactly.
+        fr[109..112].copy_from_slice(&[0x00, 0x03, 0xE8]);
+        // Trailing byte is not decoded.
+        fr[157] = 0;
+        let flight = shot::FlightResult::decode(&fr).unwrap();
+        assert_eq!(flight.encode(), fr);
+        round_trip(&Message::FlightResult(flight), BusAddr::Avr);
+
+        let mut v1 = vec![0u8; 94];
+        v1[0] = 0x5D;
+        for (i, b) in v1.iter_mut().enumerate().skip(1) {
+            *b = (i * 13 % 241) as u8;
+        }
+        v1[46..49].copy_from_slice(&[0x00, 0x00, 0x64]);
+        let e8 = shot::FlightResultV1::decode(&v1).unwrap();
+        assert_eq!(e8.encode(), v1);
+
+        let mut club = vec![0u8; 167];
+        club[0] = 0xA6;
+        for (i, b) in club.iter_mut().enumerate().skip(1) {
+            *b = (i * 29 % 233) as u8;
+        }
+        club[47..50].copy_from_slice(&[0x00, 0x27, 0x10]);
+        let ed = shot::ClubResult::decode(&club).unwrap();
+        assert_eq!(ed.encode(), club);
+
+        let mut spin = vec![0u8; 138];
+        spin[0] = 0x89;
+        for (i, b) in spin.iter_mut().enumerate().skip(1) {
+            *b = (i * 17 % 239) as u8;
+        }
+        let ef = shot::SpinResult::decode(&spin).unwrap();
+        assert_eq!(ef.encode(), spin);
+
+        let mut track = vec![0u8; 82];
+        track[0] = 0x51;
+        for i in [1, 2, 22, 25, 28, 32, 35, 38, 47, 48, 51, 54, 56, 59, 62, 67, 70, 73, 76, 80] {
+            track[i] = i as u8;
+        }
+        let e9 = shot::TrackingStatus::decode(&track).unwrap();
+        assert_eq!(e9.encode(), track);
+    }
+
+    #[test]
+    fn speed_profile_round_trip() {
+        let stub = shot::SpeedProfile::decode(&[0x01, 0x01]).unwrap();
+        assert_eq!(stub.encode(), [0x01, 0x01]);
+
+        let mut d9 = vec![0x00, 0x01, 2, 1];
+        crate::codec::write_int24(&mut d9, 100);
+        crate::codec::write_float40(&mut d9, 0.000_853);
+        for v in [2650i16, 3800, 3040] {
+            crate::codec::write_int16(&mut d9, v);
+        }
+        d9[0] = (d9.len() - 1) as u8;
+        let sp = shot::SpeedProfile::decode(&d9).unwrap();
+        assert_eq!(sp.speeds, [26.5, 38.0, 30.4]);
+        assert_eq!(sp.encode(), d9);
+    }
+
+    #[test]
+    fn prc_round_trip() {
+        let mut ec = vec![3 + 2 * 60, 0x00, 0x07, 2];
+        for i in 0..120 {
+            ec.push((i * 7 % 200) as u8);
+        }
+        let prc = shot::PrcData::decode(&ec).unwrap();
+        assert_eq!(prc.points.len(), 2);
+        assert_eq!(prc.encode(), ec);
+
+        let mut ee = vec![76 * 2];
+        for i in 0..152usize {
+            // Bytes 36-37 of each record are not decoded.
+            ee.push(if matches!(i % 76, 36 | 37) { 0 } else { (i * 11 % 200) as u8 });
+        }
+        let club_prc = shot::ClubPrc::decode(&ee).unwrap();
+        assert_eq!(club_prc.points.len(), 2);
+        assert_eq!(club_prc.encode(), ee);
+    }
+
+    #[test]
+    fn prc_length_limits() {
+        let mut ec = vec![3 + 4 * 60, 0x00, 0x07, 4];
+        ec.resize(4 + 4 * 60, 0x11);
+        let prc = shot::PrcData::decode(&ec).unwrap();
+        assert_eq!(prc.points.len(), shot::PrcData::MAX_POINTS);
+        let Message::PrcData(p) = round_trip(&Message::PrcData(prc), BusAddr::Avr) else {
+            panic!("expected PrcData");
+        };
+        assert_eq!(p.points.len(), shot::PrcData::MAX_POINTS);
+
+        let mut ee = vec![76 * 3];
+        ee.resize(1 + 76 * 3, 0);
+        let club_prc = shot::ClubPrc::decode(&ee).unwrap();
+        assert_eq!(club_prc.points.len(), shot::ClubPrc::MAX_POINTS);
+        let Message::ClubPrc(c) = round_trip(&Message::ClubPrc(club_prc), BusAddr::Avr) else {
+            panic!("expected ClubPrc");
+        };
+        assert_eq!(c.points.len(), shot::ClubPrc::MAX_POINTS);
+    }
+
+    #[test]
+    fn prc_decode_trusts_length_byte_over_count() {
+        // Byte 3 claims four records, the length byte covers two; the
+        // rest of the payload is not PRC data.
+        let mut ec = vec![3 + 2 * 60, 0x00, 0x07, 4];
+        ec.resize(4 + 4 * 60, 0x11);
+        let prc = shot::PrcData::decode(&ec).unwrap();
+        assert_eq!(prc.points.len(), 2);
+        let mut expected = ec[..4 + 2 * 60].to_vec();
+        expected[3] = 2;
+        assert_eq!(prc.encode(), expected);
+    }
+
+    #[test]
+    fn prc_encode_truncates_and_pages() {
+        let mut ec = vec![3 + 4 * 60, 0x00, 0x07, 4];
+        ec.resize(4 + 4 * 60, 0);
+        let mut prc = shot::PrcData::decode(&ec).unwrap();
+        prc.points.push(prc.points[0].clone());
+        assert_eq!(prc.encode(), ec);
+        let pages = prc.pages();
+        let sizes: Vec<_> = pages.iter().map(|p| (p.sequence, p.points.len())).collect();
+        assert_eq!(sizes, [(7, 4), (8, 1)]);
+        assert_eq!(pages[0].encode(), ec);
+    }
+
+    #[test]
+    fn club_prc_encode_truncates_and_pages() {
+        let mut ee = vec![76 * 3];
+        ee.resize(1 + 76 * 3, 0);
+        let mut club_prc = shot::ClubPrc::decode(&ee).unwrap();
+        club_prc.points.push(club_prc.points[0].clone());
+        assert_eq!(club_prc.encode(), ee);
+        let sizes: Vec<_> = club_prc.pages().iter().map(|p| p.points.len()).collect();
+        assert_eq!(sizes, [3, 1]);
+    }
+
+    #[test]
+    fn command_round_trip() {
+        let cmds = [
+            (Command::StatusPoll(status::StatusPoll { pi_mode: true }), BusAddr::Pi),
+            (Command::ModeSet(config::ModeSet { mode: config::MODE_PUTTING }), BusAddr::Avr),
+            (Command::AvrConfigCmd(config::AvrConfigCmd { arm: true }), BusAddr::Avr),
+            (Command::ParamReadReq(config::ParamReadReq { param_id: 0x0C }), BusAddr::Avr),
+            (
+                Command::ParamValue(config::ParamValue {
+                    param_id: 0x26,
+                    value: config::ParamData::Float40(0.0381),
+                }),
+                BusAddr::Avr,
+            ),
+            (Command::RadarCal(config::RadarCal { range_mm: 2743, height_mm: 25 }), BusAddr::Avr),
+            (Command::DspQuery, BusAddr::Dsp),
+            (Command::ConfigQuery, BusAddr::Avr),
+            (Command::AvrConfigQuery, BusAddr::Avr),
+            (Command::DevInfoReq, BusAddr::Pi),
+            (Command::ProdInfoReq(handshake::ProdInfoReq { sub_query: 0x08 }), BusAddr::Dsp),
+            (
+                Command::NetConfigReq(handshake::NetConfigReq { query_password: true }),
+                BusAddr::Pi,
+            ),
+            (Command::CalParamReq(handshake::CalParamReq), BusAddr::Avr),
+            (
+                Command::CalDataReq(
+                    handshake::CalDataReq::decode(&handshake::CalDataReq::encode_factory())
+                        .unwrap(),
+                ),
+                BusAddr::Avr,
+            ),
+            (
+                Command::TimeSync(handshake::TimeSync {
+                    epoch: 1_760_000_000,
+                    session: 0,
+                    tail: [0x00, 0x01],
+                }),
+                BusAddr::Avr,
+            ),
+            (Command::CamState(camera::CamState { state: 1 }), BusAddr::Pi),
+            (Command::CamConfig(camera::CamConfig::standard_preset()), BusAddr::Pi),
+            (Command::CamConfigReq(camera::CamConfigReq), BusAddr::Pi),
+            (Command::SensorAct(camera::SensorAct { payload: vec![1, 2, 3] }), BusAddr::Pi),
+            (Command::WifiScanReq(camera::WifiScanReq { page: 5 }), BusAddr::Pi),
+            (Command::ShotDataAck, BusAddr::Avr),
+            (Command::ShotResultReq, BusAddr::Avr),
+            (Command::Unknown { type_id: 0xEE, payload: vec![0x4C, 0x00, 0x03] }, BusAddr::Avr),
+        ];
+        for (cmd, dest) in cmds {
+            let frame = cmd.encode(dest);
+            let parsed = RawFrame::parse(&frame.encode()).unwrap();
+            assert_eq!(parsed.src, BusAddr::App);
+            let decoded = Command::decode(&parsed).unwrap();
+            assert_eq!(
+                std::mem::discriminant(&decoded),
+                std::mem::discriminant(&cmd),
+                "{cmd:?}"
+            );
+            assert_eq!(decoded.encode(dest).payload, frame.payload, "{cmd:?}");
+        }
+    }
+
+    #[test]
+    fn command_decode_fields() {
+        let frame = Command::ModeSet(config::ModeSet { mode: config::MODE_CHIPPING })
+            .encode(BusAddr::Avr);
+        let Command::ModeSet(m) = Command::decode(&frame).unwrap() else {
+            panic!("expected ModeSet");
+        };
+        assert_eq!(m.mode, config::MODE_CHIPPING);
+
+        let frame = Command::AvrConfigCmd(config::AvrConfigCmd { arm: false }).encode(BusAddr::Avr);
+        assert!(matches!(
+            Command::decode(&frame).unwrap(),
+            Command::AvrConfigCmd(config::AvrConfigCmd { arm: false })
+        ));
+
+        // Truncated payloads surface as errors, like Message::decode.
+        let mut frame = Command::RadarCal(config::RadarCal { range_mm: 1, height_mm: 0 })
+            .encode(BusAddr::Avr);
+        frame.payload.truncate(3);
+        assert!(Command::decode(&frame).is_err());
+    }
+
+    #[test]
+    fn unknown_preserves_type_and_payload() {
+        let msg = Message::Unknown { type_id: 0x42, src: BusAddr::Avr, payload: vec![1, 2, 3] };
+        let frame = msg.encode(BusAddr::Avr, BusAddr::App);
+        assert_eq!(frame.type_id, 0x42);
+        assert_eq!(frame.payload, [1, 2, 3]);
+    }
 }
@@ -0,0 +1,164 @@
+//! Structured parsing of device narration (0xE3 TEXT, 0xE5 SHOT_TEXT).
+//!
+//! The device reports arming, state transitions, radar parameters, and
+//! shot progress as ASCII. [`DeviceNotice::parse`] turns the known forms
+//! into typed variants with their numbers extracted; anything else is kept
+//! verbatim as [`DeviceNotice::Other`].
+
+use std::str::FromStr;
+
+use super::Message;
+
+/// A parsed 0xE3 [`Text`](super::status::Text) or 0xE5
+/// [`ShotText`](super::shot::ShotText) message.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeviceNotice {
+    /// `"ARMED DetectionMode=9"` — radar armed in the given mode.
+    Armed { detection_mode: Option<u8> },
+    /// `"ARMED CANCELLED"` — disarmed.
+    ArmCancelled,
+    /// `"System State 6"` — DSP state transition (5 = idle, 6 = armed).
+    SystemState(u8),
+    /// `"ADC errors = 0"` — radar ADC error count, reported on disarm.
+    AdcErrors(u32),
+    /// `"GainInit=0, MaxVel=120, ..."` — radar parameters for the new mode,
+    /// as `(name, value)` pairs in wire order.
+    RadarParams(Vec<(String, f64)>),
+    /// `"BALL TRIGGER: 12 ms back, at Epoch 1700000000"` — ball detected.
+    BallTrigger {
+        ms_back: Option<u32>,
+        epoch: Option<f64>,
+    },
+    /// `"Clubimpact at Epoch 1700000000"` — club impact timing.
+    ClubImpact { epoch: Option<f64> },
+    /// `"PROCESSED"` — shot processing complete.
+    Processed,
+    /// `"IDLE"` — ready for the next shot.
+    Idle,
+    /// Any other text, verbatim.
+    Other(String),
+}
+
+impl DeviceNotice {
+    /// Parse device text. Never fails; unrecognised text is `Other`.
+    #[must_use]
+    pub fn parse(text: &str) -> Self {
+        let t = text.trim();
+        if t.starts_with("ARMED CANCELLED") {
+            Self::ArmCancelled
+        } else if t.starts_with("ARMED") {
+            Self::Armed {
+                detection_mode: number_after(t, "DetectionMode="),
+            }
+        } else if let Some(state) = t.strip_prefix("System State") {
+            match leading_number(state) {
+                Some(n) => Self::SystemState(n),
+                None => Self::Other(t.to_owned()),
+            }
+        } else if t.starts_with("ADC errors") {
+            match number_after(t, "=") {
+                Some(n) => Self::AdcErrors(n),
+                None => Self::Other(t.to_owned()),
+            }
+        } else if t.starts_with("GainInit") {
+            Self::RadarParams(
+                t.split(',')
+                    .filter_map(|kv| {
+                        let (k, v) = kv.split_once('=')?;
+                        Some((k.trim().to_owned(), leading_number(v)?))
+                    })
+                    .collect(),
+            )
+        } else if let Some(rest) = t.strip_prefix("BALL TRIGGER") {
+            Self::BallTrigger {
+                ms_back: leading_number(rest.trim_start_matches(':')),
+                epoch: number_after(rest, "Epoch"),
+            }
+        } else if t.starts_with("Clubimpact") {
+            Self::ClubImpact {
+                epoch: number_after(t, "Epoch"),
+            }
+        } else if t == "PROCESSED" {
+            Self::Processed
+        } else if t == "IDLE" {
+            Self::Idle
+        } else {
+            Self::Other(t.to_owned())
+        }
+    }
+
+    /// Parse a TEXT or SHOT_TEXT message. `None` for other message types.
+    #[must_use]
+    pub fn from_message(msg: &Message) -> Option<Self> {
+        match msg {
+            Message::Text(t) => Some(Self::parse(&t.text)),
+            Message::ShotText(t) => Some(Self::parse(&t.text)),
+            _ => None,
+        }
+    }
+}
+
+/// Parse the number at the start of `s` (after leading spaces).
+fn leading_number<T: FromStr>(s: &str) -> Option<T> {
+    let s = s.trim_start();
+    let end = s
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-'))
+        .unwrap_or(s.len());
+    s[..end].parse().ok()
+}
+
+/// Parse the number following the first occurrence of `key`.
+fn number_after<T: FromStr>(s: &str, key: &str) -> Option<T> {
+    let at = s.find(key)? + key.len();
+    leading_number(&s[at..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_known_forms() {
+        use DeviceNotice as N;
+        assert_eq!(
+            N::parse("ARMED DetectionMode=9"),
+            N::Armed {
+                detection_mode: Some(9)
+            }
+        );
+        assert_eq!(N::parse("ARMED CANCELLED"), N::ArmCancelled);
+        assert_eq!(N::parse("System State 6"), N::SystemState(6));
+        assert_eq!(N::parse("ADC errors = 3"), N::AdcErrors(3));
+        assert_eq!(
+            N::parse("GainInit=1, MaxVel=30, Fs=9868.4"),
+            N::RadarParams(vec![
+                ("GainInit".into(), 1.0),
+                ("MaxVel".into(), 30.0),
+                ("Fs".into(), 9868.4),
+            ])
+        );
+        assert_eq!(
+            N::parse("BALL TRIGGER: 12 ms back, at Epoch 1700000000"),
+            N::BallTrigger {
+                ms_back: Some(12),
+                epoch: Some(1_700_000_000.0)
+            }
+        );
+        assert_eq!(
+            N::parse("Clubimpact at Epoch 1700000000"),
+            N::ClubImpact {
+                epoch: Some(1_700_000_000.0)
+            }
+        );
+        assert_eq!(N::parse("PROCESSED"), N::Processed);
+        assert_eq!(N::parse("IDLE"), N::Idle);
+        assert_eq!(
+            N::parse("DSP: CameraParam Read PASS (165)"),
+            N::Other("DSP: CameraParam Read PASS (165)".into())
+        );
+        assert_eq!(
+            N::parse("System State ?"),
+            N::Other("System State ?".into())
+        );
+    }
+}
@@ -151,6 +151,66 @@ impl FlightResult {
             poly_z,
         })
     }
+
+    /// Encode to the 158-byte wire payload.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buf = vec![0x9D];
+        codec::write_int24(&mut buf, self.total);
+        codec::write_int24_scaled(&mut buf, self.track_time, 1000.0);
+        for v in self.start_position {
+            codec::write_int24_scaled(&mut buf, v, 1000.0);
+        }
+        for v in [
+            self.launch_speed,
+            self.launch_azimuth,
+            self.launch_elevation,
+            self.carry_distance,
+            self.flight_time,
+            self.max_height,
+        ] {
+            codec::write_int24_scaled(&mut buf, v, 1000.0);
+        }
+        for v in self.landing_position {
+            codec::write_int24_scaled(&mut buf, v, 1000.0);
+        }
+        codec::write_int24(&mut buf, self.backspin_rpm);
+        codec::write_int24(&mut buf, self.sidespin_rpm);
+        codec::write_int24(&mut buf, self.riflespin_rpm);
+        for v in self.landing_spin_rpm {
+            codec::write_int24(&mut buf, v);
+        }
+        for v in self.landing_velocity {
+            codec::write_int24_scaled(&mut buf, v, 1000.0);
+        }
+        codec::write_int24_scaled(&mut buf, self.total_distance, 1000.0);
+        codec::write_int24_scaled(&mut buf, self.roll_distance, 1000.0);
+        for v in self.final_position {
+            codec::write_int24_scaled(&mut buf, v, 1000.0);
+        }
+        for v in [
+            self.clubhead_speed,
+            self.club_strike_direction,
+            self.club_attack_angle,
+            self.clubhead_speed_post,
+            self.club_swing_plane_tilt,
+            self.club_swing_plane_rotation,
+            self.club_effective_loft,
+            self.club_face_angle,
+        ] {
+            codec::write_int24_scaled(&mut buf, v, 1000.0);
+        }
+        codec::write_int24(&mut buf, self.poly_scale);
+        let ps = if self.poly_scale == 0 {
+            1.0
+        } else {
+            f64::from(self.poly_scale)
+        };
+        for poly in [&self.poly_x, &self.poly_y, &self.poly_z] {
+            write_poly(&mut buf, poly, ps);
+        }
+        buf.resize(158, 0);
+        buf
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -245,6 +305,36 @@ impl FlightResultV1 {
             poly_z,
         })
     }
+
+    /// Encode to the 94-byte wire payload.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buf = vec![0x5D];
+        codec::write_int24(&mut buf, self.total);
+        for v in [
+            self.club_velocity,
+            self.ball_velocity,
+            self.flight_time,
+            self.distance,
+            self.height,
+            self.lateral,
+            self.elevation,
+            self.azimuth,
+            self.tracked_time,
+        ] {
+            codec::write_int24_scaled(&mut buf, v, 1000.0);
+        }
+        codec::write_int24_scaled(&mut buf, self.drag, 1_000_000.0);
+        codec::write_int24(&mut buf, self.backspin_rpm);
+        codec::write_int24(&mut buf, self.sidespin_rpm);
+        codec::write_int24_scaled(&mut buf, self.acceleration, 1000.0);
+        codec::write_int24_scaled(&mut buf, self.club_strike_direction, 1000.0);
+        codec::write_int24(&mut buf, self.poly_scale);
+        let ps = f64::from(self.poly_scale).max(1.0);
+        for poly in [&self.poly_x, &self.poly_y, &self.poly_z] {
+            write_poly(&mut buf, poly, ps);
+        }
+        buf
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -361,6 +451,47 @@ impl ClubResult {
             },
         })
     }
+
+    /// Encode to the 167-byte wire payload (with timing fields).
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buf = vec![0xA6, self.num_club_prc_points];
+        codec::write_int24(&mut buf, self.flags);
+        for v in [
+            self.pre_club_speed,
+            self.post_club_speed,
+            self.strike_direction,
+            self.attack_angle,
+            self.face_angle,
+            self.dynamic_loft,
+        ] {
+            codec::write_int24_scaled(&mut buf, v, 100.0);
+        }
+        codec::write_int24_scaled(&mut buf, self.smash_factor, 1000.0);
+        codec::write_int24_scaled(&mut buf, self.dispersion_correction, 1000.0);
+        for v in [
+            self.swing_plane_horizontal,
+            self.swing_plane_vertical,
+            self.club_azimuth,
+            self.club_elevation,
+        ] {
+            codec::write_int24_scaled(&mut buf, v, 100.0);
+        }
+        codec::write_int24_scaled(&mut buf, self.club_offset, 1000.0);
+        codec::write_int24_scaled(&mut buf, self.club_height, 1000.0);
+        codec::write_int24(&mut buf, self.poly_scale);
+        let ps = if self.poly_scale == 0 {
+            1.0
+        } else {
+            f64::from(self.poly_scale)
+        };
+        for coeffs in &self.poly_coeffs {
+            write_poly(&mut buf, coeffs, ps);
+        }
+        for v in [self.pre_impact_time, self.post_impact_time, self.club_to_ball_time] {
+            codec::write_int24_scaled(&mut buf, v, 100.0);
+        }
+        buf
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -465,6 +596,37 @@ impl SpinResult {
             pll_spin: codec::read_int16(payload, 136)?,
         })
     }
+
+    /// Encode to the 138-byte wire payload.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buf = vec![self.version];
+        for elem in self.antenna_data.iter().flatten() {
+            codec::write_int16(&mut buf, elem.spin_rpm);
+            codec::write_int24_scaled(&mut buf, elem.peak, 1000.0);
+            codec::write_int16(&mut buf, elem.snr);
+        }
+        for v in [
+            self.pm_spin_raw,
+            self.pm_spin_final,
+            self.pm_spin_confidence,
+            self.lift_spin,
+            self.spin_validate_expected,
+            self.spin_validate_low,
+            self.spin_validate_high,
+            self.spin_validate_scaling,
+        ] {
+            codec::write_int16(&mut buf, v);
+        }
+        buf.push(self.spin_method);
+        codec::write_int24(&mut buf, self.spin_flags);
+        codec::write_int16(&mut buf, self.launch_spin);
+        codec::write_int16(&mut buf, self.am_spin);
+        codec::write_int16(&mut buf, self.pm_spin);
+        codec::write_int16_scaled(&mut buf, self.spin_axis, 10.0);
+        codec::write_int16(&mut buf, self.aod_spin);
+        codec::write_int16(&mut buf, self.pll_spin);
+        buf
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -531,6 +693,28 @@ impl SpeedProfile {
             speeds,
         })
     }
+
+    /// Encode to the wire payload. A profile with no samples and no scale
+    /// factor encodes as the 2-byte stub form.
+    pub fn encode(&self) -> Vec<u8> {
+        if self.scale_factor == 0 && self.speeds.is_empty() {
+            return vec![0x01, self.flags];
+        }
+        let sf = if self.scale_factor == 0 {
+            1.0
+        } else {
+            f64::from(self.scale_factor)
+        };
+        let mut buf = vec![0x00, self.flags, self.num_pre, self.num_post];
+        codec::write_int24(&mut buf, self.scale_factor);
+        codec::write_float40(&mut buf, self.time_interval);
+        for &v in &self.speeds {
+            codec::write_int16_scaled(&mut buf, v, sf);
+        }
+        // byte[0] = len(payload) - 1
+        buf[0] = (buf.len() - 1) as u8;
+        buf
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -609,6 +793,40 @@ impl TrackingStatus {
             track_measure4: codec::read_uint16(payload, 80)?,
         })
     }
+
+    /// Encode to the 82-byte wire payload. Undecoded bytes are zero.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buf = vec![0x51, self.state, self.flags];
+        buf.resize(22, 0);
+        codec::write_uint24(&mut buf, self.pre_trig_buf_start);
+        codec::write_uint24(&mut buf, self.club_impact_idx);
+        codec::write_uint24(&mut buf, self.trigger_idx);
+        buf.push(0);
+        codec::write_uint24(&mut buf, self.radar_cal1);
+        codec::write_uint24(&mut buf, self.radar_cal2);
+        codec::write_uint16(&mut buf, self.radar_cal_avr);
+        buf.resize(47, 0);
+        buf.push(self.processing_iteration);
+        buf.push(self.result_quality);
+        buf.resize(51, 0);
+        buf.push(self.detection_subtype);
+        buf.resize(54, 0);
+        buf.push(self.prc_tracking_count);
+        buf.push(0);
+        codec::write_uint16(&mut buf, self.radar_measurement);
+        buf.push(0);
+        buf.push(self.trigger_flags);
+        buf.resize(62, 0);
+        codec::write_uint16(&mut buf, self.event_counter);
+        buf.resize(67, 0);
+        codec::write_int24(&mut buf, self.radar_baseline);
+        for v in self.track_measure {
+            codec::write_int24(&mut buf, v);
+        }
+        buf.push(0);
+        codec::write_uint16(&mut buf, self.track_measure4);
+        buf
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -617,6 +835,20 @@ impl TrackingStatus {
 
 const PK_SCALE: f64 = 10000.0 / (1u32 << 23) as f64;
 
+/// Write polynomial coefficients as INT24, multiplied back by `ps`.
+fn write_poly(buf: &mut Vec<u8>, coeffs: &[f64], ps: f64) {
+    for &c in coeffs {
+        codec::write_int24_scaled(buf, c, ps);
+    }
+}
+
+/// Write PRC peak values as INT24, divided back by `PK_SCALE`.
+fn write_pk(buf: &mut Vec<u8>, pk: &[f64; 6]) {
+    for &p in pk {
+        codec::write_int24_scaled(buf, p, 1.0 / PK_SCALE);
+    }
+}
+
 /// A single ball radar tracking point (60 bytes).
 #[derive(Debug, Clone)]
 pub struct PrcPoint {
@@ -660,6 +892,12 @@ pub struct PrcData {
 }
 
 impl PrcData {
+    /// Most points one 0xEC frame can carry: the length byte must hold
+    /// `3 + points * 60`.
+    pub const MAX_POINTS: usize = 4;
+
+    /// Decode from the wire payload. The length byte wins over the record
+    /// count in byte 3: bytes past the length are not read as points.
     pub fn decode(payload: &[u8]) -> Result<Self> {
         if payload.len() < 4 {
             return Err(WireError::payload_too_short("PrcData", 4, payload.len()));
@@ -680,6 +918,8 @@ impl PrcData {
             });
         };
 
+        // The length byte bounds the record count, whatever byte 3 claims.
+        let sub_count = sub_count.min((header - 3) / stride);
         let mut points = Vec::with_capacity(sub_count);
         for i in 0..sub_count {
             let base = 4 + i * stride;
@@ -716,6 +956,50 @@ impl PrcData {
 
         Ok(Self { sequence, points })
     }
+
+    /// Split into frames of at most [`MAX_POINTS`](Self::MAX_POINTS)
+    /// points, numbered on from `sequence`.
+    #[must_use]
+    pub fn pages(&self) -> Vec<Self> {
+        self.points
+            .chunks(Self::MAX_POINTS)
+            .zip(0i16..)
+            .map(|(points, i)| Self {
+                sequence: self.sequence.wrapping_add(i),
+                points: points.to_vec(),
+            })
+            .collect()
+    }
+
+    /// Encode to the wire payload (version 4, 60-byte stride). Only the
+    /// first [`MAX_POINTS`](Self::MAX_POINTS) points fit; encode
+    /// [`pages()`](Self::pages) to send more.
+    pub fn encode(&self) -> Vec<u8> {
+        let points = &self.points[..self.points.len().min(Self::MAX_POINTS)];
+        let mut buf = vec![(3 + points.len() * 60) as u8];
+        codec::write_int16(&mut buf, self.sequence);
+        buf.push(points.len() as u8);
+        for p in points {
+            codec::write_int16(&mut buf, p.index);
+            codec::write_int16(&mut buf, p.peak);
+            codec::write_int24(&mut buf, p.snr);
+            codec::write_int16(&mut buf, p.buf_idx);
+            buf.push(p.flags);
+            codec::write_int24(&mut buf, p.time);
+            codec::write_int24_scaled(&mut buf, p.n, 100_000.0);
+            codec::write_int16_scaled(&mut buf, p.az, 100.0);
+            codec::write_int16_scaled(&mut buf, p.el, 100.0);
+            codec::write_int24_scaled(&mut buf, p.vel, 100.0);
+            codec::write_int24_scaled(&mut buf, p.dist, 1000.0);
+            codec::write_int24(&mut buf, p.sync_idx);
+            codec::write_int24(&mut buf, p.sync_buf);
+            for v in [p.az1, p.az2, p.az3, p.el1, p.el2] {
+                codec::write_int16_scaled(&mut buf, v, 100.0);
+            }
+            write_pk(&mut buf, &p.pk);
+        }
+        buf
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -770,6 +1054,12 @@ pub struct ClubPrc {
 }
 
 impl ClubPrc {
+    /// Most records one 0xEE page can carry: the length byte must hold
+    /// `points * 76`. Longer captures are paged with [`encode_request`].
+    ///
+    /// [`encode_request`]: Self::encode_request
+    pub const MAX_POINTS: usize = 3;
+
     /// Decode from a response payload. First byte is data_len, rest is sub-records.
     pub fn decode(payload: &[u8]) -> Result<Self> {
         if payload.is_empty() {
@@ -825,6 +1115,54 @@ impl ClubPrc {
         Ok(Self { points })
     }
 
+    /// Split into pages of at most [`MAX_POINTS`](Self::MAX_POINTS)
+    /// records, the answers to successive [`encode_request`] pages.
+    ///
+    /// [`encode_request`]: Self::encode_request
+    #[must_use]
+    pub fn pages(&self) -> Vec<Self> {
+        self.points
+            .chunks(Self::MAX_POINTS)
+            .map(|points| Self {
+                points: points.to_vec(),
+            })
+            .collect()
+    }
+
+    /// Encode to the response payload: `data_len` byte followed by
+    /// 76-byte records. Only the first [`MAX_POINTS`](Self::MAX_POINTS)
+    /// records fit; encode [`pages()`](Self::pages) to send more.
+    pub fn encode(&self) -> Vec<u8> {
+        let points = &self.points[..self.points.len().min(Self::MAX_POINTS)];
+        let mut buf = vec![(points.len() * 76) as u8];
+        for p in points {
+            codec::write_int16(&mut buf, p.index);
+            codec::write_int16(&mut buf, p.buf_ofs);
+            codec::write_int16(&mut buf, p.peak);
+            codec::write_int24(&mut buf, p.snr);
+            codec::write_int16(&mut buf, p.buf_idx);
+            codec::write_int24(&mut buf, p.time);
+            codec::write_int24_scaled(&mut buf, p.n, 100_000.0);
+            codec::write_int16_scaled(&mut buf, p.az, 100.0);
+            codec::write_int16_scaled(&mut buf, p.el, 100.0);
+            codec::write_int24_scaled(&mut buf, p.vel, 100.0);
+            codec::write_int24_scaled(&mut buf, p.vel2, 100.0);
+            codec::write_int24_scaled(&mut buf, p.dist, 1000.0);
+            codec::write_int24_scaled(&mut buf, p.f30, 1000.0);
+            codec::write_int24_scaled(&mut buf, p.f33, 1000.0);
+            buf.extend_from_slice(&[0, 0]);
+            buf.push(p.version);
+            codec::write_int24(&mut buf, p.f39);
+            codec::write_int24(&mut buf, p.f42);
+            codec::write_int24_scaled(&mut buf, p.f45, 1000.0);
+            for v in [p.az1, p.az2, p.az3, p.el1, p.el2] {
+                codec::write_int16_scaled(&mut buf, v, 100.0);
+            }
+            write_pk(&mut buf, &p.pk);
+        }
+        buf
+    }
+
     /// Encode a CLUB_PRC page request (APP→AVR, 77 bytes).
     /// `start_index` is the first record index to fetch (increments by 3).
     pub fn encode_request(start_index: u16) -> Vec<u8> {
@@ -864,6 +1202,13 @@ impl ShotText {
         })
     }
 
+    /// Encode as NUL-terminated ASCII.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buf = self.text.as_bytes().to_vec();
+        buf.push(0);
+        buf
+    }
+
     /// Check if this is a "PROCESSED" message.
     pub fn is_processed(&self) -> bool {
         self.text.contains("PROCESSED")
@@ -878,4 +1223,9 @@ impl ShotText {
     pub fn is_trigger(&self) -> bool {
         self.text.contains("BALL TRIGGER")
     }
+
+    /// Parse into a structured [`DeviceNotice`](super::notice::DeviceNotice).
+    pub fn notice(&self) -> super::notice::DeviceNotice {
+        super::notice::DeviceNotice::parse(&self.text)
+    }
 }
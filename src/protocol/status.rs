@@ -63,6 +63,20 @@ impl AvrStatus {
             roll: codec::read_float40(payload, 20)?,
         })
     }
+
+    /// Encode to the 25-byte wire payload.
+    ///
+    /// `full_app_id` is an INT24 at offset 8 whose last byte is shared with
+    /// `temperature` (offset 10); the temperature wins on overlap.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buf = vec![self.version, self.state, self.hw_id_hi, 0, 0, self.hw_id_lo, 0, 0];
+        codec::write_int24(&mut buf, self.full_app_id);
+        buf.truncate(10);
+        codec::write_float40(&mut buf, self.temperature);
+        codec::write_float40(&mut buf, self.tilt);
+        codec::write_float40(&mut buf, self.roll);
+        buf
+    }
 }
 
 /// DSP status response. Type 0xAA, SRC=0x40.
@@ -97,6 +111,14 @@ impl DspStatus {
         }
     }
 
+    /// Encode to the wire payload of the matching format.
+    pub fn encode(&self) -> Vec<u8> {
+        match self {
+            DspStatus::V80(s) => s.encode(),
+            DspStatus::V46(s) => s.encode(),
+        }
+    }
+
     /// Battery percentage (0-100). Returns 0 for Gen2 (field offsets TBD).
     pub fn battery_percent(&self) -> u8 {
         match self {
@@ -163,6 +185,27 @@ impl DspStatus80 {
         })
     }
 
+    /// Encode to the 129-byte wire payload. Undecoded bytes are zero.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buf = vec![0x80, self.state, 0, 0];
+        codec::write_int16(&mut buf, self.input_voltage_usb);
+        buf.resize(8, 0);
+        codec::write_int16(&mut buf, self.system_voltage);
+        buf.resize(18, 0);
+        codec::write_int16(&mut buf, self.battery_current);
+        buf.resize(40, 0);
+        codec::write_int16(&mut buf, self.temperature_raw);
+        buf.resize(53, 0);
+        codec::write_int16(&mut buf, self.battery_voltage);
+        buf.resize(57, 0);
+        codec::write_int16(&mut buf, self.battery_voltage_2);
+        buf.resize(61, 0);
+        codec::write_int16(&mut buf, self.power_level);
+        buf.push(u8::from(self.external_power));
+        buf.resize(129, 0);
+        buf
+    }
+
     /// Temperature in degrees C.
     pub fn temperature_c(&self) -> f64 {
         f64::from(self.temperature_raw) / 100.0
@@ -193,6 +236,17 @@ impl DspStatus46 {
             payload: payload.to_vec(),
         })
     }
+
+    /// Encode the raw payload with `version` and `state` applied.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buf = self.payload.clone();
+        if buf.len() < 2 {
+            buf.resize(2, 0);
+        }
+        buf[0] = self.version;
+        buf[1] = self.state;
+        buf
+    }
 }
 
 impl std::fmt::Debug for DspStatus46 {
@@ -226,6 +280,10 @@ impl PiStatus {
             payload: payload.to_vec(),
         })
     }
+
+    pub fn encode(&self) -> Vec<u8> {
+        self.payload.clone()
+    }
 }
 
 /// Generic command acknowledgment (3 bytes). Type 0x95.
@@ -249,6 +307,10 @@ impl ConfigAck {
             acked_cmd: payload[2],
         })
     }
+
+    pub fn encode(&self) -> Vec<u8> {
+        vec![0x02, self.bus_addr, self.acked_cmd]
+    }
 }
 
 /// Mode reset acknowledgment (3 bytes). Type 0xB1. Always `[02 00 00]`.
@@ -262,6 +324,10 @@ impl ModeAck {
         }
         Ok(Self)
     }
+
+    pub fn encode(&self) -> Vec<u8> {
+        vec![0x02, 0x00, 0x00]
+    }
 }
 
 /// ASCII debug/log message from device subsystems. Type 0xE3.
@@ -286,4 +352,16 @@ impl Text {
             text: String::from_utf8_lossy(&payload[start..end]).into_owned(),
         })
     }
+
+    /// Encode as NUL-terminated ASCII.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buf = self.text.as_bytes().to_vec();
+        buf.push(0);
+        buf
+    }
+
+    /// Parse into a structured [`DeviceNotice`](super::notice::DeviceNotice).
+    pub fn notice(&self) -> super::notice::DeviceNotice {
+        super::notice::DeviceNotice::parse(&self.text)
+    }
 }
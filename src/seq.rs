@@ -13,23 +13,25 @@
 //! All protocol logic lives in the sequencers. The blocking functions are thin
 //! wrappers.
 
+use std::collections::HashMap;
 use std::io::{Read, Write};
 use std::time::{Duration, Instant};
 
 use crate::addr::BusAddr;
-use crate::conn::{BinaryConnection, ConnError, Connection, Envelope};
-use crate::protocol::camera::{CamConfig, CamConfigReq, CamState};
+use crate::conn::{BinaryConnection, ConnError, Connection, Envelope, Hooks, Timestamp};
+use crate::protocol::camera::{CamConfig, CamConfigReq, CamState, WifiScan, WifiScanReq};
 use crate::protocol::config::{
-    AvrConfigCmd, AvrConfigResp, ConfigResp, ModeSet, ParamReadReq, ParamValue, RadarCal,
+    AvrConfigCmd, AvrConfigResp, ConfigResp, ModeSet, ParamData, ParamReadReq, ParamValue,
+    RadarCal,
 };
 use crate::protocol::handshake::{
-    CalDataReq, CalDataResp, CalParamReq, CalParamResp, DevInfoResp, DspQueryResp, NetConfigReq,
-    ProdInfoReq, ProdInfoResp, TimeSync,
+    CalDataReq, CalDataResp, CalParamReq, CalParamResp, DevInfo, DevInfoResp, DspQueryResp,
+    NetConfigReq, ProdInfoReq, ProdInfoResp, ProductInfo, TimeSync,
 };
 use crate::protocol::shot::{
     ClubPrc, ClubResult, FlightResult, FlightResultV1, PrcData, SpeedProfile, SpinResult,
 };
-use crate::protocol::status::{AvrStatus, DspStatus, PiStatus, StatusPoll};
+use crate::protocol::status::{AvrStatus, ConfigAck, DspStatus, PiStatus, StatusPoll};
 use crate::protocol::{Command, Message};
 
 /// Per-exchange timeout (2s for blocking wrappers).
@@ -60,8 +62,8 @@ pub trait Sequence {
 }
 
 /// Send an action on a connection.
-pub fn send_action<S: Read + Write>(
-    conn: &mut BinaryConnection<S>,
+pub fn send_action<S: Read + Write, H: Hooks>(
+    conn: &mut BinaryConnection<S, H>,
     action: Action,
 ) -> Result<(), ConnError> {
     match action {
@@ -74,8 +76,8 @@ pub fn send_action<S: Read + Write>(
 /// Sends initial actions, then loops recv→feed until complete.
 /// The stream must have a read timeout set to pace the loop. The
 /// `deadline` parameter controls the overall timeout for the sequence.
-pub fn drive<S: Read + Write>(
-    conn: &mut BinaryConnection<S>,
+pub fn drive<S: Read + Write, H: Hooks>(
+    conn: &mut BinaryConnection<S, H>,
     seq: &mut impl Sequence,
     actions: Vec<Action>,
     deadline: Instant,
@@ -168,6 +170,20 @@ pub struct DspSync {
     pub config: ConfigResp,
 }
 
+impl DspSync {
+    /// DSP firmware version, serial and build tag.
+    #[must_use]
+    pub fn info(&self) -> DevInfo {
+        self.dev_info.info(BusAddr::Dsp)
+    }
+
+    /// Pi hardware, camera model and product strings.
+    #[must_use]
+    pub fn product_info(&self) -> ProductInfo {
+        ProductInfo::from_responses(&self.prod_info)
+    }
+}
+
 #[derive(Debug)]
 enum DspStep {
     WaitStatus,
@@ -317,6 +333,14 @@ pub struct AvrSync {
     pub avr_config: AvrConfigResp,
 }
 
+impl AvrSync {
+    /// AVR model, firmware version and build time.
+    #[must_use]
+    pub fn info(&self) -> DevInfo {
+        self.dev_info.info(BusAddr::Avr)
+    }
+}
+
 #[derive(Debug)]
 enum AvrStep {
     WaitStatus1,
@@ -392,6 +416,13 @@ impl AvrSequencer {
         vec![]
     }
 
+    /// When [`check_cal_timeout`](Self::check_cal_timeout) will next act,
+    /// if a calibration response is outstanding.
+    #[must_use]
+    pub fn cal_deadline(&self) -> Option<Instant> {
+        self.cal_deadline
+    }
+
     /// Skip past the current optional cal step.
     fn advance_past_cal(&mut self) -> Vec<Action> {
         match self.step {
@@ -581,6 +612,14 @@ pub struct PiSync {
     pub password: String,
 }
 
+impl PiSync {
+    /// PI firmware revision and build time.
+    #[must_use]
+    pub fn info(&self) -> DevInfo {
+        self.dev_info.info(BusAddr::Pi)
+    }
+}
+
 #[derive(Debug)]
 enum PiStep {
     WaitStatus,
@@ -703,9 +742,8 @@ impl Sequence for PiSequencer {
             }
             PiStep::WaitNetConfigPw => {
                 if let Message::NetConfigResp(ref r) = env.message {
-                    let mut parts = r.text.splitn(2, '\0');
-                    self.ssid = parts.next().unwrap_or("").to_string();
-                    self.password = parts.next().unwrap_or("").to_string();
+                    self.ssid = r.ssid().to_string();
+                    self.password = r.password().to_string();
                     // Start the param read batches
                     let ids = vec![
                         0x01, 0x07, 0x08, 0x09, 0x06, 0x0B, 0x03, 0x04, 0x05,
@@ -781,6 +819,8 @@ enum AvrConfigStep {
     /// Wait for B0 commit after RadarCal.
     WaitRadarCalCommitAck,
     Done,
+    /// The AVR answered a write with ConfigNack.
+    Rejected(ConfigAck),
 }
 
 /// Pollable state machine for AVR configuration (Phase 4).
@@ -806,10 +846,34 @@ impl AvrConfigSequencer {
         )];
         (seq, actions)
     }
+
+    /// The ConfigNack the AVR answered a write with, if any. The
+    /// sequencer stops there and never completes.
+    #[must_use]
+    pub fn rejected(&self) -> Option<&ConfigAck> {
+        match &self.step {
+            AvrConfigStep::Rejected(nack) => Some(nack),
+            _ => None,
+        }
+    }
+
+    /// The settings this sequencer writes.
+    #[must_use]
+    pub fn into_settings(self) -> AvrSettings {
+        self.settings
+    }
 }
 
 impl Sequence for AvrConfigSequencer {
     fn feed(&mut self, env: &Envelope) -> Vec<Action> {
+        // should_skip drops ConfigNack; catch it first.
+        if env.src == BusAddr::Avr
+            && let Message::ConfigNack(ref nack) = env.message
+            && !matches!(self.step, AvrConfigStep::Done)
+        {
+            self.step = AvrConfigStep::Rejected(nack.clone());
+            return vec![];
+        }
         if should_skip_with_mode_ack(env, BusAddr::Avr) {
             return vec![];
         }
@@ -900,7 +964,7 @@ impl Sequence for AvrConfigSequencer {
                 }
                 vec![]
             }
-            AvrConfigStep::Done => vec![],
+            AvrConfigStep::Done | AvrConfigStep::Rejected(_) => vec![],
         }
     }
 
@@ -1294,6 +1358,22 @@ pub struct ShotData {
     pub prc: Vec<PrcData>,
     /// Club radar tracking points (0xEE), one per page.
     pub club_prc: Vec<ClubPrc>,
+    /// When "BALL TRIGGER" was received. Filled in by `BinaryClient`.
+    pub trigger_at: Option<Timestamp>,
+    /// When "PROCESSED" was received. Filled in by `BinaryClient`.
+    pub processed_at: Option<Timestamp>,
+    /// When the first flight result (0xD4) was received.
+    pub flight_at: Option<Timestamp>,
+    /// When the device reported re-armed, completing the shot.
+    pub completed_at: Option<Timestamp>,
+}
+
+impl ShotData {
+    /// Trigger-to-flight-result latency, if both were seen.
+    #[must_use]
+    pub fn flight_latency(&self) -> Option<Duration> {
+        Some(self.flight_at?.duration_since(self.trigger_at?))
+    }
 }
 
 /// A piece of shot data yielded during the shot lifecycle, between
@@ -1332,7 +1412,7 @@ enum ShotStep {
 /// If the device doesn't send IDLE within this window (firmware bug on
 /// some Gen1 Mevo+ units), proceed directly to ARM without the redundant
 /// ShotResultReq — ClubResult already arrives during the drain phase.
-const DRAIN_TIMEOUT: Duration = Duration::from_secs(3);
+pub const DRAIN_TIMEOUT: Duration = Duration::from_secs(3);
 
 /// Pollable state machine for post-shot handling.
 ///
@@ -1354,6 +1434,9 @@ pub struct ShotSequencer {
     /// Deadline for drain phase. Set when FlightResult (D4) arrives.
     /// If IDLE hasn't arrived by this time, skip directly to ARM.
     drain_deadline: Option<Instant>,
+    /// How long after FlightResult to wait for IDLE; `None` waits
+    /// indefinitely.
+    drain_timeout: Option<Duration>,
 }
 
 impl ShotSequencer {
@@ -1365,6 +1448,7 @@ impl ShotSequencer {
             data: ShotData::default(),
             pending: None,
             drain_deadline: None,
+            drain_timeout: Some(DRAIN_TIMEOUT),
         };
         let actions = vec![
             Action::Send(Command::ShotDataAck, BusAddr::Avr),
@@ -1373,6 +1457,29 @@ impl ShotSequencer {
         (seq, actions)
     }
 
+    /// Record when the shot was triggered and processed, for the timing
+    /// fields of [`ShotData`].
+    #[must_use]
+    pub fn with_times(
+        mut self,
+        trigger_at: Option<Timestamp>,
+        processed_at: Option<Timestamp>,
+    ) -> Self {
+        self.data.trigger_at = trigger_at;
+        self.data.processed_at = processed_at;
+        self
+    }
+
+    /// Override the IDLE wait after flight data (default
+    /// [`DRAIN_TIMEOUT`]). `None` disables the workaround, for devices
+    /// that always send IDLE (see
+    /// [`Capabilities::drain_timeout`](crate::capabilities::Capabilities::drain_timeout)).
+    #[must_use]
+    pub fn with_drain_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.drain_timeout = timeout;
+        self
+    }
+
     /// Extract the accumulated shot data. Only valid after `is_complete()`.
     #[must_use]
     pub fn into_result(self) -> ShotData {
@@ -1413,6 +1520,16 @@ impl ShotSequencer {
         }
         vec![]
     }
+
+    /// When [`check_drain_timeout`](Self::check_drain_timeout) will next
+    /// act, if still draining with flight data in hand.
+    #[must_use]
+    pub fn drain_deadline(&self) -> Option<Instant> {
+        match self.step {
+            ShotStep::Draining => self.drain_deadline,
+            _ => None,
+        }
+    }
 }
 
 impl Sequence for ShotSequencer {
@@ -1434,9 +1551,10 @@ impl Sequence for ShotSequencer {
                     }
                     Message::FlightResult(r) => {
                         self.data.flight = Some(r.clone());
+                        self.data.flight_at.get_or_insert(env.received);
                         self.pending = Some(ShotDatum::Flight(r.clone()));
                         if self.drain_deadline.is_none() {
-                            self.drain_deadline = Some(Instant::now() + DRAIN_TIMEOUT);
+                            self.drain_deadline = self.drain_timeout.map(|t| Instant::now() + t);
                         }
                     }
                     Message::ClubResult(r) => {
@@ -1478,6 +1596,7 @@ impl Sequence for ShotSequencer {
                     && text.text.contains("ARMED")
                     && !text.text.contains("CANCELLED")
                 {
+                    self.data.completed_at = Some(env.received);
                     self.step = ShotStep::Done;
                 }
                 vec![]
@@ -1491,6 +1610,279 @@ impl Sequence for ShotSequencer {
     }
 }
 
+// ===========================================================================
+// ParamReadSequencer
+// ===========================================================================
+
+/// Pollable state machine for reading parameters back from a node.
+///
+/// Sends one `ParamReadReq` (0xBE) at a time and waits for the matching
+/// `ParamValue` (0xBF) from `dest` before requesting the next, the same
+/// way the handshake reads 0x0C/0x0D. Use it to confirm that written
+/// settings (ball type, tee height, track %) actually took.
+pub struct ParamReadSequencer {
+    dest: BusAddr,
+    ids: Vec<u8>,
+    next: usize,
+    values: HashMap<u8, ParamData>,
+}
+
+impl ParamReadSequencer {
+    #[must_use]
+    pub fn new(ids: &[u8], dest: BusAddr) -> (Self, Vec<Action>) {
+        let seq = Self {
+            dest,
+            ids: ids.to_vec(),
+            next: 0,
+            values: HashMap::new(),
+        };
+        let actions = seq.request_next();
+        (seq, actions)
+    }
+
+    /// The parameter IDs and node this read was created with.
+    #[must_use]
+    pub fn request(&self) -> (&[u8], BusAddr) {
+        (&self.ids, self.dest)
+    }
+
+    /// Values read so far, keyed by parameter ID.
+    #[must_use]
+    pub fn into_result(self) -> HashMap<u8, ParamData> {
+        self.values
+    }
+
+    fn request_next(&self) -> Vec<Action> {
+        match self.ids.get(self.next) {
+            Some(&param_id) => vec![Action::Send(
+                Command::ParamReadReq(ParamReadReq { param_id }),
+                self.dest,
+            )],
+            None => vec![],
+        }
+    }
+}
+
+impl Sequence for ParamReadSequencer {
+    fn feed(&mut self, env: &Envelope) -> Vec<Action> {
+        if env.src != self.dest {
+            return vec![];
+        }
+        if let Message::ParamValue(ref pv) = env.message
+            && self.ids.get(self.next) == Some(&pv.param_id)
+        {
+            self.values.insert(pv.param_id, pv.value.clone());
+            self.next += 1;
+            return self.request_next();
+        }
+        vec![]
+    }
+
+    fn is_complete(&self) -> bool {
+        self.next >= self.ids.len()
+    }
+}
+
+// ===========================================================================
+// IdentifySequencer
+// ===========================================================================
+
+/// What [`IdentifySequencer`] learned about a device.
+#[derive(Debug, Clone)]
+pub struct DeviceIdentity {
+    pub hw_info: DspQueryResp,
+    /// PI firmware revision, build date and time.
+    pub pi_info: DevInfoResp,
+    pub ssid: String,
+}
+
+#[derive(Debug)]
+enum IdentifyStep {
+    WaitDspQuery,
+    WaitPiDevInfo,
+    WaitNetConfig,
+    WaitNetConfigPw,
+    Done,
+}
+
+/// Pollable state machine for a quick device identification: DSP query
+/// (0xC8), PI device info (0xE7) and network config (0xDE).
+///
+/// Unlike the full handshake it neither polls status nor touches
+/// configuration, so it is cheap enough to run against every host of a
+/// subnet.
+///
+/// The SSID is asked for with the SSID sub-query first. Current firmware
+/// answers that with empty text slots, and only the password sub-query
+/// returns the SSID — alongside the WiFi password, in cleartext. The
+/// sequencer falls back to it in that case and keeps only the SSID, but
+/// the password still crosses the network and reaches any connection hooks
+/// (and session recordings) on the way.
+pub struct IdentifySequencer {
+    step: IdentifyStep,
+    hw_info: Option<DspQueryResp>,
+    pi_info: Option<DevInfoResp>,
+    ssid: String,
+}
+
+impl IdentifySequencer {
+    #[must_use]
+    pub fn new() -> (Self, Vec<Action>) {
+        let seq = Self {
+            step: IdentifyStep::WaitDspQuery,
+            hw_info: None,
+            pi_info: None,
+            ssid: String::new(),
+        };
+        let actions = vec![Action::Send(Command::DspQuery, BusAddr::Dsp)];
+        (seq, actions)
+    }
+
+    /// Extract the result. Only valid after `is_complete()`.
+    #[must_use]
+    pub fn into_result(self) -> DeviceIdentity {
+        DeviceIdentity {
+            hw_info: self.hw_info.unwrap(),
+            pi_info: self.pi_info.unwrap(),
+            ssid: self.ssid,
+        }
+    }
+}
+
+impl Sequence for IdentifySequencer {
+    fn feed(&mut self, env: &Envelope) -> Vec<Action> {
+        match self.step {
+            IdentifyStep::WaitDspQuery => {
+                if should_skip_with_mode_ack(env, BusAddr::Dsp) {
+                    return vec![];
+                }
+                if let Message::DspQueryResp(ref r) = env.message {
+                    self.hw_info = Some(r.clone());
+                    self.step = IdentifyStep::WaitPiDevInfo;
+                    return vec![Action::Send(Command::DevInfoReq, BusAddr::Pi)];
+                }
+                vec![]
+            }
+            IdentifyStep::WaitPiDevInfo => {
+                if should_skip_with_mode_ack(env, BusAddr::Pi) {
+                    return vec![];
+                }
+                if let Message::DevInfoResp(ref r) = env.message {
+                    self.pi_info = Some(r.clone());
+                    self.step = IdentifyStep::WaitNetConfig;
+                    return vec![Action::Send(
+                        Command::NetConfigReq(NetConfigReq {
+                            query_password: false,
+                        }),
+                        BusAddr::Pi,
+                    )];
+                }
+                vec![]
+            }
+            IdentifyStep::WaitNetConfig => {
+                if should_skip_with_mode_ack(env, BusAddr::Pi) {
+                    return vec![];
+                }
+                if let Message::NetConfigResp(ref r) = env.message {
+                    if r.ssid().is_empty() {
+                        self.step = IdentifyStep::WaitNetConfigPw;
+                        return vec![Action::Send(
+                            Command::NetConfigReq(NetConfigReq {
+                                query_password: true,
+                            }),
+                            BusAddr::Pi,
+                        )];
+                    }
+                    self.ssid = r.ssid().to_string();
+                    self.step = IdentifyStep::Done;
+                }
+                vec![]
+            }
+            IdentifyStep::WaitNetConfigPw => {
+                if should_skip_with_mode_ack(env, BusAddr::Pi) {
+                    return vec![];
+                }
+                if let Message::NetConfigResp(ref r) = env.message {
+                    // The second slot is the password; drop it.
+                    self.ssid = r.ssid().to_string();
+                    self.step = IdentifyStep::Done;
+                }
+                vec![]
+            }
+            IdentifyStep::Done => vec![],
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        matches!(self.step, IdentifyStep::Done)
+    }
+}
+
+// ===========================================================================
+// WifiScanSequencer
+// ===========================================================================
+
+/// Pages [`WifiScanSequencer`] requests, as many as the official app.
+pub const WIFI_SCAN_PAGES: u8 = 8;
+
+/// Pollable state machine for the PI's WiFi scan (0x87).
+///
+/// Requests pages 0 to [`WIFI_SCAN_PAGES`] - 1 one at a time, taking each
+/// 0x87 reply as the page asked for, and collects them raw.
+pub struct WifiScanSequencer {
+    page: u8,
+    pages: Vec<WifiScan>,
+    done: bool,
+}
+
+impl WifiScanSequencer {
+    #[must_use]
+    pub fn new() -> (Self, Vec<Action>) {
+        let seq = Self {
+            page: 0,
+            pages: Vec::new(),
+            done: false,
+        };
+        let actions = seq.request_page();
+        (seq, actions)
+    }
+
+    /// Every page read, in page order.
+    #[must_use]
+    pub fn into_result(self) -> Vec<WifiScan> {
+        self.pages
+    }
+
+    fn request_page(&self) -> Vec<Action> {
+        vec![Action::Send(
+            Command::WifiScanReq(WifiScanReq { page: self.page }),
+            BusAddr::Pi,
+        )]
+    }
+}
+
+impl Sequence for WifiScanSequencer {
+    fn feed(&mut self, env: &Envelope) -> Vec<Action> {
+        if env.src != BusAddr::Pi || self.done {
+            return vec![];
+        }
+        if let Message::WifiScan(ref scan) = env.message {
+            self.pages.push(scan.clone());
+            self.page += 1;
+            if self.page >= WIFI_SCAN_PAGES {
+                self.done = true;
+                return vec![];
+            }
+            return self.request_page();
+        }
+        vec![]
+    }
+
+    fn is_complete(&self) -> bool {
+        self.done
+    }
+}
+
 // ===========================================================================
 // Blocking convenience wrappers (preserve pre-v0.1 API)
 // ===========================================================================
@@ -1646,6 +2038,23 @@ pub fn arm(conn: &mut Connection) -> Result<(), ConnError> {
     drive(conn, &mut seq, actions, deadline)
 }
 
+/// Read parameters from `dest` (blocking wrapper).
+pub fn read_params(
+    conn: &mut Connection,
+    ids: &[u8],
+    dest: BusAddr,
+) -> Result<HashMap<u8, ParamData>, ConnError> {
+    conn.stream_mut()
+        .set_read_timeout(Some(Duration::from_millis(100)))?;
+    let (mut seq, actions) = ParamReadSequencer::new(ids, dest);
+    if seq.is_complete() {
+        return Ok(seq.into_result());
+    }
+    let deadline = Instant::now() + Duration::from_secs(30);
+    drive(conn, &mut seq, actions, deadline)?;
+    Ok(seq.into_result())
+}
+
 /// Status from all three nodes, collected during a keepalive poll.
 #[derive(Debug, Clone)]
 pub struct KeepaliveStatus {
@@ -0,0 +1,545 @@
+//! Session recording and deterministic replay.
+//!
+//! [`SessionRecorder`] hooks a [`BinaryConnection`]'s
+//! [`on_wire`](BinaryConnection::set_on_wire) callback and appends every
+//! frame to a compact session file.
+//! [`ReplayStream`] is a `Read + Write` stream that plays a recording back
+//! into a [`BinaryClient`](crate::BinaryClient) at original or accelerated
+//! speed, so field bugs ("shot never completed") reproduce on a desk.
+//!
+//! # File format
+//!
+//! ```text
+//! "ISES" VERSION(1)
+//! { DIR(1) BUS(1) TIME_US(8, LE) LEN(4, LE) WIRE(LEN) }*
+//! ```
+//!
+//! `DIR` is 0 for APP→device, 1 for device→APP. `BUS` is the destination
+//! (sent) or source (received) bus address. `TIME_US` is wall-clock time in
+//! microseconds since the Unix epoch. `WIRE` is the complete stuffed frame
+//! (F0 .. F1), byte-identical to what crossed the socket, at most
+//! [`MAX_WIRE_LEN`] bytes. Received frames are recorded before decoding, so
+//! frames the client fails to decode are kept too.
+//!
+//! ```no_run
+//! use ironsight::session::{ReplayStream, SessionRecorder};
+//! use ironsight::{BinaryClient, BinaryConnection};
+//!
+//! // Record.
+//! let mut conn = BinaryConnection::connect(ironsight::conn::DEFAULT_ADDR)?;
+//! let recorder = SessionRecorder::create("field.ises")?;
+//! recorder.attach(&mut conn);
+//! let mut client = BinaryClient::from_tcp(conn)?;
+//! // ... run the session, then recorder.flush()?
+//!
+//! // Replay at 10x.
+//! let replay = ReplayStream::load("field.ises")?.with_speed(10.0);
+//! let mut client = BinaryClient::new(BinaryConnection::new(replay.clone()));
+//! client.handshake();
+//! while let Ok(ev) = client.poll() {
+//!     if let Some(ev) = ev {
+//!         println!("{ev:?}");
+//!     }
+//! }
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::addr::BusAddr;
+use crate::conn::BinaryConnection;
+pub use crate::conn::Direction;
+use crate::error::Result;
+use crate::frame::{FrameSplitter, RawFrame};
+
+/// File magic.
+const MAGIC: &[u8; 4] = b"ISES";
+/// Current format version.
+const VERSION: u8 = 1;
+/// Longest `WIRE` a session file may hold. Real frames are a few hundred
+/// bytes even fully stuffed; anything longer is a corrupt length field.
+pub const MAX_WIRE_LEN: u32 = 64 * 1024;
+
+/// One recorded frame.
+#[derive(Debug, Clone)]
+pub struct SessionRecord {
+    /// Wall-clock time since the Unix epoch.
+    pub timestamp: Duration,
+    pub direction: Direction,
+    /// Destination (sent) or source (received) bus address.
+    pub bus: BusAddr,
+    /// Complete stuffed wire frame (F0 .. F1).
+    pub wire: Vec<u8>,
+}
+
+impl SessionRecord {
+    /// Parse the recorded wire bytes.
+    pub fn frame(&self) -> Result<RawFrame> {
+        RawFrame::parse(&self.wire)
+    }
+
+    /// Append this record to `w` in session file format.
+    pub fn write_to(&self, w: &mut impl Write) -> io::Result<()> {
+        let dir = match self.direction {
+            Direction::Sent => 0,
+            Direction::Received => 1,
+        };
+        let time_us = u64::try_from(self.timestamp.as_micros()).unwrap_or(u64::MAX);
+        let len = u32::try_from(self.wire.len())
+            .ok()
+            .filter(|&len| len <= MAX_WIRE_LEN)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "frame too large"))?;
+        w.write_all(&[dir, self.bus.as_byte()])?;
+        w.write_all(&time_us.to_le_bytes())?;
+        w.write_all(&len.to_le_bytes())?;
+        w.write_all(&self.wire)
+    }
+
+    /// Read a complete session (header + records).
+    pub fn read_all(mut r: impl Read) -> io::Result<Vec<SessionRecord>> {
+        let mut header = [0u8; 5];
+        r.read_exact(&mut header)?;
+        if &header[..4] != MAGIC {
+            return Err(invalid_data("not a session file"));
+        }
+        if header[4] != VERSION {
+            return Err(invalid_data(format!(
+                "unsupported session version {}",
+                header[4]
+            )));
+        }
+
+        let mut records = Vec::new();
+        let mut head = [0u8; 14];
+        loop {
+            // Clean EOF between records ends the session.
+            match r.read(&mut head[..1])? {
+                0 => break,
+                _ => r.read_exact(&mut head[1..])?,
+            }
+            let direction = match head[0] {
+                0 => Direction::Sent,
+                1 => Direction::Received,
+                d => return Err(invalid_data(format!("bad direction byte 0x{d:02X}"))),
+            };
+            let bus = BusAddr::from_byte(head[1]).map_err(invalid_data)?;
+            let time_us = u64::from_le_bytes(head[2..10].try_into().unwrap_or_default());
+            let len = u32::from_le_bytes(head[10..14].try_into().unwrap_or_default());
+            if len > MAX_WIRE_LEN {
+                return Err(invalid_data(format!(
+                    "record length {len} exceeds {MAX_WIRE_LEN}"
+                )));
+            }
+            let mut wire = vec![0u8; len as usize];
+            r.read_exact(&mut wire)?;
+            records.push(SessionRecord {
+                timestamp: Duration::from_micros(time_us),
+                direction,
+                bus,
+                wire,
+            });
+        }
+        Ok(records)
+    }
+}
+
+/// Read a session file from disk.
+pub fn load(path: impl AsRef<Path>) -> io::Result<Vec<SessionRecord>> {
+    SessionRecord::read_all(BufReader::new(File::open(path)?))
+}
+
+fn invalid_data(e: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, e)
+}
+
+fn now() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+}
+
+// ---------------------------------------------------------------------------
+// SessionRecorder
+// ---------------------------------------------------------------------------
+
+/// Appends a connection's traffic to a session file.
+///
+/// Cheap to clone; clones share the same output.
+#[derive(Clone)]
+pub struct SessionRecorder {
+    inner: Arc<Mutex<RecorderState>>,
+}
+
+struct RecorderState {
+    out: Box<dyn Write + Send>,
+    /// First write error, surfaced by [`SessionRecorder::flush`].
+    error: Option<io::Error>,
+}
+
+impl SessionRecorder {
+    /// Record to any writer. Writes the file header immediately.
+    pub fn new(mut out: impl Write + Send + 'static) -> io::Result<Self> {
+        out.write_all(MAGIC)?;
+        out.write_all(&[VERSION])?;
+        Ok(Self {
+            inner: Arc::new(Mutex::new(RecorderState {
+                out: Box::new(out),
+                error: None,
+            })),
+        })
+    }
+
+    /// Record to a new file at `path` (truncates an existing file).
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::new(BufWriter::new(File::create(path)?))
+    }
+
+    /// Install an `on_wire` hook on `conn` that records every frame.
+    /// Replaces any `on_wire` hook already set; `on_send`/`on_recv` are
+    /// left alone.
+    pub fn attach<S: Read + Write>(&self, conn: &mut BinaryConnection<S>) {
+        let rec = self.clone();
+        conn.set_on_wire(move |direction, wire| rec.record_wire(direction, wire));
+    }
+
+    /// Record one frame, timestamped now.
+    pub fn record(&self, direction: Direction, frame: &RawFrame) {
+        self.record_wire(direction, &frame.encode());
+    }
+
+    /// Record one complete wire frame (F0 .. F1) as-is, timestamped now.
+    /// `bus` is read from the frame header, falling back to
+    /// [`BusAddr::App`] if it names no known node.
+    pub fn record_wire(&self, direction: Direction, wire: &[u8]) {
+        // Bus addresses never need stuffing: DEST and SRC are bytes 1 and 2.
+        let index = match direction {
+            Direction::Sent => 1,
+            Direction::Received => 2,
+        };
+        let bus = wire
+            .get(index)
+            .and_then(|&b| BusAddr::from_byte(b).ok())
+            .unwrap_or(BusAddr::App);
+        let record = SessionRecord {
+            timestamp: now(),
+            direction,
+            bus,
+            wire: wire.to_vec(),
+        };
+        let mut st = self.state();
+        if st.error.is_none()
+            && let Err(e) = record.write_to(&mut st.out)
+        {
+            st.error = Some(e);
+        }
+    }
+
+    /// Flush buffered records, returning the first write error (if any).
+    pub fn flush(&self) -> io::Result<()> {
+        let mut st = self.state();
+        if let Some(e) = st.error.take() {
+            return Err(e);
+        }
+        st.out.flush()
+    }
+
+    fn state(&self) -> MutexGuard<'_, RecorderState> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// ReplayStream
+// ---------------------------------------------------------------------------
+
+/// `Read + Write` stream that plays a recorded session back.
+///
+/// Received frames are handed out on `read()` with their original spacing
+/// divided by the speed factor; nothing due reads as `WouldBlock`, and the
+/// end of the recording reads as EOF (`ConnError::Disconnected`).
+///
+/// In lockstep mode (the default) each recorded send is a gate: frames
+/// recorded after it are held until the client writes a frame with the same
+/// destination and type ID, and their timing restarts from that point.
+/// Writes that match no gate (e.g. an extra keepalive) are ignored. This
+/// keeps responses behind the requests that caused them regardless of how
+/// fast the replaying client runs.
+///
+/// Cheap to clone; clones share the same state, so one handle can inspect
+/// [`written`](Self::written) while another is owned by the connection.
+#[derive(Clone)]
+pub struct ReplayStream {
+    inner: Arc<Mutex<ReplayState>>,
+}
+
+struct ReplayState {
+    records: VecDeque<SessionRecord>,
+    speed: f64,
+    lockstep: bool,
+    /// (real time, recorded time) pair that received frames are timed from.
+    /// Set on the first read and re-anchored at each matched gate.
+    anchor: Option<(Instant, Duration)>,
+    outbox: VecDeque<u8>,
+    splitter: FrameSplitter,
+    written: Vec<RawFrame>,
+}
+
+impl ReplayStream {
+    /// Replay `records` at original speed, in lockstep.
+    pub fn new(records: Vec<SessionRecord>) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(ReplayState {
+                records: records.into(),
+                speed: 1.0,
+                lockstep: true,
+                anchor: None,
+                outbox: VecDeque::new(),
+                splitter: FrameSplitter::new(),
+                written: Vec::new(),
+            })),
+        }
+    }
+
+    /// Load and replay a session file.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Self::new(load(path)?))
+    }
+
+    /// Set the playback speed factor (2.0 = twice as fast).
+    /// `f64::INFINITY` releases frames as soon as they are ungated.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `factor` is not positive.
+    pub fn with_speed(self, factor: f64) -> Self {
+        assert!(factor > 0.0, "replay speed must be positive");
+        self.state().speed = factor;
+        self
+    }
+
+    /// Enable or disable lockstep gating on recorded sends.
+    pub fn with_lockstep(self, lockstep: bool) -> Self {
+        self.state().lockstep = lockstep;
+        self
+    }
+
+    /// Frames the client has written so far, in order.
+    pub fn written(&self) -> Vec<RawFrame> {
+        self.state().written.clone()
+    }
+
+    /// Records not yet played (or, for sends, not yet matched).
+    pub fn remaining(&self) -> usize {
+        self.state().records.len()
+    }
+
+    /// True once every record has been played and read.
+    pub fn is_finished(&self) -> bool {
+        let st = self.state();
+        st.records.is_empty() && st.outbox.is_empty()
+    }
+
+    fn state(&self) -> MutexGuard<'_, ReplayState> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl ReplayState {
+    /// Move every due, ungated received frame into the outbox.
+    fn release(&mut self, now: Instant) {
+        while let Some(rec) = self.records.front() {
+            match rec.direction {
+                Direction::Sent if self.lockstep => break,
+                Direction::Sent => {
+                    self.records.pop_front();
+                }
+                Direction::Received => {
+                    let (real, recorded) = *self.anchor.get_or_insert((now, rec.timestamp));
+                    let offset = rec.timestamp.saturating_sub(recorded);
+                    let due = real + offset.div_f64(self.speed);
+                    if now < due {
+                        break;
+                    }
+                    self.outbox.extend(&rec.wire);
+                    self.records.pop_front();
+                }
+            }
+        }
+    }
+
+    /// Open the gate at the head of the queue if `frame` matches it.
+    fn gate(&mut self, frame: &RawFrame, now: Instant) {
+        let Some(rec) = self.records.front() else {
+            return;
+        };
+        if !self.lockstep || rec.direction != Direction::Sent {
+            return;
+        }
+        let matches = rec
+            .frame()
+            .is_ok_and(|f| f.dest == frame.dest && f.type_id == frame.type_id);
+        if matches {
+            self.anchor = Some((now, rec.timestamp));
+            self.records.pop_front();
+        }
+    }
+}
+
+impl Read for ReplayStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut st = self.state();
+        st.release(Instant::now());
+        if st.outbox.is_empty() {
+            if st.records.is_empty() {
+                return Ok(0);
+            }
+            return Err(io::ErrorKind::WouldBlock.into());
+        }
+        let n = buf.len().min(st.outbox.len());
+        for (dst, src) in buf.iter_mut().zip(st.outbox.drain(..n)) {
+            *dst = src;
+        }
+        Ok(n)
+    }
+}
+
+impl Write for ReplayStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut st = self.state();
+        let now = Instant::now();
+        for wire in st.splitter.feed(buf) {
+            if let Ok(frame) = RawFrame::parse(&wire) {
+                st.gate(&frame, now);
+                st.written.push(frame);
+            }
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::status::{ModeAck, StatusPoll};
+    use crate::protocol::{Command, Message};
+
+    fn record(direction: Direction, ms: u64, frame: RawFrame) -> SessionRecord {
+        let bus = match direction {
+            Direction::Sent => frame.dest,
+            Direction::Received => frame.src,
+        };
+        SessionRecord {
+            timestamp: Duration::from_millis(1_700_000_000_000 + ms),
+            direction,
+            bus,
+            wire: frame.encode(),
+        }
+    }
+
+    fn poll_avr() -> RawFrame {
+        Command::StatusPoll(StatusPoll { pi_mode: false }).encode(BusAddr::Avr)
+    }
+
+    fn mode_ack() -> RawFrame {
+        Message::ModeAck(ModeAck).encode(BusAddr::Avr, BusAddr::App)
+    }
+
+    #[test]
+    fn file_round_trip() {
+        let records = vec![
+            record(Direction::Sent, 0, poll_avr()),
+            record(Direction::Received, 12, mode_ack()),
+        ];
+        let mut buf = Vec::new();
+        buf.extend_from_slice(MAGIC);
+        buf.push(VERSION);
+        for r in &records {
+            r.write_to(&mut buf).unwrap();
+        }
+        let back = SessionRecord::read_all(&buf[..]).unwrap();
+        assert_eq!(back.len(), 2);
+        assert_eq!(back[0].direction, Direction::Sent);
+        assert_eq!(back[0].bus, BusAddr::Avr);
+        assert_eq!(back[1].timestamp, records[1].timestamp);
+        assert_eq!(back[1].wire, records[1].wire);
+        assert_eq!(back[1].frame().unwrap().src, BusAddr::Avr);
+
+        // Truncated record is an error, not a silent short read.
+        assert!(SessionRecord::read_all(&buf[..buf.len() - 1]).is_err());
+        assert!(SessionRecord::read_all(&b"NOPE\x01"[..]).is_err());
+    }
+
+    #[test]
+    fn oversize_length_is_rejected() {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(MAGIC);
+        buf.push(VERSION);
+        buf.extend_from_slice(&[1, BusAddr::Avr.as_byte()]);
+        buf.extend_from_slice(&0u64.to_le_bytes());
+        buf.extend_from_slice(&u32::MAX.to_le_bytes());
+        let err = SessionRecord::read_all(&buf[..]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        let huge = SessionRecord {
+            timestamp: Duration::ZERO,
+            direction: Direction::Received,
+            bus: BusAddr::Avr,
+            wire: vec![0; MAX_WIRE_LEN as usize + 1],
+        };
+        assert!(huge.write_to(&mut Vec::new()).is_err());
+    }
+
+    #[test]
+    fn lockstep_holds_responses_until_request() {
+        let mut replay = ReplayStream::new(vec![
+            record(Direction::Received, 0, mode_ack()),
+            record(Direction::Sent, 5, poll_avr()),
+            record(Direction::Received, 6, mode_ack()),
+        ])
+        .with_speed(f64::INFINITY);
+        let mut buf = [0u8; 64];
+        let wire = mode_ack().encode();
+
+        // Frames before the first send flow immediately.
+        assert_eq!(replay.read(&mut buf).unwrap(), wire.len());
+        let err = replay.read(&mut buf).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::WouldBlock);
+
+        // A non-matching write does not open the gate.
+        let other = Command::StatusPoll(StatusPoll { pi_mode: false }).encode(BusAddr::Dsp);
+        replay.write_all(&other.encode()).unwrap();
+        assert!(replay.read(&mut buf).is_err());
+
+        replay.write_all(&poll_avr().encode()).unwrap();
+        assert_eq!(replay.read(&mut buf).unwrap(), wire.len());
+        assert!(replay.is_finished());
+        assert_eq!(replay.read(&mut buf).unwrap(), 0);
+        assert_eq!(replay.written().len(), 2);
+    }
+
+    #[test]
+    fn timing_follows_recording() {
+        let mut replay = ReplayStream::new(vec![
+            record(Direction::Received, 0, mode_ack()),
+            record(Direction::Received, 10_000, mode_ack()),
+        ]);
+        let mut buf = [0u8; 64];
+        assert!(replay.read(&mut buf).is_ok());
+        // Second frame is 10s out at 1x.
+        assert!(replay.read(&mut buf).is_err());
+        assert_eq!(replay.remaining(), 1);
+    }
+}
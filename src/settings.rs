@@ -0,0 +1,334 @@
+//! Typed builder for [`AvrSettings`].
+//!
+//! Wraps the raw `0xBF` parameter IDs and the `0xA4` radar calibration in
+//! named, unit-aware setters and checks values against the ranges the
+//! device accepts before anything is sent.
+//!
+//! ```
+//! use ironsight::protocol::config::{BallType, DetectionMode};
+//! use ironsight::seq::AvrSettings;
+//!
+//! let settings = AvrSettings::builder(DetectionMode::Outdoor)
+//!     .ball_type(BallType::Rct)
+//!     .min_track_pct(1.0)
+//!     .tee_height_in(1.5)
+//!     .radar_cal_ft(9.0, 1.0)
+//!     .build()?;
+//! assert_eq!(settings.radar_cal.unwrap().range_mm, 2743);
+//! # Ok::<(), ironsight::settings::SettingsError>(())
+//! ```
+
+use crate::protocol::config::{
+    BallType, DetectionMode, PARAM_BALL_TYPE, PARAM_MIN_TRACK_PCT, PARAM_SURFACE_FIRMNESS,
+    PARAM_TEE_HEIGHT, ParamData, ParamValue, RadarCal,
+};
+use crate::seq::AvrSettings;
+
+const MM_PER_INCH: f64 = 25.4;
+const MM_PER_FOOT: f64 = 304.8;
+
+/// Minimum track percentage range (WIRE.md §6, param 0x0F).
+const MIN_TRACK_PCT: std::ops::RangeInclusive<f64> = 0.6..=1.0;
+
+/// Tee height range in meters (param 0x26): up to 4 in, the longest tee
+/// the Rules of Golf allow.
+const TEE_HEIGHT_M: std::ops::RangeInclusive<f64> = 0.0..=4.0 * MM_PER_INCH / 1000.0;
+
+/// A builder value outside the range the device accepts.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum SettingsError {
+    #[error("minimum track percentage {0} out of range (0.6-1.0)")]
+    MinTrackPct(f64),
+
+    #[error("tee height {0} m out of range (0-0.1016 m)")]
+    TeeHeight(f64),
+
+    #[error("radar range {0} mm out of range (1-65535 mm)")]
+    RadarRange(f64),
+
+    #[error("surface height {0} mm out of range (0-255 mm)")]
+    SurfaceHeight(f64),
+}
+
+/// Builder for [`AvrSettings`]. Created by [`AvrSettings::builder`].
+///
+/// Unset parameters are not written, so the device keeps its current
+/// values. Radar calibration is optional for the same reason (the FS Golf
+/// app skips it on mode changes).
+#[derive(Debug, Clone)]
+pub struct AvrSettingsBuilder {
+    mode: DetectionMode,
+    ball_type: Option<BallType>,
+    min_track_pct: Option<f64>,
+    tee_height_m: Option<f64>,
+    surface_firmness: Option<u8>,
+    /// (range, surface height), both in mm before rounding.
+    radar_cal: Option<(f64, f64)>,
+}
+
+impl AvrSettings {
+    /// Start building settings for the given detection mode.
+    #[must_use]
+    pub fn builder(mode: DetectionMode) -> AvrSettingsBuilder {
+        AvrSettingsBuilder {
+            mode,
+            ball_type: None,
+            min_track_pct: None,
+            tee_height_m: None,
+            surface_firmness: None,
+            radar_cal: None,
+        }
+    }
+
+    /// The subset of `self` that differs from `previous`: params whose
+    /// value changed and radar calibration if it changed. The mode is
+    /// always kept (the config sequence commits through ModeSet).
+    #[must_use]
+    pub fn changes_from(&self, previous: &AvrSettings) -> AvrSettings {
+        AvrSettings {
+            mode: self.mode,
+            params: self
+                .params
+                .iter()
+                .filter(|p| !previous.params.contains(p))
+                .cloned()
+                .collect(),
+            radar_cal: self
+                .radar_cal
+                .clone()
+                .filter(|cal| previous.radar_cal.as_ref() != Some(cal)),
+        }
+    }
+
+    /// The settings the device holds after applying `self` on top of
+    /// `previous`: params and radar calibration not mentioned in `self`
+    /// keep their previous values.
+    #[must_use]
+    pub fn merged_onto(&self, previous: &AvrSettings) -> AvrSettings {
+        let mut params: Vec<ParamValue> = previous
+            .params
+            .iter()
+            .filter(|old| !self.params.iter().any(|p| p.param_id == old.param_id))
+            .cloned()
+            .collect();
+        params.extend(self.params.iter().cloned());
+        AvrSettings {
+            mode: self.mode,
+            params,
+            radar_cal: self.radar_cal.clone().or_else(|| previous.radar_cal.clone()),
+        }
+    }
+}
+
+impl AvrSettingsBuilder {
+    /// Ball type (param 0x06).
+    #[must_use]
+    pub fn ball_type(mut self, ball: BallType) -> Self {
+        self.ball_type = Some(ball);
+        self
+    }
+
+    /// Outdoor minimum track percentage, 0.6-1.0 (param 0x0F).
+    #[must_use]
+    pub fn min_track_pct(mut self, pct: f64) -> Self {
+        self.min_track_pct = Some(pct);
+        self
+    }
+
+    /// Driver tee height in meters, up to 0.1016 (param 0x26).
+    #[must_use]
+    pub fn tee_height_m(mut self, meters: f64) -> Self {
+        self.tee_height_m = Some(meters);
+        self
+    }
+
+    /// Driver tee height in inches, up to 4 (param 0x26).
+    #[must_use]
+    pub fn tee_height_in(self, inches: f64) -> Self {
+        self.tee_height_m(inches * MM_PER_INCH / 1000.0)
+    }
+
+    /// Surface firmness index (param 0x08). Not range-checked: the
+    /// values the device accepts are unconfirmed.
+    #[must_use]
+    pub fn surface_firmness(mut self, index: u8) -> Self {
+        self.surface_firmness = Some(index);
+        self
+    }
+
+    /// Radar calibration: sensor-to-tee distance and surface height, in
+    /// meters.
+    #[must_use]
+    pub fn radar_cal_m(mut self, range_m: f64, surface_height_m: f64) -> Self {
+        self.radar_cal = Some((range_m * 1000.0, surface_height_m * 1000.0));
+        self
+    }
+
+    /// Radar calibration: sensor-to-tee distance in feet and surface
+    /// height in inches, as entered in the FS Golf app.
+    #[must_use]
+    pub fn radar_cal_ft(mut self, range_ft: f64, surface_height_in: f64) -> Self {
+        self.radar_cal = Some((range_ft * MM_PER_FOOT, surface_height_in * MM_PER_INCH));
+        self
+    }
+
+    /// Validate and produce the settings.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first [`SettingsError`] for an out-of-range value.
+    pub fn build(self) -> Result<AvrSettings, SettingsError> {
+        let mut params = Vec::new();
+        if let Some(ball) = self.ball_type {
+            params.push(ParamValue {
+                param_id: PARAM_BALL_TYPE,
+                value: ParamData::Int24(ball as i32),
+            });
+        }
+        if let Some(pct) = self.min_track_pct {
+            if !MIN_TRACK_PCT.contains(&pct) {
+                return Err(SettingsError::MinTrackPct(pct));
+            }
+            params.push(ParamValue {
+                param_id: PARAM_MIN_TRACK_PCT,
+                value: ParamData::Float40(pct),
+            });
+        }
+        if let Some(height) = self.tee_height_m {
+            if !TEE_HEIGHT_M.contains(&height) {
+                return Err(SettingsError::TeeHeight(height));
+            }
+            params.push(ParamValue {
+                param_id: PARAM_TEE_HEIGHT,
+                value: ParamData::Float40(height),
+            });
+        }
+        if let Some(index) = self.surface_firmness {
+            params.push(ParamValue {
+                param_id: PARAM_SURFACE_FIRMNESS,
+                value: ParamData::Int24(i32::from(index)),
+            });
+        }
+        let radar_cal = match self.radar_cal {
+            Some((range, height)) => {
+                let range_mm = range.round();
+                if !(1.0..=f64::from(u16::MAX)).contains(&range_mm) {
+                    return Err(SettingsError::RadarRange(range));
+                }
+                // The app truncates: floor(height_inches * 25.4).
+                let height_mm = height.floor();
+                if !(0.0..=f64::from(u8::MAX)).contains(&height_mm) {
+                    return Err(SettingsError::SurfaceHeight(height));
+                }
+                Some(RadarCal {
+                    range_mm: range_mm as u16,
+                    height_mm: height_mm as u8,
+                })
+            }
+            None => None,
+        };
+        Ok(AvrSettings {
+            mode: self.mode.comms_index(),
+            params,
+            radar_cal,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_typed_params() {
+        let s = AvrSettings::builder(DetectionMode::Chipping)
+            .ball_type(BallType::Standard)
+            .min_track_pct(0.8)
+            .tee_height_m(0.0381)
+            .surface_firmness(2)
+            .radar_cal_m(2.743, 0.0255)
+            .build()
+            .unwrap();
+        assert_eq!(s.mode, 5);
+        let ids: Vec<u8> = s.params.iter().map(|p| p.param_id).collect();
+        assert_eq!(ids, [0x06, 0x0F, 0x26, 0x08]);
+        assert!(matches!(s.params[0].value, ParamData::Int24(1)));
+        assert!(matches!(s.params[1].value, ParamData::Float40(v) if v == 0.8));
+        let cal = s.radar_cal.unwrap();
+        assert_eq!((cal.range_mm, cal.height_mm), (2743, 25));
+    }
+
+    #[test]
+    fn imperial_matches_app_rounding() {
+        let s = AvrSettings::builder(DetectionMode::Outdoor)
+            .tee_height_in(1.5)
+            .radar_cal_ft(9.0, 1.0)
+            .build()
+            .unwrap();
+        assert!(matches!(s.params[0].value, ParamData::Float40(v) if (v - 0.0381).abs() < 1e-9));
+        let cal = s.radar_cal.unwrap();
+        assert_eq!((cal.range_mm, cal.height_mm), (2743, 25));
+    }
+
+    #[test]
+    fn rejects_out_of_range() {
+        let b = || AvrSettings::builder(DetectionMode::Outdoor);
+        assert_eq!(
+            b().min_track_pct(0.5).build().unwrap_err(),
+            SettingsError::MinTrackPct(0.5)
+        );
+        assert!(matches!(
+            b().tee_height_m(-0.01).build(),
+            Err(SettingsError::TeeHeight(_))
+        ));
+        assert!(matches!(
+            b().tee_height_m(f64::NAN).build(),
+            Err(SettingsError::TeeHeight(_))
+        ));
+        assert!(matches!(
+            b().tee_height_in(4.5).build(),
+            Err(SettingsError::TeeHeight(_))
+        ));
+        assert!(b().tee_height_in(4.0).build().is_ok());
+        assert!(matches!(
+            b().radar_cal_m(70.0, 0.0).build(),
+            Err(SettingsError::RadarRange(_))
+        ));
+        assert!(matches!(
+            b().radar_cal_ft(9.0, 11.0).build(),
+            Err(SettingsError::SurfaceHeight(_))
+        ));
+        let empty = b().build().unwrap();
+        assert!(empty.params.is_empty() && empty.radar_cal.is_none());
+    }
+
+    #[test]
+    fn diff_and_merge() {
+        let old = AvrSettings::builder(DetectionMode::Outdoor)
+            .ball_type(BallType::Rct)
+            .tee_height_in(1.5)
+            .radar_cal_ft(9.0, 1.0)
+            .build()
+            .unwrap();
+        let new = AvrSettings::builder(DetectionMode::Chipping)
+            .ball_type(BallType::Rct)
+            .tee_height_in(2.0)
+            .radar_cal_ft(9.0, 1.0)
+            .build()
+            .unwrap();
+
+        let delta = new.changes_from(&old);
+        assert_eq!(delta.mode, 5);
+        let ids: Vec<u8> = delta.params.iter().map(|p| p.param_id).collect();
+        assert_eq!(ids, [0x26]);
+        assert!(delta.radar_cal.is_none());
+
+        let merged = AvrSettings::builder(DetectionMode::Putting)
+            .build()
+            .unwrap()
+            .merged_onto(&new);
+        assert_eq!(merged.mode, 3);
+        assert_eq!(merged.params, new.params);
+        assert_eq!(merged.radar_cal, new.radar_cal);
+    }
+}
@@ -0,0 +1,991 @@
+//! In-process Mevo+ simulator for the port-5100 binary protocol.
+//!
+//! [`SimDevice`] is a `Read + Write` stream that plays the DSP, AVR and PI
+//! roles. Frames written to it are parsed and answered the way a real unit
+//! answers them (see `docs/SEQUENCE.md`): the three handshake phases, 0x95
+//! ConfigAck for parameter writes and config commits, ModeSet/RadarCal
+//! echoes, and "ARMED DetectionMode=N" after an arm command. Responses are
+//! queued and handed back on the next `read()`; an empty queue reads as
+//! `WouldBlock`, matching a non-blocking socket.
+//!
+//! [`SimDevice::inject_shot`] pushes a complete shot the way the AVR pushes
+//! one: E5 "BALL TRIGGER" → E8 → E5 "PROCESSED" → D4/ED/D9/EF/EC/EE. The
+//! two ShotDataAcks are answered with E9 and, after the second, "IDLE"; the
+//! client's ShotResultReq and re-arm are answered as usual.
+//!
+//! [`SimServer`] serves the same device over a `TcpListener` for code that
+//! needs a real socket (e.g. [`BinaryClient::from_tcp`](crate::BinaryClient::from_tcp)).
+//!
+//! ```
+//! use ironsight::sim::SimDevice;
+//! use ironsight::{BinaryClient, BinaryConnection, BinaryEvent};
+//!
+//! let device = SimDevice::new();
+//! let mut client = BinaryClient::new(BinaryConnection::new(device.clone()));
+//! client.handshake();
+//! loop {
+//!     if let Some(BinaryEvent::Handshake(h)) = client.poll()? {
+//!         assert_eq!(h.pi.ssid, device.ssid());
+//!         break;
+//!     }
+//! }
+//! # Ok::<(), ironsight::ConnError>(())
+//! ```
+
+use std::collections::{HashMap, VecDeque};
+use std::io::{self, Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::addr::BusAddr;
+use crate::frame::{FrameSplitter, RawFrame};
+use crate::protocol::camera::{CamConfig, WifiScan};
+use crate::protocol::config::{
+    AvrConfigResp, ConfigResp, MODE_OUTDOOR, ParamData, ParamValue,
+};
+use crate::protocol::handshake::{
+    CalDataResp, CalParamResp, DevInfoResp, DeviceGen, DspQueryResp, NetConfigResp,
+    ProdInfoResp,
+};
+use crate::protocol::shot::{
+    ClubPrc, ClubPrcPoint, ClubResult, FlightResult, FlightResultV1, PrcData, PrcPoint,
+    ShotText, SpeedProfile, SpinResult, TrackingStatus,
+};
+use crate::protocol::status::{
+    AvrStatus, ConfigAck, DspStatus, DspStatus46, DspStatus80, ModeAck, PiStatus, Text,
+};
+use crate::protocol::{Command, Message};
+
+// ---------------------------------------------------------------------------
+// Public types
+// ---------------------------------------------------------------------------
+
+/// Headline numbers for a simulated shot.
+///
+/// Everything else in the injected messages (trajectory polynomials, PRC
+/// points, speed profile) is filled with plausible constants.
+#[derive(Debug, Clone)]
+pub struct SimShot {
+    /// Ball speed (m/s)
+    pub ball_speed: f64,
+    /// Vertical launch angle (deg)
+    pub launch_elevation: f64,
+    /// Horizontal launch angle (deg, neg = right)
+    pub launch_azimuth: f64,
+    /// Carry distance (m)
+    pub carry_distance: f64,
+    /// Backspin (RPM)
+    pub backspin_rpm: i32,
+    /// Sidespin (RPM)
+    pub sidespin_rpm: i32,
+    /// Spin axis (deg)
+    pub spin_axis: f64,
+    /// Club head speed (m/s)
+    pub club_speed: f64,
+}
+
+impl Default for SimShot {
+    /// A 7-iron: 120 mph ball speed, 16° launch, ~165 yd carry.
+    fn default() -> Self {
+        Self {
+            ball_speed: 53.6,
+            launch_elevation: 16.3,
+            launch_azimuth: -1.2,
+            carry_distance: 150.0,
+            backspin_rpm: 7000,
+            sidespin_rpm: -400,
+            spin_axis: -3.2,
+            club_speed: 38.0,
+        }
+    }
+}
+
+/// Simulated Mevo+ / Mevo Gen2 device.
+///
+/// Cheap to clone: clones share the same device, so keep one handle to
+/// drive the simulation ([`inject_shot`](Self::inject_shot),
+/// [`disconnect`](Self::disconnect)) while the client owns another.
+#[derive(Clone)]
+pub struct SimDevice {
+    inner: Arc<Mutex<SimState>>,
+}
+
+impl Default for SimDevice {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SimDevice {
+    /// A Gen1 Mevo+ with default identity.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_generation(DeviceGen::MevoPlus)
+    }
+
+    /// A device of the given hardware generation. Affects the DspStatus
+    /// format (0x80 vs 0x46), the C8 dspType byte and the A2 version.
+    #[must_use]
+    pub fn with_generation(generation: DeviceGen) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(SimState::new(generation))),
+        }
+    }
+
+    /// Set the WiFi SSID and password reported by the PI (0xDE).
+    #[must_use]
+    pub fn with_network(self, ssid: &str, password: &str) -> Self {
+        {
+            let mut st = self.state();
+            st.ssid = ssid.to_string();
+            st.password = password.to_string();
+        }
+        self
+    }
+
+    /// Set the raw WiFi scan (0x87) pages the PI answers with, by page
+    /// index. Pages past the end are answered with an empty payload.
+    #[must_use]
+    pub fn with_wifi_scan_pages(self, pages: Vec<Vec<u8>>) -> Self {
+        self.state().wifi_pages = pages;
+        self
+    }
+
+    /// Hardware generation.
+    #[must_use]
+    pub fn generation(&self) -> DeviceGen {
+        self.state().generation
+    }
+
+    /// WiFi SSID reported by the PI.
+    #[must_use]
+    pub fn ssid(&self) -> String {
+        self.state().ssid.clone()
+    }
+
+    /// Whether the radar is armed.
+    #[must_use]
+    pub fn is_armed(&self) -> bool {
+        self.state().armed
+    }
+
+    /// Detection mode (commsIndex) from the last 0xA5 ModeSet.
+    #[must_use]
+    pub fn mode(&self) -> u8 {
+        self.state().mode
+    }
+
+    /// Last value written to an AVR parameter (0xBF), if any.
+    #[must_use]
+    pub fn param(&self, param_id: u8) -> Option<ParamData> {
+        self.state().params.get(&param_id).cloned()
+    }
+
+    /// Number of shots injected so far.
+    #[must_use]
+    pub fn shot_count(&self) -> i32 {
+        self.state().shot_count
+    }
+
+    /// Push a complete shot. Returns `false` (and sends nothing) when the
+    /// radar is not armed — a real unit does not trigger while idle.
+    pub fn inject_shot(&self, shot: &SimShot) -> bool {
+        let mut st = self.state();
+        if !st.armed {
+            return false;
+        }
+        st.push_shot(shot);
+        true
+    }
+
+    /// Push a shot that triggers but is never processed (ball not found):
+    /// "BALL TRIGGER" and the early flight estimate, then nothing. Returns
+    /// `false` when the radar is not armed.
+    pub fn inject_misread(&self, shot: &SimShot) -> bool {
+        let mut st = self.state();
+        if !st.armed {
+            return false;
+        }
+        st.push_trigger(shot);
+        true
+    }
+
+    /// Set the battery level and external power flag reported in the
+    /// DSP status (Gen1 only; the Gen2 layout is not decoded).
+    pub fn set_battery(&self, percent: u8, external_power: bool) {
+        let mut st = self.state();
+        st.battery_percent = percent;
+        st.external_power = external_power;
+    }
+
+    /// Set the temperature reported in the DSP and AVR status.
+    pub fn set_temperature(&self, celsius: f64) {
+        self.state().temperature = celsius;
+    }
+
+    /// Set the tilt and roll (degrees) reported in the AVR status.
+    pub fn set_level(&self, tilt: f64, roll: f64) {
+        let mut st = self.state();
+        st.tilt = tilt;
+        st.roll = roll;
+    }
+
+    /// Run the device clock `secs` ahead of the host (negative = behind).
+    /// "BALL TRIGGER" and "Clubimpact" epochs report this clock.
+    pub fn set_clock_offset(&self, secs: f64) {
+        self.state().clock_offset = secs;
+    }
+
+    /// Push an arbitrary frame from `src` to the APP (e.g. unsolicited text).
+    pub fn inject(&self, src: BusAddr, type_id: u8, payload: Vec<u8>) {
+        self.state().push(src, type_id, payload);
+    }
+
+    /// Drop the connection: reads return `Ok(0)` and writes fail until
+    /// [`reset`](Self::reset) is called.
+    pub fn disconnect(&self) {
+        self.state().connected = false;
+    }
+
+    /// Start a fresh session: clears buffered I/O and the armed state, and
+    /// reconnects after [`disconnect`](Self::disconnect). Written
+    /// parameters and the detection mode persist, as on real hardware.
+    pub fn reset(&self) {
+        self.state().reset_session();
+    }
+
+    fn state(&self) -> MutexGuard<'_, SimState> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Read for SimDevice {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut st = self.state();
+        if !st.connected {
+            return Ok(0);
+        }
+        if st.outbox.is_empty() {
+            return Err(io::ErrorKind::WouldBlock.into());
+        }
+        let n = buf.len().min(st.outbox.len());
+        for (dst, src) in buf.iter_mut().zip(st.outbox.drain(..n)) {
+            *dst = src;
+        }
+        Ok(n)
+    }
+}
+
+impl Write for SimDevice {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut st = self.state();
+        if !st.connected {
+            return Err(io::ErrorKind::BrokenPipe.into());
+        }
+        for wire in st.splitter.feed(buf) {
+            // Garbage from the APP is ignored, as the device does.
+            if let Ok(frame) = RawFrame::parse(&wire) {
+                st.handle(&frame);
+            }
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// SimServer
+// ---------------------------------------------------------------------------
+
+/// Serves a [`SimDevice`] on a local TCP port.
+///
+/// Connections are accepted one at a time; each new connection starts a
+/// fresh session ([`SimDevice::reset`]). [`SimDevice::disconnect`] closes
+/// the current socket. The server thread stops when the `SimServer` is
+/// dropped.
+pub struct SimServer {
+    addr: SocketAddr,
+    device: SimDevice,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl SimServer {
+    /// Bind `addr` (use port 0 for an ephemeral port) and start serving.
+    pub fn spawn(addr: impl ToSocketAddrs, device: SimDevice) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        listener.set_nonblocking(true)?;
+        let addr = listener.local_addr()?;
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread = {
+            let device = device.clone();
+            let stop = Arc::clone(&stop);
+            thread::spawn(move || accept_loop(&listener, &device, &stop))
+        };
+        Ok(Self {
+            addr,
+            device,
+            stop,
+            thread: Some(thread),
+        })
+    }
+
+    /// Address the server is listening on.
+    #[must_use]
+    pub fn local_addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// The simulated device behind this server.
+    #[must_use]
+    pub fn device(&self) -> &SimDevice {
+        &self.device
+    }
+}
+
+impl Drop for SimServer {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(t) = self.thread.take() {
+            let _ = t.join();
+        }
+    }
+}
+
+const SERVER_TICK: Duration = Duration::from_millis(2);
+
+fn accept_loop(listener: &TcpListener, device: &SimDevice, stop: &AtomicBool) {
+    while !stop.load(Ordering::Relaxed) {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                device.reset();
+                let _ = serve(stream, device, stop);
+                device.reset();
+            }
+            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => thread::sleep(SERVER_TICK),
+            Err(_) => return,
+        }
+    }
+}
+
+/// Pump bytes between one TCP connection and the device until either side
+/// closes.
+fn serve(mut stream: TcpStream, device: &SimDevice, stop: &AtomicBool) -> io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_nodelay(true)?;
+    stream.set_read_timeout(Some(SERVER_TICK))?;
+    let mut buf = [0u8; 4096];
+    loop {
+        let outbound = {
+            let mut st = device.state();
+            if !st.connected || stop.load(Ordering::Relaxed) {
+                let _ = stream.shutdown(Shutdown::Both);
+                return Ok(());
+            }
+            st.outbox.drain(..).collect::<Vec<u8>>()
+        };
+        if !outbound.is_empty() {
+            stream.write_all(&outbound)?;
+        }
+        match stream.read(&mut buf) {
+            Ok(0) => return Ok(()),
+            Ok(n) => {
+                let mut dev = device.clone();
+                dev.write_all(&buf[..n])?;
+            }
+            Err(ref e)
+                if e.kind() == io::ErrorKind::WouldBlock || e.kind() == io::ErrorKind::TimedOut => {}
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Device state and protocol handling
+// ---------------------------------------------------------------------------
+
+struct SimState {
+    generation: DeviceGen,
+    ssid: String,
+    password: String,
+    /// Raw WiFi scan pages, by page index.
+    wifi_pages: Vec<Vec<u8>>,
+    connected: bool,
+    splitter: FrameSplitter,
+    /// Encoded wire bytes waiting to be read by the APP.
+    outbox: VecDeque<u8>,
+    armed: bool,
+    mode: u8,
+    params: HashMap<u8, ParamData>,
+    cam_config: CamConfig,
+    shot_count: i32,
+    /// ShotDataAcks still expected before the device reports IDLE.
+    acks_pending: u8,
+    /// Last ClubResult, re-sent on ShotResultReq.
+    last_club: Option<ClubResult>,
+    // Reported in DspStatus (Gen1) / AvrStatus.
+    battery_percent: u8,
+    external_power: bool,
+    temperature: f64,
+    tilt: f64,
+    roll: f64,
+    /// Device clock minus host clock (seconds), as reported in shot text.
+    clock_offset: f64,
+}
+
+impl SimState {
+    fn new(generation: DeviceGen) -> Self {
+        Self {
+            generation,
+            ssid: "FS M2-012345".to_string(),
+            password: "12345678".to_string(),
+            wifi_pages: Vec::new(),
+            connected: true,
+            splitter: FrameSplitter::new(),
+            outbox: VecDeque::new(),
+            armed: false,
+            mode: MODE_OUTDOOR,
+            params: HashMap::new(),
+            cam_config: CamConfig::standard_preset(),
+            shot_count: 0,
+            acks_pending: 0,
+            last_club: None,
+            battery_percent: 87,
+            external_power: false,
+            temperature: 38.5,
+            tilt: 0.4,
+            roll: -0.2,
+            clock_offset: 0.0,
+        }
+    }
+
+    fn reset_session(&mut self) {
+        self.connected = true;
+        self.splitter = FrameSplitter::new();
+        self.outbox.clear();
+        self.armed = false;
+        self.acks_pending = 0;
+    }
+
+    fn push(&mut self, src: BusAddr, type_id: u8, payload: Vec<u8>) {
+        let frame = RawFrame {
+            dest: BusAddr::App,
+            src,
+            type_id,
+            payload,
+        };
+        self.outbox.extend(frame.encode());
+    }
+
+    fn send(&mut self, src: BusAddr, msg: Message) {
+        self.outbox.extend(msg.encode(src, BusAddr::App).encode());
+    }
+
+    fn ack(&mut self, src: BusAddr, cmd_type: u8) {
+        let ack = ConfigAck {
+            bus_addr: src.as_byte(),
+            acked_cmd: cmd_type & 0x7F,
+        };
+        self.send(src, Message::ConfigAck(ack));
+    }
+
+    fn text(&mut self, src: BusAddr, text: &str) {
+        let text = Text {
+            text: text.to_string(),
+        };
+        self.send(src, Message::Text(text));
+    }
+
+    fn shot_text(&mut self, text: &str) {
+        let text = ShotText {
+            text: text.to_string(),
+        };
+        self.send(BusAddr::Avr, Message::ShotText(text));
+    }
+
+    fn handle(&mut self, frame: &RawFrame) {
+        if frame.src != BusAddr::App {
+            return;
+        }
+        // Malformed requests are dropped, as the real device does.
+        let Ok(cmd) = Command::decode(frame) else {
+            return;
+        };
+        match frame.dest {
+            BusAddr::Dsp => self.handle_dsp(cmd),
+            BusAddr::Avr => self.handle_avr(cmd, frame.type_id),
+            BusAddr::Pi => self.handle_pi(cmd, frame.type_id),
+            BusAddr::App => {}
+        }
+    }
+
+    fn handle_dsp(&mut self, cmd: Command) {
+        let dsp = BusAddr::Dsp;
+        match cmd {
+            Command::StatusPoll(_) => {
+                let status = self.dsp_status();
+                self.send(dsp, Message::DspStatus(status));
+            }
+            Command::DspQuery => {
+                let dsp_type = match self.generation {
+                    DeviceGen::MevoPlus => 0x80,
+                    DeviceGen::Gen2 => 0xC0,
+                    DeviceGen::Unknown(b) => b,
+                };
+                let resp = DspQueryResp {
+                    version: 0x02,
+                    dsp_type,
+                    pcb: 0x0E,
+                };
+                self.send(dsp, Message::DspQueryResp(resp));
+            }
+            Command::DevInfoReq => self.send(dsp, dev_info(["2.4.1", "012345", "REL"])),
+            Command::ProdInfoReq(req) => {
+                let text = match req.sub_query {
+                    0x00 => "RPI-3B+",
+                    0x08 => "IMX219",
+                    _ => "FS-MEVO-PLUS",
+                };
+                let resp = ProdInfoResp {
+                    text: text.to_string(),
+                };
+                self.send(dsp, Message::ProdInfoResp(resp));
+            }
+            Command::ConfigQuery => self.send(dsp, config_resp()),
+            _ => {}
+        }
+    }
+
+    fn handle_avr(&mut self, cmd: Command, type_id: u8) {
+        let avr = BusAddr::Avr;
+        match cmd {
+            Command::StatusPoll(_) => {
+                let status = self.avr_status();
+                self.send(avr, Message::AvrStatus(status));
+            }
+            Command::DevInfoReq => {
+                self.send(avr, dev_info(["1.9.3 MEVO+", "2024-01-12", "10:31:07"]));
+            }
+            Command::ParamReadReq(req) => {
+                let param_id = req.param_id;
+                let value = self.params.get(&param_id).cloned().unwrap_or(ParamData::Int24(0));
+                self.send(avr, Message::ParamValue(ParamValue { param_id, value }));
+            }
+            Command::ParamValue(pv) => {
+                self.params.insert(pv.param_id, pv.value);
+                self.ack(avr, type_id);
+            }
+            Command::ConfigQuery => self.send(avr, config_resp()),
+            Command::CalDataReq(_) => {
+                let resp = CalDataResp {
+                    payload: vec![0xAE; 175],
+                };
+                self.send(avr, Message::CalDataResp(resp));
+            }
+            Command::CalParamReq(_) => {
+                self.ack(avr, type_id);
+                let mut resp = vec![0u8; 242];
+                resp[0] = 0xF1;
+                resp[1..7].copy_from_slice(b"FS-CAL");
+                self.send(avr, Message::CalParamResp(CalParamResp { payload: resp }));
+            }
+            Command::AvrConfigQuery => {
+                let version = if self.generation == DeviceGen::Gen2 { 2 } else { 1 };
+                let mut resp = vec![0x10, version, 0xE1, 0x03, 0xE8, 0x00, 0x0C, 0x03, 0xE8, 0x00, 0x3D];
+                resp.resize(17, 0);
+                self.send(avr, Message::AvrConfigResp(AvrConfigResp { payload: resp }));
+            }
+            Command::TimeSync(mut ts) => {
+                // The AVR echoes the APP's epoch.
+                ts.tail = [0x00, 0x02];
+                self.send(avr, Message::TimeSync(ts));
+            }
+            Command::ModeSet(m) => {
+                self.mode = m.mode;
+                self.send(avr, Message::ModeSet(m));
+            }
+            Command::RadarCal(cal) => self.send(avr, Message::RadarCal(cal)),
+            Command::AvrConfigCmd(cmd) => {
+                self.ack(avr, type_id);
+                if cmd.arm {
+                    self.armed = true;
+                    self.text(BusAddr::Dsp, "System State 6");
+                    let armed = format!("ARMED DetectionMode={}", self.mode);
+                    self.text(avr, &armed);
+                } else if self.armed {
+                    self.armed = false;
+                    self.text(avr, "ARMED CANCELLED");
+                    self.text(avr, "ADC errors = 0");
+                    self.text(BusAddr::Dsp, "System State 5");
+                    self.send(avr, Message::ModeAck(ModeAck));
+                }
+            }
+            Command::ShotDataAck => {
+                self.send(avr, Message::TrackingStatus(tracking_status()));
+                if self.acks_pending > 0 {
+                    self.acks_pending -= 1;
+                    if self.acks_pending == 0 {
+                        // Shot-completion flow: ModeAck arrives before IDLE.
+                        self.text(BusAddr::Dsp, "System State 5");
+                        self.send(avr, Message::ModeAck(ModeAck));
+                        self.shot_text("IDLE");
+                    }
+                }
+            }
+            Command::ShotResultReq => {
+                if let Some(club) = self.last_club.clone() {
+                    self.send(avr, Message::ClubResult(club));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_pi(&mut self, cmd: Command, type_id: u8) {
+        let pi = BusAddr::Pi;
+        match cmd {
+            Command::StatusPoll(_) => {
+                let mut payload = vec![0x10, 0x01];
+                payload.resize(17, 0);
+                self.send(pi, Message::PiStatus(PiStatus { payload }));
+            }
+            Command::DevInfoReq => self.send(pi, dev_info(["BM17.04", "2026-01-20", "09:12:44"])),
+            Command::ParamReadReq(req) => {
+                let value = ParamData::Int24(0);
+                self.send(pi, Message::ParamValue(ParamValue { param_id: req.param_id, value }));
+            }
+            Command::ParamValue(_) | Command::CamState(_) => self.ack(pi, type_id),
+            Command::CamConfig(cfg) => {
+                self.cam_config = cfg;
+                self.ack(pi, type_id);
+            }
+            Command::CamConfigReq(_) => {
+                let cfg = self.cam_config.clone();
+                self.send(pi, Message::CamConfig(cfg));
+            }
+            Command::NetConfigReq(req) => {
+                // The SSID query returns empty text slots; the password
+                // query returns both.
+                let text = if req.query_password {
+                    format!("{}\0{}", self.ssid, self.password)
+                } else {
+                    String::new()
+                };
+                self.send(pi, Message::NetConfigResp(NetConfigResp { text }));
+            }
+            Command::WifiScanReq(req) => {
+                let payload = self
+                    .wifi_pages
+                    .get(usize::from(req.page))
+                    .cloned()
+                    .unwrap_or_default();
+                self.send(pi, Message::WifiScan(WifiScan { payload }));
+            }
+            _ => {}
+        }
+    }
+
+
+    // -- Payload builders ---------------------------------------------------
+
+    fn dsp_status(&self) -> DspStatus {
+        let state = if self.armed { 6 } else { 5 };
+        if self.generation != DeviceGen::MevoPlus {
+            let mut payload = vec![0x46, state];
+            payload.resize(71, 0);
+            return DspStatus::V46(DspStatus46 {
+                state,
+                version: 0x46,
+                payload,
+            });
+        }
+        DspStatus::V80(DspStatus80 {
+            state,
+            input_voltage_usb: 4900,
+            system_voltage: 3300,
+            battery_current: -350,
+            temperature_raw: (self.temperature * 100.0) as i16,
+            battery_voltage: 4012,
+            battery_voltage_2: 4010,
+            power_level: i16::from(self.battery_percent) << 8,
+            external_power: self.external_power,
+        })
+    }
+
+    fn avr_status(&self) -> AvrStatus {
+        AvrStatus {
+            version: 0x18,
+            state: u8::from(self.armed),
+            hw_id_hi: 0x01,
+            hw_id_lo: 0x07,
+            full_app_id: 0,
+            temperature: self.temperature,
+            tilt: self.tilt,
+            roll: self.roll,
+        }
+    }
+
+    /// Trigger through the pre-PROCESSED data. Returns the Unix epoch
+    /// reported in the trigger text.
+    fn push_trigger(&mut self, shot: &SimShot) -> u64 {
+        self.shot_count += 1;
+        self.armed = false;
+        let total = self.shot_count;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs_f64();
+        let epoch = (now + self.clock_offset).floor() as u64;
+        let avr = BusAddr::Avr;
+
+        self.shot_text(&format!("BALL TRIGGER: 12 ms back, at Epoch {epoch}"));
+        self.send(avr, Message::TrackingStatus(tracking_status()));
+        self.send(avr, Message::FlightResultV1(flight_result_v1(total, shot)));
+        self.send(avr, Message::PrcData(prc_data(shot)));
+        epoch
+    }
+
+    fn push_shot(&mut self, shot: &SimShot) {
+        let epoch = self.push_trigger(shot);
+        let total = self.shot_count;
+        let avr = BusAddr::Avr;
+        self.shot_text(&format!("Clubimpact at Epoch {epoch}"));
+        self.shot_text("PROCESSED");
+        // Main results land right behind PROCESSED, as observed live.
+        self.send(avr, Message::FlightResult(flight_result(total, shot)));
+        let club = club_result(shot);
+        self.last_club = Some(club.clone());
+        self.send(avr, Message::ClubResult(club));
+        self.send(avr, Message::SpeedProfile(speed_profile(shot)));
+        self.send(avr, Message::SpinResult(spin_result(shot)));
+        self.send(avr, Message::PrcData(prc_data(shot)));
+        self.send(avr, Message::ClubPrc(club_prc(shot)));
+        // The rest of the completion flow (IDLE) follows the APP's two
+        // ShotDataAcks.
+        self.acks_pending = 2;
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Static payloads
+// ---------------------------------------------------------------------------
+
+fn dev_info(slots: [&str; 3]) -> Message {
+    Message::DevInfoResp(DevInfoResp::from_slots(slots))
+}
+
+fn config_resp() -> Message {
+    Message::ConfigResp(ConfigResp {
+        params: std::array::from_fn(|i| i as i16 * 10),
+    })
+}
+
+fn tracking_status() -> TrackingStatus {
+    TrackingStatus {
+        state: 0x02,
+        flags: 0,
+        pre_trig_buf_start: 0,
+        club_impact_idx: 0xFF_FFFF,
+        trigger_idx: 0,
+        radar_cal1: 0,
+        radar_cal2: 0,
+        radar_cal_avr: 0,
+        processing_iteration: 0,
+        result_quality: 0,
+        detection_subtype: 0,
+        prc_tracking_count: 0,
+        radar_measurement: 0,
+        trigger_flags: 0,
+        event_counter: 0,
+        radar_baseline: 0,
+        track_measure: [0; 3],
+        track_measure4: 0,
+    }
+}
+
+fn flight_result_v1(total: i32, shot: &SimShot) -> FlightResultV1 {
+    FlightResultV1 {
+        total,
+        club_velocity: shot.club_speed,
+        ball_velocity: shot.ball_speed,
+        flight_time: 0.0,
+        distance: shot.carry_distance,
+        height: 0.0,
+        lateral: 0.0,
+        elevation: shot.launch_elevation,
+        azimuth: shot.launch_azimuth,
+        tracked_time: 0.0,
+        drag: 0.0,
+        backspin_rpm: shot.backspin_rpm,
+        sidespin_rpm: shot.sidespin_rpm,
+        acceleration: 0.0,
+        club_strike_direction: 0.0,
+        poly_scale: 1000,
+        poly_x: [0.0; 5],
+        poly_y: [0.0; 5],
+        poly_z: [0.0; 5],
+    }
+}
+
+fn flight_result(total: i32, shot: &SimShot) -> FlightResult {
+    FlightResult {
+        total,
+        track_time: 5.5,
+        start_position: [0.0; 3],
+        launch_speed: shot.ball_speed,
+        launch_azimuth: shot.launch_azimuth,
+        launch_elevation: shot.launch_elevation,
+        carry_distance: shot.carry_distance,
+        flight_time: 6.1,
+        max_height: 28.0,
+        landing_position: [0.0; 3],
+        backspin_rpm: shot.backspin_rpm,
+        sidespin_rpm: shot.sidespin_rpm,
+        riflespin_rpm: 0,
+        landing_spin_rpm: [0; 3],
+        landing_velocity: [0.0; 3],
+        total_distance: 0.0,
+        roll_distance: 0.0,
+        final_position: [0.0; 3],
+        clubhead_speed: shot.club_speed,
+        club_strike_direction: 0.0,
+        club_attack_angle: -3.5,
+        clubhead_speed_post: shot.club_speed * 0.8,
+        club_swing_plane_tilt: 0.0,
+        club_swing_plane_rotation: 0.0,
+        club_effective_loft: 20.0,
+        club_face_angle: 0.0,
+        poly_scale: 1000,
+        poly_x: [0.0; 5],
+        poly_y: [0.0; 5],
+        poly_z: [0.0; 5],
+    }
+}
+
+fn club_result(shot: &SimShot) -> ClubResult {
+    ClubResult {
+        num_club_prc_points: 24,
+        flags: 0,
+        pre_club_speed: shot.club_speed,
+        post_club_speed: shot.club_speed * 0.8,
+        strike_direction: 0.0,
+        attack_angle: -3.5,
+        face_angle: 0.0,
+        dynamic_loft: 20.0,
+        smash_factor: shot.ball_speed / shot.club_speed,
+        dispersion_correction: 0.0,
+        swing_plane_horizontal: 0.0,
+        swing_plane_vertical: 0.0,
+        club_azimuth: 0.0,
+        club_elevation: 0.0,
+        club_offset: 0.0,
+        club_height: 0.0,
+        poly_scale: 1000,
+        poly_coeffs: [[0.0; 3]; 12],
+        pre_impact_time: 0.0,
+        post_impact_time: 0.0,
+        club_to_ball_time: 0.0,
+    }
+}
+
+fn spin_result(shot: &SimShot) -> SpinResult {
+    let total = f64::from(shot.backspin_rpm).hypot(f64::from(shot.sidespin_rpm)) as i16;
+    SpinResult {
+        version: 0x89,
+        antenna_data: Default::default(),
+        pm_spin_raw: total,
+        pm_spin_final: total,
+        pm_spin_confidence: 90,
+        lift_spin: 0,
+        spin_validate_expected: 0,
+        spin_validate_low: 0,
+        spin_validate_high: 0,
+        spin_validate_scaling: 0,
+        spin_method: 0,
+        spin_flags: 0,
+        launch_spin: total,
+        am_spin: 0,
+        pm_spin: total,
+        spin_axis: shot.spin_axis,
+        aod_spin: 0,
+        pll_spin: 0,
+    }
+}
+
+fn speed_profile(shot: &SimShot) -> SpeedProfile {
+    SpeedProfile {
+        flags: 0x01,
+        num_pre: 4,
+        num_post: 2,
+        scale_factor: 100,
+        time_interval: 0.000_853,
+        speeds: [0.7, 0.85, 0.95, 1.0, 0.8, 0.75]
+            .iter()
+            .map(|f| (shot.club_speed * f * 100.0).round() / 100.0)
+            .collect(),
+    }
+}
+
+fn prc_data(shot: &SimShot) -> PrcData {
+    PrcData {
+        sequence: 0,
+        points: vec![PrcPoint {
+            index: 0,
+            peak: 0,
+            snr: 0,
+            buf_idx: 0,
+            flags: 0,
+            time: 0,
+            n: 0.0,
+            az: shot.launch_azimuth,
+            el: shot.launch_elevation,
+            vel: shot.ball_speed,
+            dist: 1.5,
+            sync_idx: 0,
+            sync_buf: 0,
+            az1: 0.0,
+            az2: 0.0,
+            az3: 0.0,
+            el1: 0.0,
+            el2: 0.0,
+            pk: [0.0; 6],
+        }],
+    }
+}
+
+fn club_prc(shot: &SimShot) -> ClubPrc {
+    ClubPrc {
+        points: vec![ClubPrcPoint {
+            index: 0,
+            buf_ofs: -400,
+            peak: 0,
+            snr: 0,
+            buf_idx: 0,
+            time: 0,
+            n: 0.0,
+            az: 0.0,
+            el: 0.0,
+            vel: shot.club_speed,
+            vel2: 0.0,
+            dist: 0.0,
+            f30: 0.0,
+            f33: 0.0,
+            version: 0,
+            f39: 0,
+            f42: 0,
+            f45: 0.0,
+            az1: 0.0,
+            az2: 0.0,
+            az3: 0.0,
+            el1: 0.0,
+            el2: 0.0,
+            pk: [0.0; 6],
+        }],
+    }
+}
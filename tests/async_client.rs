@@ -0,0 +1,162 @@
+//! Async client lifecycle against the simulator over a real TCP socket.
+
+#![cfg(feature = "async")]
+
+mod common;
+
+use std::time::Duration;
+
+use ironsight::sim::{SimDevice, SimServer, SimShot};
+use ironsight::{AsyncBinaryClient, BinaryEvent, ConnError, ReconnectPolicy};
+
+use common::wait_for_async;
+
+#[tokio::test]
+async fn lifecycle_over_tokio_tcp() -> Result<(), ConnError> {
+    let server = SimServer::spawn("127.0.0.1:0", SimDevice::new())?;
+    let device = server.device().clone();
+    let addr = server.local_addr();
+
+    // Runs on a spawned task to prove the client is `Send`.
+    let task = tokio::spawn(async move {
+        let mut client = AsyncBinaryClient::connect(addr).await?;
+        client.handshake();
+        wait_for_async(&mut client, |e| matches!(e, BinaryEvent::Handshake(_))).await?;
+        client.arm();
+        wait_for_async(&mut client, |e| matches!(e, BinaryEvent::Armed)).await?;
+        assert!(device.inject_shot(&SimShot::default()));
+        let ev = wait_for_async(&mut client, |e| matches!(e, BinaryEvent::ShotComplete(_))).await?;
+        assert!(client.is_armed());
+        Ok::<_, ConnError>(ev)
+    });
+    let BinaryEvent::ShotComplete(data) = task.await.unwrap()? else {
+        unreachable!()
+    };
+    assert!(data.flight.is_some());
+    assert_eq!(server.device().shot_count(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn keepalive_fires_without_traffic() -> Result<(), ConnError> {
+    let server = SimServer::spawn("127.0.0.1:0", SimDevice::new())?;
+    let mut client = AsyncBinaryClient::connect(server.local_addr()).await?;
+    client.set_keepalive_interval(Duration::from_millis(20));
+    client.handshake();
+    wait_for_async(&mut client, |e| matches!(e, BinaryEvent::Handshake(_))).await?;
+    client.arm();
+    wait_for_async(&mut client, |e| matches!(e, BinaryEvent::Armed)).await?;
+    // Nothing arrives unprompted; only the keepalive timer can wake us.
+    let ev = wait_for_async(&mut client, |e| matches!(e, BinaryEvent::Keepalive(_))).await?;
+    let BinaryEvent::Keepalive(status) = ev else {
+        unreachable!()
+    };
+    assert!(status.avr.is_some());
+    Ok(())
+}
+
+#[tokio::test]
+async fn reconnect_restores_session() -> Result<(), ConnError> {
+    let server = SimServer::spawn("127.0.0.1:0", SimDevice::new())?;
+    let device = server.device().clone();
+    let mut client = AsyncBinaryClient::connect(server.local_addr()).await?;
+    let policy = ReconnectPolicy {
+        initial_backoff: Duration::from_millis(10),
+        ..ReconnectPolicy::default()
+    };
+    client.enable_reconnect(policy)?;
+    client.handshake();
+    client.arm();
+    wait_for_async(&mut client, |e| matches!(e, BinaryEvent::Armed)).await?;
+
+    device.disconnect();
+    wait_for_async(&mut client, |e| {
+        matches!(e, BinaryEvent::Reconnecting { .. })
+    })
+    .await?;
+    wait_for_async(&mut client, |e| matches!(e, BinaryEvent::Reconnected)).await?;
+    wait_for_async(&mut client, |e| matches!(e, BinaryEvent::Armed)).await?;
+    assert!(device.is_armed());
+    assert!(device.inject_shot(&SimShot::default()));
+    wait_for_async(&mut client, |e| matches!(e, BinaryEvent::ShotComplete(_))).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn reconnect_gives_up_after_max_attempts() -> Result<(), ConnError> {
+    let server = SimServer::spawn("127.0.0.1:0", SimDevice::new())?;
+    let mut client = AsyncBinaryClient::connect(server.local_addr()).await?;
+    let policy = ReconnectPolicy {
+        initial_backoff: Duration::from_millis(10),
+        max_attempts: Some(2),
+        ..ReconnectPolicy::default()
+    };
+    client.set_reconnect(policy, || async {
+        Err::<tokio::net::TcpStream, _>(ConnError::Disconnected)
+    });
+    client.handshake();
+    wait_for_async(&mut client, |e| matches!(e, BinaryEvent::Handshake(_))).await?;
+
+    server.device().disconnect();
+    wait_for_async(&mut client, |e| {
+        matches!(e, BinaryEvent::Reconnecting { attempt: 2, .. })
+    })
+    .await?;
+    assert!(matches!(
+        client.next_event().await,
+        Err(ConnError::Disconnected)
+    ));
+    assert!(matches!(
+        client.next_event().await,
+        Err(ConnError::Disconnected)
+    ));
+    Ok(())
+}
+
+#[tokio::test]
+async fn closed_socket_is_disconnected() -> Result<(), ConnError> {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    let accept = tokio::spawn(async move { drop(listener.accept().await) });
+    let mut client = AsyncBinaryClient::connect(addr).await?;
+    accept.await.unwrap();
+    assert!(matches!(
+        client.next_event().await,
+        Err(ConnError::Disconnected)
+    ));
+    Ok(())
+}
+
+#[cfg(feature = "gvp")]
+#[tokio::test]
+async fn gvp_messages_in_arrival_order() -> Result<(), ironsight::gvp::GvpError> {
+    use ironsight::gvp::{AsyncGvpClient, GvpEvent};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    let camera = tokio::spawn(async move {
+        let (mut sock, _) = listener.accept().await?;
+        let mut req = [0u8; 256];
+        let n = sock.read(&mut req).await?;
+        assert!(String::from_utf8_lossy(&req[..n]).contains("CONFIG_REQUEST"));
+        // Two messages in one segment.
+        let status =
+            r#"{"bufferStatus":[{"bufferIndex":0,"status":"IDLE"}],"type":"STATUS","version":1}"#;
+        let log = r#"{"level":1,"message":"hello","type":"LOG","version":1}"#;
+        sock.write_all(format!("{status}\0{log}\0").as_bytes())
+            .await?;
+        Ok::<_, std::io::Error>(sock)
+    });
+
+    let mut client = AsyncGvpClient::connect(addr).await?;
+    client.query_config().await?;
+    assert!(matches!(client.next_event().await?, GvpEvent::Status(_)));
+    assert!(matches!(client.next_event().await?, GvpEvent::Log(_)));
+    drop(camera.await.unwrap()?);
+    assert!(matches!(
+        client.next_event().await,
+        Err(ironsight::gvp::GvpError::Disconnected)
+    ));
+    Ok(())
+}
@@ -0,0 +1,76 @@
+//! Helpers shared by the integration tests.
+
+// Each test crate uses a subset.
+#![allow(dead_code)]
+
+use std::io::{Read, Write};
+use std::time::{Duration, Instant};
+
+use ironsight::client::BinaryClient;
+use ironsight::{BinaryEvent, ConnError, Timed};
+
+/// How long any wait in a test may take before it fails.
+pub const DEADLINE: Duration = Duration::from_secs(10);
+
+/// Poll until `pred` matches an event, returning it.
+pub fn wait_for<S: Read + Write>(
+    client: &mut BinaryClient<S>,
+    pred: impl FnMut(&BinaryEvent) -> bool,
+) -> Result<BinaryEvent, ConnError> {
+    Ok(wait_for_timed(client, pred)?.event)
+}
+
+/// [`wait_for`], returning the event with its time.
+pub fn wait_for_timed<S: Read + Write>(
+    client: &mut BinaryClient<S>,
+    mut pred: impl FnMut(&BinaryEvent) -> bool,
+) -> Result<Timed<BinaryEvent>, ConnError> {
+    let start = Instant::now();
+    while start.elapsed() < DEADLINE {
+        match client.poll_timed()? {
+            Some(ev) if pred(&ev.event) => return Ok(ev),
+            Some(_) => {}
+            None => std::thread::sleep(Duration::from_millis(1)),
+        }
+    }
+    panic!("timed out waiting for event");
+}
+
+/// [`wait_for`] for the async client.
+#[cfg(feature = "async")]
+pub async fn wait_for_async<S>(
+    client: &mut ironsight::AsyncBinaryClient<S>,
+    mut pred: impl FnMut(&BinaryEvent) -> bool,
+) -> Result<BinaryEvent, ConnError>
+where
+    S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin,
+{
+    let wait = async {
+        loop {
+            let ev = client.next_event().await?;
+            if pred(&ev) {
+                return Ok(ev);
+            }
+        }
+    };
+    tokio::time::timeout(DEADLINE, wait)
+        .await
+        .expect("timed out waiting for event")
+}
+
+/// Poll until `pred` matches a `(device, event)` pair, returning it.
+#[cfg(feature = "manager")]
+pub fn wait_for_device(
+    devices: &mut ironsight::DeviceManager,
+    mut pred: impl FnMut(&ironsight::DeviceId, &BinaryEvent) -> bool,
+) -> Result<(ironsight::DeviceId, BinaryEvent), ironsight::manager::ManagerError> {
+    let start = Instant::now();
+    while start.elapsed() < DEADLINE {
+        if let Some((id, ev)) = devices.poll_timeout(DEADLINE - start.elapsed())?
+            && pred(&id, &ev)
+        {
+            return Ok((id, ev));
+        }
+    }
+    panic!("timed out waiting for event");
+}
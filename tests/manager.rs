@@ -0,0 +1,76 @@
+//! Several simulated devices driven by one `DeviceManager`.
+
+#![cfg(feature = "manager")]
+
+mod common;
+
+use std::time::{Duration, Instant};
+
+use ironsight::BinaryEvent;
+use ironsight::manager::{DeviceId, DeviceManager, ManagerError};
+use ironsight::sim::{SimDevice, SimServer, SimShot};
+
+use common::wait_for_device;
+
+#[test]
+fn events_are_tagged_with_their_device() -> Result<(), ManagerError> {
+    let bay1 = SimServer::spawn("127.0.0.1:0", SimDevice::new().with_network("BAY1", "pw"))?;
+    let bay2 = SimServer::spawn("127.0.0.1:0", SimDevice::new().with_network("BAY2", "pw"))?;
+
+    let mut devices = DeviceManager::new()?;
+    devices.connect("bay1", &bay1.local_addr())?.handshake();
+    devices.connect("bay2", &bay2.local_addr())?.handshake();
+    assert_eq!(devices.len(), 2);
+
+    let mut pending = vec!["bay1", "bay2"];
+    while !pending.is_empty() {
+        let (id, ev) = wait_for_device(&mut devices, |_, e| matches!(e, BinaryEvent::Handshake(_)))?;
+        let BinaryEvent::Handshake(h) = ev else {
+            unreachable!()
+        };
+        assert_eq!(h.pi.ssid, id.as_str().to_uppercase());
+        pending.retain(|p| *p != id.as_str());
+    }
+
+    for (_, client) in devices.clients_mut() {
+        client.arm();
+    }
+    let mut pending = vec!["bay1", "bay2"];
+    while !pending.is_empty() {
+        let (id, _) = wait_for_device(&mut devices, |_, e| matches!(e, BinaryEvent::Armed))?;
+        pending.retain(|p| *p != id.as_str());
+    }
+
+    assert!(bay2.device().inject_shot(&SimShot::default()));
+    let (id, _) = wait_for_device(&mut devices, |_, e| {
+        matches!(e, BinaryEvent::ShotComplete(_))
+    })?;
+    assert_eq!(id.as_str(), "bay2");
+    assert_eq!(bay1.device().shot_count(), 0);
+
+    assert!(devices.remove("bay2").is_some());
+    assert!(devices.client("bay2").is_none());
+    assert_eq!(devices.ids().collect::<Vec<_>>(), [&DeviceId::new("bay1")]);
+    Ok(())
+}
+
+#[test]
+fn poll_timeout_returns_none_when_idle() -> Result<(), ManagerError> {
+    let server = SimServer::spawn("127.0.0.1:0", SimDevice::new())?;
+    let mut devices = DeviceManager::new()?;
+    devices.connect("bay1", &server.local_addr())?;
+
+    let start = Instant::now();
+    assert!(devices.poll_timeout(Duration::from_millis(50))?.is_none());
+    assert!(start.elapsed() >= Duration::from_millis(50));
+    Ok(())
+}
+
+#[test]
+fn unknown_device() {
+    let mut devices = DeviceManager::new().unwrap();
+    assert!(matches!(
+        devices.enable_reconnect("nope", Default::default()),
+        Err(ManagerError::UnknownDevice(id)) if id == "nope"
+    ));
+}
@@ -0,0 +1,93 @@
+//! Record a simulated session to disk, then replay it into a fresh client
+//! and check the same events come out.
+
+mod common;
+
+use std::time::{Duration, Instant};
+
+use ironsight::client::BinaryClient;
+use ironsight::conn::BinaryConnection;
+use ironsight::protocol::TYPE_CONFIG_ACK;
+use ironsight::session::{self, Direction, ReplayStream, SessionRecorder};
+use ironsight::sim::{SimDevice, SimShot};
+use ironsight::{BinaryEvent, BusAddr, ConnError};
+
+use common::{DEADLINE, wait_for};
+
+/// Handshake, arm, and wait for one shot. Returns the carry distance.
+fn handshake_arm_shot<S: std::io::Read + std::io::Write>(
+    client: &mut BinaryClient<S>,
+    device: Option<&SimDevice>,
+) -> Result<f64, ConnError> {
+    client.handshake();
+    wait_for(client, |e| matches!(e, BinaryEvent::Handshake(_)))?;
+    client.arm();
+    wait_for(client, |e| matches!(e, BinaryEvent::Armed))?;
+    if let Some(device) = device {
+        assert!(device.inject_shot(&SimShot::default()));
+    }
+    let ev = wait_for(client, |e| matches!(e, BinaryEvent::ShotComplete(_)))?;
+    let BinaryEvent::ShotComplete(data) = ev else {
+        unreachable!()
+    };
+    Ok(data.flight.expect("flight result").carry_distance)
+}
+
+#[test]
+fn record_then_replay() -> Result<(), ConnError> {
+    let path = std::env::temp_dir().join(format!("ironsight-session-{}.ises", std::process::id()));
+
+    let device = SimDevice::new();
+    let mut conn = BinaryConnection::new(device.clone());
+    let recorder = SessionRecorder::create(&path)?;
+    recorder.attach(&mut conn);
+    let mut client = BinaryClient::new(conn);
+    let carry = handshake_arm_shot(&mut client, Some(&device))?;
+    recorder.flush()?;
+
+    let records = session::load(&path)?;
+    let _ = std::fs::remove_file(&path);
+    assert!(records.iter().any(|r| r.direction == Direction::Sent));
+    assert!(records.iter().any(|r| r.direction == Direction::Received));
+    let sent = records
+        .iter()
+        .filter(|r| r.direction == Direction::Sent)
+        .count();
+
+    let replay = ReplayStream::new(records).with_speed(f64::INFINITY);
+    let mut client = BinaryClient::new(BinaryConnection::new(replay.clone()));
+    let replayed = handshake_arm_shot(&mut client, None)?;
+    assert_eq!(replayed, carry);
+    assert!(replay.written().len() >= sent);
+    Ok(())
+}
+
+#[test]
+fn recorder_keeps_undecodable_frames() -> Result<(), ConnError> {
+    let path = std::env::temp_dir().join(format!("ironsight-bad-{}.ises", std::process::id()));
+
+    let device = SimDevice::new();
+    let mut conn = BinaryConnection::new(device.clone());
+    let recorder = SessionRecorder::create(&path)?;
+    recorder.attach(&mut conn);
+    // A truncated ConfigAck fails to decode.
+    device.inject(BusAddr::Avr, TYPE_CONFIG_ACK, vec![0x02]);
+    let start = Instant::now();
+    let err = loop {
+        assert!(start.elapsed() < DEADLINE, "frame never arrived");
+        match conn.recv() {
+            Err(e) => break e,
+            Ok(_) => std::thread::sleep(Duration::from_millis(1)),
+        }
+    };
+    assert!(matches!(err, ConnError::Wire(_)));
+    recorder.flush()?;
+
+    let records = session::load(&path)?;
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].direction, Direction::Received);
+    assert_eq!(records[0].bus, BusAddr::Avr);
+    assert_eq!(records[0].frame().unwrap().payload, [0x02]);
+    Ok(())
+}
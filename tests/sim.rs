@@ -0,0 +1,935 @@
+//! End-to-end client lifecycle against the in-process simulator:
+//! handshake → configure → arm → shot → re-arm, over both the in-memory
+//! stream and a real TCP socket.
+
+mod common;
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use ironsight::alert::{Alert, AlertMonitor};
+use ironsight::client::{BinaryClient, ReconnectPolicy, ShotAbortReason};
+use ironsight::conn::BinaryConnection;
+use ironsight::discover::{DiscoverOptions, discover, probe};
+use ironsight::epoch::EpochMapPolicy;
+use ironsight::level::LevelAssist;
+use ironsight::protocol::camera::CamConfig;
+use ironsight::protocol::debug::Severity;
+use ironsight::protocol::handshake::{DeviceGen, FirmwareVersion, NetConfigResp};
+use ironsight::protocol::notice::DeviceNotice;
+use ironsight::protocol::{
+    Command, Message, TYPE_CONFIG_NACK, TYPE_DSP_DEBUG, TYPE_NET_CONFIG, TYPE_SHOT_TEXT,
+};
+use ironsight::protocol::config::{
+    DetectionMode, MODE_CHIPPING, MODE_OUTDOOR, ParamData, ParamValue, RadarCal,
+};
+use ironsight::seq::{AvrSettings, DRAIN_TIMEOUT, IdentifySequencer, WIFI_SCAN_PAGES, drive};
+use ironsight::sim::{SimDevice, SimServer, SimShot};
+use ironsight::{BinaryEvent, BusAddr, ClientState, ConnError, Connection, RawFrame, Timestamp};
+
+use common::{DEADLINE, wait_for, wait_for_timed};
+
+fn settings() -> AvrSettings {
+    AvrSettings {
+        mode: MODE_OUTDOOR,
+        params: vec![
+            ParamValue { param_id: 0x06, value: ParamData::Int24(0) },
+            ParamValue { param_id: 0x0F, value: ParamData::Float40(1.0) },
+            ParamValue { param_id: 0x26, value: ParamData::Float40(0.0381) },
+        ],
+        radar_cal: Some(RadarCal { range_mm: 2743, height_mm: 0 }),
+    }
+}
+
+fn run_lifecycle<S: std::io::Read + std::io::Write>(
+    client: &mut BinaryClient<S>,
+    device: &SimDevice,
+) -> Result<(), ConnError> {
+    client.handshake();
+    let ev = wait_for(client, |e| matches!(e, BinaryEvent::Handshake(_)))?;
+    let BinaryEvent::Handshake(h) = ev else { unreachable!() };
+    assert_eq!(h.pi.ssid, device.ssid());
+    assert_eq!(h.dsp.hw_info.device_gen(), device.generation());
+
+    client.configure_avr(settings());
+    wait_for(client, |e| matches!(e, BinaryEvent::Configured))?;
+    client.configure_cam(CamConfig::standard_preset());
+    wait_for(client, |e| matches!(e, BinaryEvent::Configured))?;
+    assert!(matches!(device.param(0x0F), Some(ParamData::Float40(v)) if v == 1.0));
+
+    client.arm();
+    wait_for(client, |e| matches!(e, BinaryEvent::Armed))?;
+    assert!(device.is_armed());
+
+    let shot = SimShot::default();
+    assert!(device.inject_shot(&shot));
+    wait_for(client, |e| matches!(e, BinaryEvent::Trigger))?;
+    let ev = wait_for(client, |e| matches!(e, BinaryEvent::ShotComplete(_)))?;
+    let BinaryEvent::ShotComplete(data) = ev else { unreachable!() };
+    let flight = data.flight.expect("flight result");
+    assert!((flight.launch_speed - shot.ball_speed).abs() < 0.01);
+    assert!((flight.carry_distance - shot.carry_distance).abs() < 0.01);
+    assert_eq!(flight.backspin_rpm, shot.backspin_rpm);
+    assert!(data.club.is_some());
+    assert!(data.spin.is_some());
+
+    // The shot sequencer re-arms the device.
+    assert!(device.is_armed());
+    assert!(client.is_armed());
+    assert_eq!(device.shot_count(), 1);
+    Ok(())
+}
+
+#[test]
+fn lifecycle_in_process() -> Result<(), ConnError> {
+    let device = SimDevice::new();
+    let mut client = BinaryClient::new(BinaryConnection::new(device.clone()));
+    run_lifecycle(&mut client, &device)
+}
+
+#[test]
+fn lifecycle_over_tcp() -> Result<(), ConnError> {
+    let server = SimServer::spawn("127.0.0.1:0", SimDevice::new()).unwrap();
+    let conn = Connection::connect(server.local_addr())?;
+    let mut client = BinaryClient::from_tcp(conn)?;
+    run_lifecycle(&mut client, server.device())
+}
+
+#[test]
+fn shot_ignored_while_disarmed() {
+    let device = SimDevice::new();
+    assert!(!device.inject_shot(&SimShot::default()));
+    assert_eq!(device.shot_count(), 0);
+}
+
+#[test]
+fn disconnect_surfaces_as_error() {
+    let device = SimDevice::new();
+    let mut client = BinaryClient::new(BinaryConnection::new(device.clone()));
+    device.disconnect();
+    assert!(matches!(client.poll(), Err(ConnError::Disconnected)));
+}
+
+#[test]
+fn poll_timeout_blocks_until_event_or_timeout() -> Result<(), ConnError> {
+    let server = SimServer::spawn("127.0.0.1:0", SimDevice::new()).unwrap();
+    let conn = Connection::connect(server.local_addr())?;
+    let mut client = BinaryClient::from_tcp(conn)?;
+
+    // Nothing queued and no timers: returns None after the full timeout.
+    let start = Instant::now();
+    assert!(client.poll_timeout(Duration::from_millis(50))?.is_none());
+    assert!(start.elapsed() >= Duration::from_millis(50));
+    // The socket is still non-blocking afterwards.
+    let start = Instant::now();
+    assert!(client.poll()?.is_none());
+    assert!(start.elapsed() < Duration::from_millis(50));
+
+    client.handshake();
+    let start = Instant::now();
+    while start.elapsed() < DEADLINE {
+        if let Some(BinaryEvent::Handshake(_)) = client.poll_timeout(DEADLINE)? {
+            break;
+        }
+    }
+    client.arm();
+    while start.elapsed() < DEADLINE {
+        if let Some(BinaryEvent::Armed) = client.poll_timeout(DEADLINE)? {
+            break;
+        }
+    }
+
+    // Idle and armed: the keepalive timer wakes the wait, not the timeout.
+    client.set_keepalive_interval(Duration::from_millis(100));
+    let start = Instant::now();
+    loop {
+        match client.poll_timeout(DEADLINE)? {
+            Some(BinaryEvent::Keepalive(_)) => break,
+            Some(_) => {}
+            None => panic!("keepalive never fired"),
+        }
+    }
+    assert!(start.elapsed() < Duration::from_secs(2));
+
+    assert!(server.device().inject_shot(&SimShot::default()));
+    loop {
+        match client.poll_timeout(DEADLINE)? {
+            Some(BinaryEvent::ShotComplete(_)) => break,
+            Some(_) => {}
+            None => panic!("shot never completed"),
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn reconnect_restores_session() -> Result<(), ConnError> {
+    let device = SimDevice::new();
+    let mut client = BinaryClient::new(BinaryConnection::new(device.clone()));
+    let replacement = SimDevice::new();
+    let next = replacement.clone();
+    let policy = ReconnectPolicy {
+        initial_backoff: Duration::from_millis(10),
+        ..ReconnectPolicy::default()
+    };
+    client.set_reconnect(policy, move || Ok(next.clone()));
+
+    client.handshake();
+    client.configure_avr(settings());
+    client.arm();
+    wait_for(&mut client, |e| matches!(e, BinaryEvent::Armed))?;
+
+    device.disconnect();
+    let ev = wait_for(&mut client, |e| matches!(e, BinaryEvent::Reconnecting { .. }))?;
+    assert!(matches!(ev, BinaryEvent::Reconnecting { attempt: 1, .. }));
+    assert!(client.is_reconnecting());
+    wait_for(&mut client, |e| matches!(e, BinaryEvent::Reconnected))?;
+    wait_for(&mut client, |e| matches!(e, BinaryEvent::Handshake(_)))?;
+    wait_for(&mut client, |e| matches!(e, BinaryEvent::Armed))?;
+
+    // The fresh device got the replayed config and is armed.
+    assert!(matches!(replacement.param(0x0F), Some(ParamData::Float40(v)) if v == 1.0));
+    assert!(replacement.is_armed());
+    assert!(replacement.inject_shot(&SimShot::default()));
+    wait_for(&mut client, |e| matches!(e, BinaryEvent::ShotComplete(_)))?;
+    Ok(())
+}
+
+#[test]
+fn reconnect_gives_up_after_max_attempts() {
+    let device = SimDevice::new();
+    let mut client = BinaryClient::new(BinaryConnection::new(device.clone()));
+    let policy = ReconnectPolicy {
+        initial_backoff: Duration::from_millis(1),
+        max_attempts: Some(2),
+        ..ReconnectPolicy::default()
+    };
+    client.set_reconnect(policy, || Err(ConnError::Disconnected));
+    device.disconnect();
+
+    let mut attempts = Vec::new();
+    let start = Instant::now();
+    let err = loop {
+        assert!(start.elapsed() < DEADLINE, "never gave up");
+        match client.poll() {
+            Ok(Some(BinaryEvent::Reconnecting { attempt, .. })) => attempts.push(attempt),
+            Ok(_) => std::thread::sleep(Duration::from_millis(1)),
+            Err(e) => break e,
+        }
+    };
+    assert_eq!(attempts, [1, 2]);
+    assert!(matches!(err, ConnError::Disconnected));
+    assert!(!client.is_reconnecting());
+
+    // Giving up is terminal: no new cycle starts on later polls.
+    for _ in 0..5 {
+        assert!(matches!(client.poll(), Err(ConnError::Disconnected)));
+    }
+    assert_eq!(client.state(), ClientState::Disconnected);
+}
+
+#[test]
+fn reconnect_runs_dropped_requests_after_replay() -> Result<(), ConnError> {
+    let device = SimDevice::new();
+    let mut client = BinaryClient::new(BinaryConnection::new(device.clone()));
+    let replacement = SimDevice::new().with_wifi_scan_pages(vec![vec![0x01, 0x02]]);
+    let next = replacement.clone();
+    let policy = ReconnectPolicy {
+        initial_backoff: Duration::from_millis(10),
+        ..ReconnectPolicy::default()
+    };
+    client.set_reconnect(policy, move || Ok(next.clone()));
+
+    client.handshake();
+    client.configure_avr(settings());
+    wait_for(&mut client, |e| matches!(e, BinaryEvent::Configured))?;
+
+    device.disconnect();
+    client.read_params(&[0x0F], BusAddr::Avr);
+    client.scan_wifi();
+    wait_for(&mut client, |e| matches!(e, BinaryEvent::Reconnected))?;
+    wait_for(&mut client, |e| matches!(e, BinaryEvent::Configured))?;
+    let ev = wait_for(&mut client, |e| matches!(e, BinaryEvent::ParamsRead(_)))?;
+    assert!(matches!(&ev, BinaryEvent::ParamsRead(v) if v.contains_key(&0x0F)));
+    let ev = wait_for(&mut client, |e| matches!(e, BinaryEvent::WifiScan(_)))?;
+    assert!(matches!(ev, BinaryEvent::WifiScan(p) if p[0].payload == [0x01, 0x02]));
+    Ok(())
+}
+
+#[test]
+fn disarm_and_state_changes() -> Result<(), ConnError> {
+    let device = SimDevice::new();
+    let mut client = BinaryClient::new(BinaryConnection::new(device.clone()));
+    let mut states = Vec::new();
+    let mut track = |e: &BinaryEvent| {
+        if let BinaryEvent::StateChanged(s) = e {
+            states.push(*s);
+        }
+    };
+    assert_eq!(client.state(), ClientState::Disconnected);
+
+    client.handshake();
+    wait_for(&mut client, |e| {
+        track(e);
+        matches!(e, BinaryEvent::Handshake(_))
+    })?;
+    client.arm();
+    wait_for(&mut client, |e| {
+        track(e);
+        matches!(e, BinaryEvent::StateChanged(ClientState::Armed))
+    })?;
+    assert!(device.inject_shot(&SimShot::default()));
+    wait_for(&mut client, |e| {
+        track(e);
+        matches!(e, BinaryEvent::StateChanged(ClientState::Armed))
+    })?;
+    client.disarm();
+    wait_for(&mut client, |e| {
+        track(e);
+        matches!(e, BinaryEvent::StateChanged(ClientState::Idle))
+    })?;
+
+    assert_eq!(
+        states,
+        [
+            ClientState::Handshaking,
+            ClientState::Idle,
+            ClientState::Configuring,
+            ClientState::Armed,
+            ClientState::ShotInProgress,
+            ClientState::Armed,
+            ClientState::Configuring,
+            ClientState::Idle,
+        ]
+    );
+    assert!(!device.is_armed());
+    assert!(!client.is_armed());
+    Ok(())
+}
+
+#[test]
+fn device_text_becomes_notices() -> Result<(), ConnError> {
+    let device = SimDevice::new();
+    let mut client = BinaryClient::new(BinaryConnection::new(device.clone()));
+    let mut notices = Vec::new();
+    let mut passed_through = 0;
+    let mut last_notice = None;
+    let mut track = |e: &BinaryEvent| {
+        // Unconsumed text still arrives as Message, right after its Notice.
+        match e {
+            BinaryEvent::Notice { src, notice } => {
+                notices.push(notice.clone());
+                last_notice = Some(*src);
+                return;
+            }
+            BinaryEvent::Message(env)
+                if matches!(env.message, Message::Text(_) | Message::ShotText(_)) =>
+            {
+                assert_eq!(last_notice, Some(env.src));
+                passed_through += 1;
+            }
+            _ => {}
+        }
+        last_notice = None;
+    };
+
+    client.handshake();
+    client.configure_avr(settings());
+    client.arm();
+    wait_for(&mut client, |e| {
+        track(e);
+        matches!(e, BinaryEvent::Armed)
+    })?;
+    assert!(device.inject_shot(&SimShot::default()));
+    wait_for(&mut client, |e| {
+        track(e);
+        matches!(e, BinaryEvent::ShotComplete(_))
+    })?;
+    client.disarm();
+    wait_for(&mut client, |e| {
+        track(e);
+        matches!(e, BinaryEvent::Disarmed)
+    })?;
+
+    assert!(passed_through > 0);
+    let armed = DeviceNotice::Armed { detection_mode: Some(MODE_OUTDOOR) };
+    for expected in [
+        DeviceNotice::SystemState(6),
+        armed,
+        DeviceNotice::Processed,
+        DeviceNotice::Idle,
+        DeviceNotice::ArmCancelled,
+        DeviceNotice::AdcErrors(0),
+        DeviceNotice::SystemState(5),
+    ] {
+        assert!(notices.contains(&expected), "missing {expected:?} in {notices:?}");
+    }
+    assert!(notices.iter().any(|n| matches!(
+        n,
+        DeviceNotice::BallTrigger { ms_back: Some(12), epoch: Some(_) }
+    )));
+    Ok(())
+}
+
+#[test]
+fn recv_returns_frames_from_one_read_in_arrival_order() -> Result<(), ConnError> {
+    let device = SimDevice::new();
+    for text in ["ONE", "TWO", "THREE"] {
+        device.inject(BusAddr::Avr, TYPE_SHOT_TEXT, format!("{text}\0").into_bytes());
+    }
+    let mut conn = BinaryConnection::new(device);
+    let mut texts = Vec::new();
+    while let Some(env) = conn.recv()? {
+        let Message::ShotText(st) = env.message else { panic!("{:?}", env.message) };
+        texts.push(st.text);
+    }
+    assert_eq!(texts, ["ONE", "TWO", "THREE"]);
+    Ok(())
+}
+
+#[test]
+fn dsp_debug_frames_become_lines() -> Result<(), ConnError> {
+    let device = SimDevice::new();
+    let mut client = BinaryClient::new(BinaryConnection::new(device.clone()));
+    device.inject(BusAddr::Dsp, TYPE_DSP_DEBUG, b"\x1b[31mTrack fa".to_vec());
+    device.inject(BusAddr::Dsp, TYPE_DSP_DEBUG, b"il\x1b[0m\r\nv=1\r\nv=2\r\n".to_vec());
+
+    let mut lines = Vec::new();
+    wait_for(&mut client, |e| {
+        if let BinaryEvent::DspDebug(line) = e {
+            lines.push(line.clone());
+        }
+        lines.len() == 3
+    })?;
+    let texts: Vec<&str> = lines.iter().map(|l| l.text.as_str()).collect();
+    assert_eq!(texts, ["Track fail", "v=1", "v=2"]);
+    assert_eq!(lines[0].severity, Severity::Error);
+    assert_eq!(lines[1].severity, Severity::Info);
+    Ok(())
+}
+
+#[test]
+fn keepalive_records_link_health() -> Result<(), ConnError> {
+    let device = SimDevice::new();
+    let mut client = BinaryClient::new(BinaryConnection::new(device.clone()));
+    client.set_keepalive_interval(Duration::from_millis(10));
+    client.handshake();
+    client.arm();
+    wait_for(&mut client, |e| matches!(e, BinaryEvent::Armed))?;
+    wait_for(&mut client, |e| matches!(e, BinaryEvent::Keepalive(_)))?;
+    let ev = wait_for(&mut client, |e| matches!(e, BinaryEvent::Keepalive(_)))?;
+    let BinaryEvent::Keepalive(status) = ev else { unreachable!() };
+
+    let health = &status.health;
+    for node in [&health.dsp, &health.avr, &health.pi] {
+        assert_eq!((node.responses, node.missed), (2, 0));
+        assert!(node.last_rtt.is_some() && node.avg_rtt <= node.max_rtt);
+    }
+    assert!(health.messages_received > 0);
+    assert!(health.messages_per_sec > 0.0);
+    assert_eq!(client.health().dsp, health.dsp);
+    Ok(())
+}
+
+#[test]
+fn keepalive_status_raises_alerts() -> Result<(), ConnError> {
+    let device = SimDevice::new();
+    let mut client = BinaryClient::new(BinaryConnection::new(device.clone()));
+    client.set_keepalive_interval(Duration::from_millis(10));
+    client.set_alert_monitor(AlertMonitor::default());
+    client.handshake();
+    client.arm();
+    wait_for(&mut client, |e| matches!(e, BinaryEvent::Armed))?;
+
+    device.set_battery(9, false);
+    device.set_level(0.5, 6.0);
+    let mut alerts = Vec::new();
+    wait_for(&mut client, |e| {
+        if let BinaryEvent::Alert(a) = e {
+            alerts.push(a.clone());
+        }
+        alerts.len() == 2
+    })?;
+    assert_eq!(
+        alerts,
+        [
+            Alert::BatteryCritical { percent: 9 },
+            Alert::OutOfLevel { tilt: 0.5, roll: 6.0 },
+        ]
+    );
+
+    device.set_battery(9, true);
+    let ev = wait_for(&mut client, |e| matches!(e, BinaryEvent::Alert(_)))?;
+    assert!(matches!(ev, BinaryEvent::Alert(Alert::PowerConnected)));
+    Ok(())
+}
+
+#[test]
+fn level_assist_streams_while_disarmed() -> Result<(), ConnError> {
+    let device = SimDevice::new();
+    let mut client = BinaryClient::new(BinaryConnection::new(device.clone()));
+    device.set_level(4.0, -2.0);
+    client.start_level_assist(LevelAssist {
+        interval: Duration::from_millis(5),
+        smoothing: 0.5,
+        tolerance_deg: 1.0,
+    });
+    client.handshake();
+
+    let ev = wait_for(&mut client, |e| matches!(e, BinaryEvent::LevelReading(_)))?;
+    let BinaryEvent::LevelReading(first) = ev else { unreachable!() };
+    assert_eq!((first.tilt, first.roll, first.within_tolerance), (4.0, -2.0, false));
+
+    device.set_level(0.0, 0.0);
+    wait_for(&mut client, |e| {
+        matches!(e, BinaryEvent::LevelReading(r) if r.within_tolerance)
+    })?;
+
+    // Arming pauses level assist.
+    client.arm();
+    wait_for(&mut client, |e| matches!(e, BinaryEvent::Armed))?;
+    let start = Instant::now();
+    while start.elapsed() < Duration::from_millis(50) {
+        let ev = client.poll()?;
+        assert!(!matches!(ev, Some(BinaryEvent::LevelReading(_))));
+    }
+    Ok(())
+}
+
+#[test]
+fn shot_data_carries_receive_times() -> Result<(), ConnError> {
+    let device = SimDevice::new();
+    let mut client = BinaryClient::new(BinaryConnection::new(device.clone()));
+    client.handshake();
+    client.arm();
+    wait_for(&mut client, |e| matches!(e, BinaryEvent::Armed))?;
+
+    assert!(device.inject_shot(&SimShot::default()));
+    let before = Timestamp::now();
+    let trigger_time = wait_for_timed(&mut client, |e| matches!(e, BinaryEvent::Trigger))?.at;
+    assert!(trigger_time.instant >= before.instant);
+    let ev = wait_for_timed(&mut client, |e| matches!(e, BinaryEvent::ShotComplete(_)))?;
+    let BinaryEvent::ShotComplete(data) = ev.event else { unreachable!() };
+
+    let trigger = data.trigger_at.expect("trigger time");
+    let processed = data.processed_at.expect("processed time");
+    let flight = data.flight_at.expect("flight time");
+    let completed = data.completed_at.expect("completed time");
+    assert_eq!(trigger, trigger_time);
+    assert!(trigger.instant <= processed.instant);
+    assert!(processed.instant <= flight.instant);
+    assert!(flight.instant <= completed.instant);
+    assert_eq!(ev.at, completed);
+    assert_eq!(data.flight_latency(), Some(flight.duration_since(trigger)));
+    Ok(())
+}
+
+#[test]
+fn epoch_map_bounds_device_offset() -> Result<(), ConnError> {
+    let device = SimDevice::new();
+    let mut client = BinaryClient::new(BinaryConnection::new(device.clone()));
+    device.set_clock_offset(100.0);
+    client.enable_epoch_map(EpochMapPolicy::default());
+    client.handshake();
+    wait_for(&mut client, |e| matches!(e, BinaryEvent::Handshake(_)))?;
+    client.arm();
+    wait_for(&mut client, |e| matches!(e, BinaryEvent::Armed))?;
+
+    // The trigger epoch is the only device clock reading; TimeSync
+    // replies just echo the host epoch.
+    assert!(device.inject_shot(&SimShot::default()));
+    let ev = wait_for_timed(&mut client, |e| matches!(e, BinaryEvent::EpochOffset(_)))?;
+    let BinaryEvent::EpochOffset(est) = ev.event else { unreachable!() };
+    assert!(est.uncertainty < 1.0, "{est:?}");
+    assert!((est.offset - 100.0).abs() <= est.uncertainty + 0.01, "{est:?}");
+
+    let map = client.epoch_map().expect("epoch map enabled");
+    let device_now = map.to_device(ev.at).unwrap();
+    let (a, b) = (map.to_host(device_now).unwrap(), ev.at);
+    assert!(a.instant.max(b.instant) - a.instant.min(b.instant) < Duration::from_millis(1));
+    Ok(())
+}
+
+#[test]
+fn trigger_without_processed_aborts_and_rearms() -> Result<(), ConnError> {
+    let device = SimDevice::new();
+    let mut client = BinaryClient::new(BinaryConnection::new(device.clone()));
+    client.set_trigger_timeout(Duration::from_millis(50));
+    client.handshake();
+    client.arm();
+    wait_for(&mut client, |e| matches!(e, BinaryEvent::Armed))?;
+
+    let shot = SimShot::default();
+    assert!(device.inject_misread(&shot));
+    wait_for(&mut client, |e| matches!(e, BinaryEvent::Trigger))?;
+    assert_eq!(client.state(), ClientState::ShotInProgress);
+    let ev = wait_for(&mut client, |e| matches!(e, BinaryEvent::ShotAborted { .. }))?;
+    let BinaryEvent::ShotAborted { reason, partial } = ev else { unreachable!() };
+    assert_eq!(reason, ShotAbortReason::Timeout);
+    let partial = partial.expect("early flight estimate");
+    assert!((partial.ball_velocity - shot.ball_speed).abs() < 0.01);
+
+    // Re-armed, and the next shot goes through normally.
+    wait_for(&mut client, |e| matches!(e, BinaryEvent::Armed))?;
+    assert!(device.is_armed());
+    assert!(device.inject_shot(&shot));
+    wait_for(&mut client, |e| matches!(e, BinaryEvent::ShotComplete(_)))?;
+    Ok(())
+}
+
+#[test]
+fn trigger_timeout_without_rearm() -> Result<(), ConnError> {
+    let device = SimDevice::new();
+    let mut client = BinaryClient::new(BinaryConnection::new(device.clone()));
+    client.set_trigger_timeout(Duration::from_millis(20));
+    client.set_rearm_after_abort(false);
+    client.handshake();
+    client.arm();
+    wait_for(&mut client, |e| matches!(e, BinaryEvent::Armed))?;
+
+    assert!(device.inject_misread(&SimShot::default()));
+    wait_for(&mut client, |e| matches!(e, BinaryEvent::ShotAborted { .. }))?;
+    wait_for(&mut client, |e| matches!(e, BinaryEvent::StateChanged(ClientState::Idle)))?;
+    assert!(!device.is_armed());
+    Ok(())
+}
+
+#[test]
+fn connection_lost_mid_shot_aborts() -> Result<(), ConnError> {
+    let device = SimDevice::new();
+    let mut client = BinaryClient::new(BinaryConnection::new(device.clone()));
+    let next = device.clone();
+    let policy = ReconnectPolicy {
+        initial_backoff: Duration::from_millis(10),
+        ..ReconnectPolicy::default()
+    };
+    client.set_reconnect(policy, move || {
+        next.reset();
+        Ok(next.clone())
+    });
+    client.handshake();
+    client.arm();
+    wait_for(&mut client, |e| matches!(e, BinaryEvent::Armed))?;
+
+    assert!(device.inject_misread(&SimShot::default()));
+    wait_for(&mut client, |e| matches!(e, BinaryEvent::Trigger))?;
+    device.disconnect();
+    let ev = wait_for(&mut client, |e| matches!(e, BinaryEvent::ShotAborted { .. }))?;
+    assert!(matches!(
+        ev,
+        BinaryEvent::ShotAborted { reason: ShotAbortReason::ConnectionLost, .. }
+    ));
+    wait_for(&mut client, |e| matches!(e, BinaryEvent::Armed))?;
+    Ok(())
+}
+
+#[test]
+fn read_params_returns_written_values() -> Result<(), ConnError> {
+    let device = SimDevice::new();
+    let mut client = BinaryClient::new(BinaryConnection::new(device.clone()));
+    client.handshake();
+    client.configure_avr(settings());
+    wait_for(&mut client, |e| matches!(e, BinaryEvent::Configured))?;
+
+    client.read_params(&[0x06, 0x0F, 0x26], BusAddr::Avr);
+    let ev = wait_for(&mut client, |e| matches!(e, BinaryEvent::ParamsRead(_)))?;
+    let BinaryEvent::ParamsRead(values) = ev else { unreachable!() };
+    assert_eq!(values.len(), 3);
+    assert!(matches!(values[&0x06], ParamData::Int24(0)));
+    assert!(matches!(values[&0x0F], ParamData::Float40(v) if v == 1.0));
+    assert!(matches!(values[&0x26], ParamData::Float40(v) if (v - 0.0381).abs() < 1e-6));
+
+    client.read_params(&[], BusAddr::Avr);
+    let ev = wait_for(&mut client, |e| matches!(e, BinaryEvent::ParamsRead(_)))?;
+    assert!(matches!(ev, BinaryEvent::ParamsRead(v) if v.is_empty()));
+    Ok(())
+}
+
+/// Sim stream that drops ModeSet commands for one mode, either silently
+/// or answering with ConfigNack.
+struct DropModeSet {
+    inner: SimDevice,
+    mode: u8,
+    nack: bool,
+}
+
+impl std::io::Read for DropModeSet {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+impl std::io::Write for DropModeSet {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if let Ok(frame) = RawFrame::parse(buf)
+            && frame.type_id == 0xA5
+            && frame.payload.get(2) == Some(&self.mode)
+        {
+            if self.nack {
+                self.inner.inject(BusAddr::Avr, TYPE_CONFIG_NACK, vec![0x02, 0x30, 0x25]);
+            }
+            return Ok(buf.len());
+        }
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[test]
+fn hooks_need_not_be_send() -> Result<(), ConnError> {
+    let mut conn = BinaryConnection::new(SimDevice::new());
+    let sent = Rc::new(RefCell::new(Vec::new()));
+    let log = Rc::clone(&sent);
+    conn.set_on_send(move |cmd, _| log.borrow_mut().push(cmd.clone()));
+    let received = Rc::new(Cell::new(0));
+    let count = Rc::clone(&received);
+    conn.set_on_recv(move |_| count.set(count.get() + 1));
+    let mut client = BinaryClient::new(conn);
+
+    client.handshake();
+    wait_for(&mut client, |e| matches!(e, BinaryEvent::Handshake(_)))?;
+    assert!(!sent.borrow().is_empty());
+    assert!(received.get() > 0);
+    Ok(())
+}
+
+#[test]
+fn apply_settings_writes_only_changes() -> Result<(), ConnError> {
+    let device = SimDevice::new();
+    let mut conn = BinaryConnection::new(device.clone());
+    let sent = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let log = sent.clone();
+    conn.set_on_send(move |cmd, _| log.lock().unwrap().push(cmd.clone()));
+    let mut client = BinaryClient::new(conn);
+
+    client.handshake();
+    client.configure_avr(settings());
+    client.arm();
+    wait_for(&mut client, |e| matches!(e, BinaryEvent::Armed))?;
+    sent.lock().unwrap().clear();
+
+    client.set_mode(DetectionMode::Chipping);
+    wait_for(&mut client, |e| matches!(e, BinaryEvent::SettingsApplied))?;
+    assert_eq!(device.mode(), MODE_CHIPPING);
+    assert!(device.is_armed());
+    assert!(client.is_armed());
+    let sent = sent.lock().unwrap();
+    assert!(!sent.iter().any(|c| matches!(c, Command::ParamValue(_) | Command::RadarCal(_))));
+    assert!(sent.iter().any(|c| matches!(c, Command::ModeSet(m) if m.mode == MODE_CHIPPING)));
+    Ok(())
+}
+
+#[test]
+fn apply_settings_rolls_back_on_failure() -> Result<(), ConnError> {
+    let device = SimDevice::new();
+    let stream = DropModeSet { inner: device.clone(), mode: MODE_CHIPPING, nack: false };
+    let mut client = BinaryClient::new(BinaryConnection::new(stream));
+    client.handshake();
+    client.apply_settings(settings());
+    wait_for(&mut client, |e| matches!(e, BinaryEvent::SettingsApplied))?;
+
+    client.set_operation_timeout(Duration::from_millis(200));
+    client.set_mode(DetectionMode::Chipping);
+    let ev = wait_for(&mut client, |e| {
+        matches!(e, BinaryEvent::SettingsApplied | BinaryEvent::SettingsRolledBack(_))
+    })?;
+    assert!(matches!(ev, BinaryEvent::SettingsRolledBack(ConnError::Timeout)));
+    assert_eq!(device.mode(), MODE_OUTDOOR);
+    assert!(device.is_armed());
+    assert!(client.is_armed());
+    Ok(())
+}
+
+#[test]
+fn apply_settings_rolls_back_on_nack() -> Result<(), ConnError> {
+    let device = SimDevice::new();
+    let stream = DropModeSet { inner: device.clone(), mode: MODE_CHIPPING, nack: true };
+    let mut client = BinaryClient::new(BinaryConnection::new(stream));
+    client.handshake();
+    client.apply_settings(settings());
+    wait_for(&mut client, |e| matches!(e, BinaryEvent::SettingsApplied))?;
+
+    // The NACK fails the change at once, well inside the op timeout.
+    client.set_operation_timeout(Duration::from_secs(5));
+    let start = Instant::now();
+    client.set_mode(DetectionMode::Chipping);
+    let ev = wait_for(&mut client, |e| {
+        matches!(e, BinaryEvent::SettingsApplied | BinaryEvent::SettingsRolledBack(_))
+    })?;
+    assert!(start.elapsed() < Duration::from_secs(1));
+    assert!(matches!(ev, BinaryEvent::SettingsRolledBack(ConnError::Protocol(_))));
+    assert_eq!(device.mode(), MODE_OUTDOOR);
+    assert!(device.is_armed());
+    Ok(())
+}
+
+#[test]
+fn apply_settings_without_previous_settings_reports_failure() -> Result<(), ConnError> {
+    let device = SimDevice::new();
+    let stream = DropModeSet { inner: device.clone(), mode: MODE_CHIPPING, nack: true };
+    let mut client = BinaryClient::new(BinaryConnection::new(stream));
+    client.handshake();
+    client.apply_settings(AvrSettings { mode: MODE_CHIPPING, ..settings() });
+    let ev = wait_for(&mut client, |e| {
+        matches!(
+            e,
+            BinaryEvent::SettingsApplied
+                | BinaryEvent::SettingsRolledBack(_)
+                | BinaryEvent::SettingsFailed(_)
+        )
+    })?;
+    assert!(matches!(ev, BinaryEvent::SettingsFailed(ConnError::Protocol(_))));
+    assert!(device.is_armed());
+    assert!(client.is_armed());
+    Ok(())
+}
+
+#[test]
+fn discover_identifies_sim_device() {
+    let server = SimServer::spawn(
+        "127.0.0.1:0",
+        SimDevice::with_generation(DeviceGen::Gen2).with_network("BAY7", "secret"),
+    )
+    .unwrap();
+    let options = DiscoverOptions { port: server.local_addr().port(), ..DiscoverOptions::default() };
+    // Nothing listens on 127.0.0.2, so its probe is refused.
+    let hosts = ["127.0.0.2".parse().unwrap(), "127.0.0.1".parse().unwrap()];
+    let found = discover(&hosts, &options);
+    assert_eq!(found.len(), 1);
+    let dev = &found[0];
+    assert_eq!(dev.addr, server.local_addr());
+    assert_eq!(dev.ssid, "BAY7");
+    assert_eq!(dev.generation, DeviceGen::Gen2);
+    assert_eq!(dev.firmware, FirmwareVersion::parse("BM17.04"));
+    assert!(probe(hosts[0], &options).is_err());
+}
+
+/// Sim stream that answers one NetConfig sub-query (0x00 SSID, 0x08
+/// password) with `text` in place of the sim's own reply.
+struct NetConfigAnswered {
+    inner: SimDevice,
+    sub_query: u8,
+    text: &'static str,
+}
+
+impl std::io::Read for NetConfigAnswered {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+impl std::io::Write for NetConfigAnswered {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if let Ok(frame) = RawFrame::parse(buf)
+            && frame.type_id == TYPE_NET_CONFIG
+            && frame.payload == [0x01, self.sub_query]
+        {
+            let resp = NetConfigResp { text: self.text.into() };
+            self.inner.inject(BusAddr::Pi, TYPE_NET_CONFIG, resp.encode());
+            return Ok(buf.len());
+        }
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[test]
+fn identify_skips_password_query_when_ssid_answers() -> Result<(), ConnError> {
+    let device = SimDevice::new().with_network("BAY7", "secret");
+    let answered = NetConfigAnswered { inner: device, sub_query: 0x00, text: "BAY7" };
+    let mut conn = BinaryConnection::new(answered);
+    let sent = Rc::new(RefCell::new(Vec::new()));
+    let log = Rc::clone(&sent);
+    conn.set_on_send(move |cmd, _| log.borrow_mut().push(cmd.clone()));
+    let (mut seq, actions) = IdentifySequencer::new();
+    drive(&mut conn, &mut seq, actions, Instant::now() + DEADLINE)?;
+    assert_eq!(seq.into_result().ssid, "BAY7");
+    let sent = sent.borrow();
+    assert!(sent.iter().any(|c| matches!(c, Command::NetConfigReq(r) if !r.query_password)));
+    assert!(!sent.iter().any(|c| matches!(c, Command::NetConfigReq(r) if r.query_password)));
+    Ok(())
+}
+
+#[test]
+fn identify_ignores_password_when_ssid_slot_is_empty() -> Result<(), ConnError> {
+    let device = SimDevice::new().with_network("BAY7", "secret");
+    let answered = NetConfigAnswered { inner: device, sub_query: 0x08, text: "\0secret" };
+    let mut conn = BinaryConnection::new(answered);
+    let (mut seq, actions) = IdentifySequencer::new();
+    drive(&mut conn, &mut seq, actions, Instant::now() + DEADLINE)?;
+    assert_eq!(seq.into_result().ssid, "");
+    Ok(())
+}
+
+#[test]
+fn wifi_scan_reads_every_page() -> Result<(), ConnError> {
+    let raw: Vec<Vec<u8>> = (0..3u8).map(|i| vec![0x03, 0x00, i, 0x00]).collect();
+    let device = SimDevice::new().with_wifi_scan_pages(raw.clone());
+    let mut client = BinaryClient::new(BinaryConnection::new(device.clone()));
+    client.scan_wifi();
+    let ev = wait_for(&mut client, |e| matches!(e, BinaryEvent::WifiScan(_)))?;
+    let BinaryEvent::WifiScan(pages) = ev else { unreachable!() };
+    assert_eq!(pages.len(), usize::from(WIFI_SCAN_PAGES));
+    let payloads: Vec<_> = pages.iter().map(|p| p.payload.clone()).collect();
+    assert_eq!(payloads[..3], raw);
+    assert!(payloads[3..].iter().all(Vec::is_empty));
+    Ok(())
+}
+
+#[test]
+fn handshake_decodes_device_info() -> Result<(), ConnError> {
+    let device = SimDevice::new();
+    let mut client = BinaryClient::new(BinaryConnection::new(device.clone()));
+    client.handshake();
+    let ev = wait_for(&mut client, |e| matches!(e, BinaryEvent::Handshake(_)))?;
+    let BinaryEvent::Handshake(h) = ev else { unreachable!() };
+
+    let dsp = h.dsp.info();
+    assert_eq!(dsp.firmware.unwrap().numbers, [2, 4, 1]);
+    assert_eq!(dsp.serial.as_deref(), Some("012345"));
+    assert_eq!(dsp.tag.as_deref(), Some("REL"));
+
+    let avr = h.avr.info();
+    assert_eq!(avr.model.as_deref(), Some("MEVO+"));
+    assert_eq!(avr.build.unwrap().to_string(), "2024-01-12 10:31:07");
+
+    let pi = h.pi.info();
+    assert_eq!(pi.firmware.as_ref().map(|v| v.as_str()), Some("BM17.04"));
+    assert_eq!(CamConfig::fusion_preset_for(pi.firmware.as_ref()).framerate, 180);
+
+    let product = h.dsp.product_info();
+    assert_eq!(product.pi_hardware, "RPI-3B+");
+    assert_eq!(product.camera_model, "IMX219");
+    Ok(())
+}
+
+#[test]
+fn capabilities_follow_generation() -> Result<(), ConnError> {
+    for generation in [DeviceGen::MevoPlus, DeviceGen::Gen2] {
+        let device = SimDevice::with_generation(generation);
+        let mut client = BinaryClient::new(BinaryConnection::new(device.clone()));
+        assert!(client.capabilities().is_none());
+        client.handshake();
+        wait_for(&mut client, |e| matches!(e, BinaryEvent::Handshake(_)))?;
+
+        let caps = client.capabilities().expect("capabilities after handshake");
+        let gen2 = generation == DeviceGen::Gen2;
+        assert_eq!(caps.generation, generation);
+        assert_eq!(caps.avr_config_version, if gen2 { 2 } else { 1 });
+        assert_eq!(caps.power_status, !gen2);
+        assert_eq!(caps.post_shot_config_query, !gen2);
+        assert_eq!(caps.dsp_debug, gen2);
+        assert_eq!(caps.drain_timeout, DRAIN_TIMEOUT);
+        assert_eq!(caps.pi_firmware, FirmwareVersion::parse("BM17.04"));
+        assert!(caps.raw_fusion);
+        assert_eq!(caps.fusion_preset().raw_camera_mode, 7);
+    }
+    Ok(())
+}